@@ -0,0 +1,138 @@
+use std::fmt;
+
+use serde::Deserialize;
+
+use super::function::StateMutability;
+
+/// An entry of a JSON ABI, e.g. of an ERC standard, that a contract is
+/// checked against by `fe check --conforms-to`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct AbiEntry {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub inputs: Vec<AbiParam>,
+    #[serde(default)]
+    pub outputs: Vec<AbiParam>,
+    /// Missing in ABIs emitted by old versions of solc, in which case the
+    /// mutability isn't checked.
+    #[serde(rename = "stateMutability", default)]
+    pub state_mutability: Option<StateMutability>,
+    #[serde(default)]
+    pub anonymous: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct AbiParam {
+    #[serde(rename = "type")]
+    pub ty: String,
+    #[serde(default)]
+    pub components: Vec<AbiParam>,
+    #[serde(default)]
+    pub indexed: bool,
+}
+
+impl AbiEntry {
+    /// Returns the signature that the selector or topic of the entry is a
+    /// hash of, e.g. `transfer(address,uint256)`.
+    pub fn signature(&self) -> String {
+        format!("{}({})", self.name, type_names(&self.inputs).join(","))
+    }
+
+    pub fn output_types(&self) -> Vec<String> {
+        type_names(&self.outputs)
+    }
+}
+
+impl AbiParam {
+    /// Returns the name of the type as it appears in signatures, in which
+    /// tuples are spelled out, e.g. `(uint256,address)[]` for `tuple[]`.
+    pub fn type_name(&self) -> String {
+        match self.ty.strip_prefix("tuple") {
+            Some(dims) => format!("({}){dims}", type_names(&self.components).join(",")),
+            None => self.ty.clone(),
+        }
+    }
+}
+
+fn type_names(params: &[AbiParam]) -> Vec<String> {
+    params.iter().map(AbiParam::type_name).collect()
+}
+
+/// A way in which a contract fails to implement an entry of a JSON ABI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiMismatch {
+    Missing {
+        kind: String,
+        signature: String,
+    },
+    Mutability {
+        signature: String,
+        expected: StateMutability,
+        actual: StateMutability,
+    },
+    Outputs {
+        signature: String,
+        expected: Vec<String>,
+        actual: Vec<String>,
+    },
+    Indexed {
+        signature: String,
+        expected: Vec<bool>,
+        actual: Vec<bool>,
+    },
+    Anonymous {
+        signature: String,
+        expected: bool,
+    },
+}
+
+impl fmt::Display for AbiMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbiMismatch::Missing { kind, signature } => {
+                write!(f, "{kind} `{signature}` is missing")
+            }
+            AbiMismatch::Mutability {
+                signature,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "function `{signature}` is `{actual}`, but the ABI requires `{expected}`"
+            ),
+            AbiMismatch::Outputs {
+                signature,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "function `{signature}` returns `({})`, but the ABI requires `({})`",
+                actual.join(","),
+                expected.join(",")
+            ),
+            AbiMismatch::Indexed {
+                signature,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "the indexed fields of event `{signature}` are {actual:?}, but the ABI requires {expected:?}"
+            ),
+            AbiMismatch::Anonymous {
+                signature,
+                expected,
+            } => {
+                let not = |anonymous: bool| if anonymous { "" } else { "not " };
+                write!(
+                    f,
+                    "event `{signature}` is {}anonymous, but the ABI requires it to {}be",
+                    not(!expected),
+                    not(*expected)
+                )
+            }
+        }
+    }
+}
@@ -1,14 +1,20 @@
-use super::{event::AbiEvent, function::AbiFunction};
+use std::collections::BTreeMap;
+
+use super::{
+    conformance::{AbiEntry, AbiMismatch},
+    event::AbiEvent,
+    function::{AbiFunction, AbiFunctionType},
+};
 
 use serde::{ser::SerializeSeq, Serialize, Serializer};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AbiContract {
     /// Public functions in the contract.
-    funcs: Vec<AbiFunction>,
+    pub(crate) funcs: Vec<AbiFunction>,
 
     /// Events emitted from the contract.
-    events: Vec<AbiEvent>,
+    pub(crate) events: Vec<AbiEvent>,
 }
 
 impl Serialize for AbiContract {
@@ -30,4 +36,372 @@ impl AbiContract {
     pub fn new(funcs: Vec<AbiFunction>, events: Vec<AbiEvent>) -> Self {
         Self { funcs, events }
     }
+
+    /// Returns the selectors of the contract's public functions, the topic
+    /// hashes of its events and the selectors of the given `errors`.
+    pub fn selectors(&self, errors: &[AbiFunction]) -> AbiSelectors {
+        let selector_entry = |func: &AbiFunction| {
+            let selector = func.selector();
+            (
+                selector.selector_signature().to_string(),
+                format!("0x{}", selector.hex()),
+            )
+        };
+
+        let functions = self
+            .funcs
+            .iter()
+            .filter(|func| func.func_type() == AbiFunctionType::Function)
+            .map(selector_entry)
+            .collect();
+        let events = self
+            .events
+            .iter()
+            .map(|event| {
+                let sig = event.signature();
+                (sig.signature().to_string(), format!("0x{}", sig.hash_hex()))
+            })
+            .collect();
+        let errors = errors.iter().map(selector_entry).collect();
+
+        AbiSelectors {
+            functions,
+            events,
+            errors,
+        }
+    }
+}
+
+impl AbiContract {
+    /// Returns the ways in which the contract, which may revert with the
+    /// given `errors`, fails to implement the functions, events and errors of
+    /// the `expected` JSON ABI.
+    ///
+    /// Constructors, fallback and receive functions aren't part of the
+    /// interface of a deployed contract and are ignored.
+    pub fn conformance_mismatches(
+        &self,
+        errors: &[AbiFunction],
+        expected: &[AbiEntry],
+    ) -> Vec<AbiMismatch> {
+        let mut mismatches = vec![];
+        for entry in expected {
+            let signature = entry.signature();
+            let missing = || AbiMismatch::Missing {
+                kind: entry.kind.clone(),
+                signature: signature.clone(),
+            };
+
+            match entry.kind.as_str() {
+                "function" => {
+                    let func = self.funcs.iter().find(|func| {
+                        func.func_type() == AbiFunctionType::Function
+                            && func.selector().selector_signature() == signature
+                    });
+                    let func = match func {
+                        Some(func) => func,
+                        None => {
+                            mismatches.push(missing());
+                            continue;
+                        }
+                    };
+
+                    if let Some(expected) = &entry.state_mutability {
+                        let actual = func.state_mutability();
+                        if !actual.satisfies(expected) {
+                            mismatches.push(AbiMismatch::Mutability {
+                                signature: signature.clone(),
+                                expected: expected.clone(),
+                                actual: actual.clone(),
+                            });
+                        }
+                    }
+
+                    let (expected, actual) = (entry.output_types(), func.output_types());
+                    if expected != actual {
+                        mismatches.push(AbiMismatch::Outputs {
+                            signature,
+                            expected,
+                            actual,
+                        });
+                    }
+                }
+                "event" => {
+                    let event = self
+                        .events
+                        .iter()
+                        .find(|event| event.signature().signature() == signature);
+                    let event = match event {
+                        Some(event) => event,
+                        None => {
+                            mismatches.push(missing());
+                            continue;
+                        }
+                    };
+
+                    let expected: Vec<_> = entry.inputs.iter().map(|input| input.indexed).collect();
+                    let actual: Vec<_> = event.inputs.iter().map(|input| input.indexed).collect();
+                    if expected != actual {
+                        mismatches.push(AbiMismatch::Indexed {
+                            signature: signature.clone(),
+                            expected,
+                            actual,
+                        });
+                    }
+
+                    if entry.anonymous != event.anonymous {
+                        mismatches.push(AbiMismatch::Anonymous {
+                            signature,
+                            expected: entry.anonymous,
+                        });
+                    }
+                }
+                "error"
+                    if !errors
+                        .iter()
+                        .any(|error| error.selector().selector_signature() == signature) =>
+                {
+                    mismatches.push(missing());
+                }
+                _ => {}
+            }
+        }
+
+        mismatches
+    }
+}
+
+/// Function and error selectors and event topics of a contract, keyed by
+/// their signatures.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AbiSelectors {
+    pub functions: BTreeMap<String, String>,
+    pub events: BTreeMap<String, String>,
+    pub errors: BTreeMap<String, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        conformance::AbiParam, event::AbiEventField, function::StateMutability, types::AbiType,
+    };
+
+    #[test]
+    fn contract_selectors() {
+        let constructor = AbiFunction::new(
+            AbiFunctionType::Constructor,
+            "".into(),
+            vec![],
+            None,
+            StateMutability::Payable,
+        );
+        let transfer = AbiFunction::new(
+            AbiFunctionType::Function,
+            "transfer".into(),
+            vec![
+                ("to".into(), AbiType::Address),
+                ("value".into(), AbiType::UInt(256)),
+            ],
+            Some(AbiType::Bool),
+            StateMutability::Payable,
+        );
+        let event = AbiEvent::new(
+            "Transfer".into(),
+            vec![
+                AbiEventField::new("from".into(), AbiType::Address, true),
+                AbiEventField::new("to".into(), AbiType::Address, true),
+                AbiEventField::new("value".into(), AbiType::UInt(256), false),
+            ],
+            false,
+        );
+        let error = AbiFunction::new(
+            AbiFunctionType::Function,
+            "Unauthorized".into(),
+            vec![],
+            None,
+            StateMutability::Pure,
+        );
+
+        let contract = AbiContract::new(vec![constructor, transfer], vec![event]);
+        let selectors = contract.selectors(&[error]);
+
+        assert_eq!(
+            selectors.functions,
+            BTreeMap::from([(
+                "transfer(address,uint256)".to_string(),
+                "0xa9059cbb".to_string()
+            )])
+        );
+        assert_eq!(
+            selectors.events,
+            BTreeMap::from([(
+                "Transfer(address,address,uint256)".to_string(),
+                "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef".to_string()
+            )])
+        );
+        assert_eq!(
+            selectors.errors,
+            BTreeMap::from([("Unauthorized()".to_string(), "0x82b42900".to_string())])
+        );
+    }
+
+    fn param(ty: &str, indexed: bool) -> AbiParam {
+        AbiParam {
+            ty: ty.into(),
+            components: vec![],
+            indexed,
+        }
+    }
+
+    fn entry(
+        kind: &str,
+        name: &str,
+        inputs: Vec<AbiParam>,
+        outputs: Vec<AbiParam>,
+        state_mutability: Option<StateMutability>,
+    ) -> AbiEntry {
+        AbiEntry {
+            kind: kind.into(),
+            name: name.into(),
+            inputs,
+            outputs,
+            state_mutability,
+            anonymous: false,
+        }
+    }
+
+    #[test]
+    fn contract_conformance() {
+        let balance_of = AbiFunction::new(
+            AbiFunctionType::Function,
+            "balanceOf".into(),
+            vec![("owner".into(), AbiType::Address)],
+            Some(AbiType::UInt(256)),
+            StateMutability::Payable,
+        );
+        let transfer = AbiFunction::new(
+            AbiFunctionType::Function,
+            "transfer".into(),
+            vec![
+                ("to".into(), AbiType::Address),
+                ("value".into(), AbiType::UInt(256)),
+            ],
+            None,
+            StateMutability::Payable,
+        );
+        let event = AbiEvent::new(
+            "Transfer".into(),
+            vec![
+                AbiEventField::new("from".into(), AbiType::Address, true),
+                AbiEventField::new("to".into(), AbiType::Address, false),
+                AbiEventField::new("value".into(), AbiType::UInt(256), false),
+            ],
+            false,
+        );
+        let error = AbiFunction::new(
+            AbiFunctionType::Function,
+            "Unauthorized".into(),
+            vec![],
+            None,
+            StateMutability::Pure,
+        );
+        let contract = AbiContract::new(vec![balance_of, transfer], vec![event]);
+
+        let expected = vec![
+            entry(
+                "constructor",
+                "",
+                vec![],
+                vec![],
+                Some(StateMutability::Nonpayable),
+            ),
+            entry(
+                "function",
+                "balanceOf",
+                vec![param("address", false)],
+                vec![param("uint256", false)],
+                Some(StateMutability::View),
+            ),
+            entry(
+                "function",
+                "transfer",
+                vec![param("address", false), param("uint256", false)],
+                vec![param("bool", false)],
+                Some(StateMutability::Nonpayable),
+            ),
+            entry(
+                "function",
+                "approve",
+                vec![param("address", false), param("uint256", false)],
+                vec![param("bool", false)],
+                Some(StateMutability::Nonpayable),
+            ),
+            entry(
+                "event",
+                "Transfer",
+                vec![
+                    param("address", true),
+                    param("address", true),
+                    param("uint256", false),
+                ],
+                vec![],
+                None,
+            ),
+            entry("error", "Unauthorized", vec![], vec![], None),
+            entry(
+                "error",
+                "InsufficientBalance",
+                vec![param("uint256", false)],
+                vec![],
+                None,
+            ),
+        ];
+
+        assert_eq!(
+            contract.conformance_mismatches(&[error], &expected),
+            vec![
+                AbiMismatch::Mutability {
+                    signature: "balanceOf(address)".into(),
+                    expected: StateMutability::View,
+                    actual: StateMutability::Payable,
+                },
+                AbiMismatch::Outputs {
+                    signature: "transfer(address,uint256)".into(),
+                    expected: vec!["bool".into()],
+                    actual: vec![],
+                },
+                AbiMismatch::Missing {
+                    kind: "function".into(),
+                    signature: "approve(address,uint256)".into(),
+                },
+                AbiMismatch::Indexed {
+                    signature: "Transfer(address,address,uint256)".into(),
+                    expected: vec![true, true, false],
+                    actual: vec![true, false, false],
+                },
+                AbiMismatch::Missing {
+                    kind: "error".into(),
+                    signature: "InsufficientBalance(uint256)".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn tuple_signatures() {
+        let entry = entry(
+            "function",
+            "submit",
+            vec![AbiParam {
+                ty: "tuple[]".into(),
+                components: vec![param("uint256", false), param("address", false)],
+                indexed: false,
+            }],
+            vec![],
+            None,
+        );
+        assert_eq!(entry.signature(), "submit((uint256,address)[])");
+    }
 }
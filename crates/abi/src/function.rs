@@ -1,11 +1,13 @@
 use fe_common::utils::keccak;
 
-use serde::Serialize;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
 
 use super::types::AbiType;
 
 /// The mutability of a public function.
-#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum StateMutability {
     Pure,
@@ -46,15 +48,43 @@ impl StateMutability {
             (SelfParam::Mut, _) => StateMutability::Payable,
         }
     }
+
+    /// Returns `true` if a function with this mutability can stand in for
+    /// one with the `expected` mutability.
+    ///
+    /// Fe marks every function that takes `mut self` as payable, so a
+    /// payable function satisfies a nonpayable one.
+    pub fn satisfies(&self, expected: &StateMutability) -> bool {
+        match expected {
+            StateMutability::Pure => *self == StateMutability::Pure,
+            StateMutability::View => {
+                matches!(self, StateMutability::Pure | StateMutability::View)
+            }
+            StateMutability::Nonpayable => true,
+            StateMutability::Payable => *self == StateMutability::Payable,
+        }
+    }
+}
+
+impl fmt::Display for StateMutability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            StateMutability::Pure => "pure",
+            StateMutability::View => "view",
+            StateMutability::Nonpayable => "nonpayable",
+            StateMutability::Payable => "payable",
+        };
+        write!(f, "{name}")
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct AbiFunction {
     #[serde(rename = "type")]
     func_type: AbiFunctionType,
-    name: String,
-    inputs: Vec<AbiFunctionParamInner>,
-    outputs: Vec<AbiFunctionParamInner>,
+    pub(crate) name: String,
+    pub(crate) inputs: Vec<AbiFunctionParamInner>,
+    pub(crate) outputs: Vec<AbiFunctionParamInner>,
     #[serde(rename = "stateMutability")]
     state_mutability: StateMutability,
 }
@@ -87,6 +117,22 @@ impl AbiFunction {
     pub fn selector(&self) -> AbiFunctionSelector {
         AbiFunctionSelector::new(self)
     }
+
+    pub fn func_type(&self) -> AbiFunctionType {
+        self.func_type
+    }
+
+    pub fn state_mutability(&self) -> &StateMutability {
+        &self.state_mutability
+    }
+
+    /// Returns the names of the output types as they appear in signatures.
+    pub fn output_types(&self) -> Vec<String> {
+        self.outputs
+            .iter()
+            .map(|param| param.ty.selector_type_name())
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
@@ -136,10 +182,10 @@ impl AbiFunctionSelector {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
-struct AbiFunctionParamInner {
-    name: String,
+pub(crate) struct AbiFunctionParamInner {
+    pub(crate) name: String,
     #[serde(flatten)]
-    ty: AbiType,
+    pub(crate) ty: AbiType,
 }
 
 impl AbiFunctionParamInner {
@@ -1,4 +1,9 @@
+pub mod conformance;
 pub mod contract;
 pub mod event;
 pub mod function;
+pub mod natspec;
+pub mod public_api;
+pub mod storage;
 pub mod types;
+pub mod typescript;
@@ -0,0 +1,294 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+/// The tags of a doc comment, in the NatSpec format of Solidity.
+///
+/// Lines before the first tag are part of the `@notice`, and lines without a
+/// tag continue the tag before them. Unknown tags are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Natspec {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub notice: Option<String>,
+    pub dev: Option<String>,
+    pub params: BTreeMap<String, String>,
+    pub returns: Vec<String>,
+}
+
+enum Tag {
+    Title,
+    Author,
+    Notice,
+    Dev,
+    Param(String),
+    Return,
+    Unknown,
+}
+
+impl Natspec {
+    pub fn parse(doc: &str) -> Self {
+        let mut natspec = Natspec::default();
+        let mut tag = Tag::Notice;
+        for line in doc.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let text = match line.strip_prefix('@') {
+                Some(tagged) => {
+                    let (name, text) = split_word(tagged);
+                    let (new_tag, text) = match name {
+                        "title" => (Tag::Title, text),
+                        "author" => (Tag::Author, text),
+                        "notice" => (Tag::Notice, text),
+                        "dev" => (Tag::Dev, text),
+                        "param" => {
+                            let (param, text) = split_word(text);
+                            natspec.params.entry(param.into()).or_default();
+                            (Tag::Param(param.into()), text)
+                        }
+                        "return" => {
+                            natspec.returns.push(String::new());
+                            (Tag::Return, text)
+                        }
+                        _ => (Tag::Unknown, text),
+                    };
+                    tag = new_tag;
+                    text
+                }
+                None => line,
+            };
+            natspec.append(&tag, text);
+        }
+
+        natspec
+    }
+
+    fn append(&mut self, tag: &Tag, text: &str) {
+        let content = match tag {
+            Tag::Title => self.title.get_or_insert_with(String::new),
+            Tag::Author => self.author.get_or_insert_with(String::new),
+            Tag::Notice => self.notice.get_or_insert_with(String::new),
+            Tag::Dev => self.dev.get_or_insert_with(String::new),
+            Tag::Param(name) => self.params.get_mut(name).unwrap(),
+            Tag::Return => self.returns.last_mut().unwrap(),
+            Tag::Unknown => return,
+        };
+        if !content.is_empty() && !text.is_empty() {
+            content.push(' ');
+        }
+        content.push_str(text);
+    }
+}
+
+fn split_word(text: &str) -> (&str, &str) {
+    match text.split_once(char::is_whitespace) {
+        Some((word, rest)) => (word, rest.trim_start()),
+        None => (text, ""),
+    }
+}
+
+/// The documentation of a contract for its users, in the `userdoc` format of
+/// solc.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UserDoc {
+    kind: &'static str,
+    version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notice: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    methods: BTreeMap<String, UserDocEntry>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    events: BTreeMap<String, UserDocEntry>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    errors: BTreeMap<String, Vec<UserDocEntry>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UserDocEntry {
+    notice: String,
+}
+
+/// The documentation of a contract for its developers, in the `devdoc`
+/// format of solc.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DevDoc {
+    kind: &'static str,
+    version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    methods: BTreeMap<String, DevDocEntry>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    events: BTreeMap<String, DevDocEntry>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    errors: BTreeMap<String, Vec<DevDocEntry>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DevDocEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    params: BTreeMap<String, String>,
+    /// Keyed by `_0`, `_1`, etc., since return values aren't named.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    returns: BTreeMap<String, String>,
+}
+
+/// The `userdoc` and `devdoc` of a contract, which block explorers and
+/// wallets display to users.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NatspecDocs {
+    pub userdoc: UserDoc,
+    pub devdoc: DevDoc,
+}
+
+impl NatspecDocs {
+    pub fn new(contract: &Natspec) -> Self {
+        Self {
+            userdoc: UserDoc {
+                kind: "user",
+                version: 1,
+                notice: contract.notice.clone(),
+                methods: BTreeMap::new(),
+                events: BTreeMap::new(),
+                errors: BTreeMap::new(),
+            },
+            devdoc: DevDoc {
+                kind: "dev",
+                version: 1,
+                title: contract.title.clone(),
+                author: contract.author.clone(),
+                details: contract.dev.clone(),
+                methods: BTreeMap::new(),
+                events: BTreeMap::new(),
+                errors: BTreeMap::new(),
+            },
+        }
+    }
+
+    /// Adds the documentation of the function with the given signature, or
+    /// of the constructor if the signature is `constructor`.
+    pub fn add_method(&mut self, signature: &str, natspec: &Natspec) {
+        if let Some(entry) = user_entry(natspec) {
+            self.userdoc.methods.insert(signature.into(), entry);
+        }
+        if let Some(entry) = dev_entry(natspec) {
+            self.devdoc.methods.insert(signature.into(), entry);
+        }
+    }
+
+    pub fn add_event(&mut self, signature: &str, natspec: &Natspec) {
+        if let Some(entry) = user_entry(natspec) {
+            self.userdoc.events.insert(signature.into(), entry);
+        }
+        if let Some(entry) = dev_entry(natspec) {
+            self.devdoc.events.insert(signature.into(), entry);
+        }
+    }
+
+    pub fn add_error(&mut self, signature: &str, natspec: &Natspec) {
+        if let Some(entry) = user_entry(natspec) {
+            self.userdoc.errors.insert(signature.into(), vec![entry]);
+        }
+        if let Some(entry) = dev_entry(natspec) {
+            self.devdoc.errors.insert(signature.into(), vec![entry]);
+        }
+    }
+}
+
+fn user_entry(natspec: &Natspec) -> Option<UserDocEntry> {
+    natspec.notice.as_ref().map(|notice| UserDocEntry {
+        notice: notice.clone(),
+    })
+}
+
+fn dev_entry(natspec: &Natspec) -> Option<DevDocEntry> {
+    let entry = DevDocEntry {
+        details: natspec.dev.clone(),
+        params: natspec.params.clone(),
+        returns: natspec
+            .returns
+            .iter()
+            .enumerate()
+            .map(|(idx, text)| (format!("_{idx}"), text.clone()))
+            .collect(),
+    };
+    if entry.details.is_none() && entry.params.is_empty() && entry.returns.is_empty() {
+        None
+    } else {
+        Some(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tags() {
+        let natspec = Natspec::parse(
+            "Transfers tokens\n\
+             to another account.\n\
+             @dev Reverts with `InsufficientBalance`.\n\
+             @param to The recipient\n\
+             @param value The amount,\n  in wei\n\
+             @custom:security ignored\n\
+             @return Whether the transfer succeeded",
+        );
+
+        assert_eq!(
+            natspec.notice.as_deref(),
+            Some("Transfers tokens to another account.")
+        );
+        assert_eq!(
+            natspec.dev.as_deref(),
+            Some("Reverts with `InsufficientBalance`.")
+        );
+        assert_eq!(
+            natspec.params,
+            BTreeMap::from([
+                ("to".to_string(), "The recipient".to_string()),
+                ("value".to_string(), "The amount, in wei".to_string()),
+            ])
+        );
+        assert_eq!(natspec.returns, vec!["Whether the transfer succeeded"]);
+        assert_eq!(natspec.title, None);
+    }
+
+    #[test]
+    fn contract_docs() {
+        let mut docs = NatspecDocs::new(&Natspec::parse("A simple token\n@title Token"));
+        docs.add_method(
+            "transfer(address,uint256)",
+            &Natspec::parse("Transfers tokens\n@return Whether it succeeded"),
+        );
+        docs.add_event("Transfer(address,address,uint256)", &Natspec::default());
+        docs.add_error(
+            "Unauthorized(address)",
+            &Natspec::parse("@dev Not the owner"),
+        );
+
+        let userdoc = &docs.userdoc;
+        assert_eq!(userdoc.notice.as_deref(), Some("A simple token"));
+        assert_eq!(
+            userdoc.methods["transfer(address,uint256)"].notice,
+            "Transfers tokens"
+        );
+        assert!(userdoc.events.is_empty());
+        assert!(userdoc.errors.is_empty());
+
+        let devdoc = &docs.devdoc;
+        assert_eq!(devdoc.title.as_deref(), Some("Token"));
+        assert_eq!(
+            devdoc.methods["transfer(address,uint256)"].returns,
+            BTreeMap::from([("_0".to_string(), "Whether it succeeded".to_string())])
+        );
+        assert_eq!(
+            devdoc.errors["Unauthorized(address)"][0].details.as_deref(),
+            Some("Not the owner")
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The public items of an ingot, as emitted by `fe public-api`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicApi {
+    pub ingot: String,
+    pub items: Vec<PublicItem>,
+}
+
+/// A public item and its signature.
+///
+/// `path` is relative to the root module of the ingot, e.g. `foo::Bar::new`.
+/// The signature includes everything that a user of the item may depend on,
+/// e.g. the variants of an enum or the functions of a trait.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicItem {
+    pub path: String,
+    pub kind: String,
+    pub signature: String,
+}
+
+/// A change between two versions of an ingot's public API that may break
+/// code that depends on the old version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiChange {
+    Removed {
+        path: String,
+        kind: String,
+    },
+    Changed {
+        path: String,
+        old: String,
+        new: String,
+    },
+}
+
+impl PublicApi {
+    pub fn new(ingot: String, items: Vec<PublicItem>) -> Self {
+        Self { ingot, items }
+    }
+
+    /// Returns the changes from `self` to `new` that may break users of the
+    /// ingot. Items that are added in `new` are allowed.
+    pub fn breaking_changes(&self, new: &PublicApi) -> Vec<ApiChange> {
+        let mut changes = vec![];
+        for old_item in &self.items {
+            match new
+                .items
+                .iter()
+                .find(|item| item.path == old_item.path && item.kind == old_item.kind)
+            {
+                None => changes.push(ApiChange::Removed {
+                    path: old_item.path.clone(),
+                    kind: old_item.kind.clone(),
+                }),
+                Some(new_item) if new_item.signature != old_item.signature => {
+                    changes.push(ApiChange::Changed {
+                        path: old_item.path.clone(),
+                        old: old_item.signature.clone(),
+                        new: new_item.signature.clone(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        changes
+    }
+}
+
+impl fmt::Display for ApiChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiChange::Removed { path, kind } => write!(f, "{kind} `{path}` was removed"),
+            ApiChange::Changed { path, old, new } => {
+                write!(f, "`{path}` changed from `{old}` to `{new}`")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(path: &str, kind: &str, signature: &str) -> PublicItem {
+        PublicItem {
+            path: path.into(),
+            kind: kind.into(),
+            signature: signature.into(),
+        }
+    }
+
+    #[test]
+    fn added_items_are_compatible() {
+        let old = PublicApi::new("lib".into(), vec![item("foo", "fn", "fn foo()")]);
+        let new = PublicApi::new(
+            "lib".into(),
+            vec![item("foo", "fn", "fn foo()"), item("bar", "fn", "fn bar()")],
+        );
+
+        assert!(old.breaking_changes(&new).is_empty());
+    }
+
+    #[test]
+    fn changed_signature() {
+        let old = PublicApi::new("lib".into(), vec![item("E", "enum", "enum E { A, B }")]);
+        let new = PublicApi::new("lib".into(), vec![item("E", "enum", "enum E { A, B, C }")]);
+
+        let changes = old.breaking_changes(&new);
+        assert_eq!(
+            changes,
+            vec![ApiChange::Changed {
+                path: "E".into(),
+                old: "enum E { A, B }".into(),
+                new: "enum E { A, B, C }".into(),
+            }]
+        );
+        assert_eq!(
+            changes[0].to_string(),
+            "`E` changed from `enum E { A, B }` to `enum E { A, B, C }`"
+        );
+    }
+
+    #[test]
+    fn removed_item() {
+        let old = PublicApi::new(
+            "lib".into(),
+            vec![item("foo::Bar", "struct", "struct Bar { .. }")],
+        );
+        let new = PublicApi::new("lib".into(), vec![]);
+
+        let changes = old.breaking_changes(&new);
+        assert_eq!(
+            changes,
+            vec![ApiChange::Removed {
+                path: "foo::Bar".into(),
+                kind: "struct".into(),
+            }]
+        );
+        assert_eq!(changes[0].to_string(), "struct `foo::Bar` was removed");
+    }
+}
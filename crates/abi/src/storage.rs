@@ -0,0 +1,171 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The storage layout of a contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageLayout {
+    pub storage: Vec<StorageField>,
+}
+
+/// The location of a contract field in storage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The storage slot that the field starts in.
+    pub slot: usize,
+    /// The byte offset of the field within its first slot.
+    pub offset: usize,
+    /// The number of bytes the field occupies.
+    pub size: usize,
+}
+
+/// A change between two storage layouts that breaks the storage of an
+/// already deployed contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageLayoutChange {
+    Removed {
+        name: String,
+    },
+    Moved {
+        name: String,
+        old: (usize, usize),
+        new: (usize, usize),
+    },
+    TypeChanged {
+        name: String,
+        old: String,
+        new: String,
+    },
+}
+
+impl StorageLayout {
+    pub fn new(storage: Vec<StorageField>) -> Self {
+        Self { storage }
+    }
+
+    /// Returns the changes from `self` to `new` that would corrupt the
+    /// storage of a contract that is upgraded from `self` to `new`.
+    /// Fields that are appended after the existing fields are allowed.
+    pub fn breaking_changes(&self, new: &StorageLayout) -> Vec<StorageLayoutChange> {
+        let mut changes = vec![];
+        for old_field in &self.storage {
+            let new_field = match new.storage.iter().find(|f| f.name == old_field.name) {
+                Some(field) => field,
+                None => {
+                    changes.push(StorageLayoutChange::Removed {
+                        name: old_field.name.clone(),
+                    });
+                    continue;
+                }
+            };
+
+            if (old_field.slot, old_field.offset) != (new_field.slot, new_field.offset) {
+                changes.push(StorageLayoutChange::Moved {
+                    name: old_field.name.clone(),
+                    old: (old_field.slot, old_field.offset),
+                    new: (new_field.slot, new_field.offset),
+                });
+            }
+
+            if old_field.ty != new_field.ty {
+                changes.push(StorageLayoutChange::TypeChanged {
+                    name: old_field.name.clone(),
+                    old: old_field.ty.clone(),
+                    new: new_field.ty.clone(),
+                });
+            }
+        }
+
+        changes
+    }
+}
+
+impl fmt::Display for StorageLayoutChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageLayoutChange::Removed { name } => write!(f, "field `{name}` was removed"),
+            StorageLayoutChange::Moved { name, old, new } => write!(
+                f,
+                "field `{name}` moved from slot {} (offset {}) to slot {} (offset {})",
+                old.0, old.1, new.0, new.1
+            ),
+            StorageLayoutChange::TypeChanged { name, old, new } => {
+                write!(f, "field `{name}` changed type from `{old}` to `{new}`")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str, ty: &str, slot: usize, offset: usize, size: usize) -> StorageField {
+        StorageField {
+            name: name.into(),
+            ty: ty.into(),
+            slot,
+            offset,
+            size,
+        }
+    }
+
+    #[test]
+    fn appended_fields_are_compatible() {
+        let old = StorageLayout::new(vec![field("a", "u256", 0, 0, 32)]);
+        let new = StorageLayout::new(vec![
+            field("a", "u256", 0, 0, 32),
+            field("b", "u8", 1, 0, 1),
+        ]);
+
+        assert!(old.breaking_changes(&new).is_empty());
+    }
+
+    #[test]
+    fn inserted_field_moves_following_fields() {
+        let old = StorageLayout::new(vec![
+            field("a", "u256", 0, 0, 32),
+            field("b", "bool", 1, 0, 1),
+        ]);
+        let new = StorageLayout::new(vec![
+            field("a", "u256", 0, 0, 32),
+            field("c", "u8", 1, 0, 1),
+            field("b", "u8", 1, 1, 1),
+        ]);
+
+        let changes = old.breaking_changes(&new);
+        assert_eq!(
+            changes,
+            vec![
+                StorageLayoutChange::Moved {
+                    name: "b".into(),
+                    old: (1, 0),
+                    new: (1, 1),
+                },
+                StorageLayoutChange::TypeChanged {
+                    name: "b".into(),
+                    old: "bool".into(),
+                    new: "u8".into(),
+                },
+            ]
+        );
+        assert_eq!(
+            changes[0].to_string(),
+            "field `b` moved from slot 1 (offset 0) to slot 1 (offset 1)"
+        );
+    }
+
+    #[test]
+    fn removed_field() {
+        let old = StorageLayout::new(vec![field("a", "u256", 0, 0, 32)]);
+        let new = StorageLayout::new(vec![]);
+
+        assert_eq!(
+            old.breaking_changes(&new),
+            vec![StorageLayoutChange::Removed { name: "a".into() }]
+        );
+    }
+}
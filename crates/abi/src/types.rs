@@ -56,6 +56,32 @@ impl AbiType {
         }
     }
 
+    /// Returns the TypeScript type that viem decodes values of the type to.
+    pub fn typescript_type(&self) -> String {
+        match self {
+            Self::UInt(bits) | Self::Int(bits) if *bits <= 48 => "number".to_string(),
+            Self::UInt(_) | Self::Int(_) => "bigint".to_string(),
+            Self::Address | Self::Function | Self::Bytes => "`0x${string}`".to_string(),
+            Self::Bool => "boolean".to_string(),
+            Self::String => "string".to_string(),
+            Self::Array { elem_ty, .. } => {
+                if elem_ty.as_ref() == &AbiType::UInt(8) {
+                    "`0x${string}`".to_string()
+                } else {
+                    format!("readonly {}[]", elem_ty.typescript_type())
+                }
+            }
+            Self::Tuple(fields) => format!(
+                "{{ {} }}",
+                fields
+                    .iter()
+                    .map(|field| format!("{}: {}", field.name, field.ty.typescript_type()))
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ),
+        }
+    }
+
     pub fn header_size(&self) -> usize {
         match self {
             Self::UInt(_) | Self::Int(_) | Self::Address | Self::Bool | Self::Function => 32,
@@ -0,0 +1,264 @@
+//! TypeScript bindings of contracts, emitted by `fe build --emit=ts-bindings`.
+//!
+//! The ABI is exported `as const`, so that viem infers the types of calls
+//! from it and ethers accepts it as is. The types of the arguments and return
+//! values of the functions and of the fields of the events and errors are also
+//! exported, for code that doesn't go through either.
+use std::fmt::Write;
+
+use super::{
+    contract::AbiContract,
+    function::{AbiFunction, AbiFunctionParamInner, AbiFunctionType},
+};
+
+impl AbiContract {
+    /// Returns a TypeScript module with the bindings of the contract `name`,
+    /// which may revert with the given `errors`.
+    pub fn typescript_bindings(&self, name: &str, errors: &[AbiFunction]) -> String {
+        let ident = lower_camel_case(name);
+        let selectors = self.selectors(errors);
+        let mut ts = String::new();
+
+        writeln!(
+            ts,
+            "// Bindings of the `{name}` contract, generated by `fe build --emit=ts-bindings`.\n\
+             // Do not edit."
+        )
+        .unwrap();
+
+        writeln!(
+            ts,
+            "\nexport const {ident}Abi = {} as const;",
+            to_json(self)
+        )
+        .unwrap();
+
+        writeln!(
+            ts,
+            "\n/** Selectors and event topics of `{name}`, keyed by signature. */\n\
+             export const {ident}Selectors = {} as const;",
+            to_json(&selectors)
+        )
+        .unwrap();
+
+        let constructor = self
+            .funcs
+            .iter()
+            .find(|func| func.func_type() == AbiFunctionType::Constructor);
+        if let Some(constructor) = constructor {
+            writeln!(
+                ts,
+                "\n/** Arguments of the constructor of `{name}`. */\n\
+                 export type {name}ConstructorArgs = {};",
+                args_type(&constructor.inputs)
+            )
+            .unwrap();
+        }
+
+        let funcs: Vec<_> = self
+            .funcs
+            .iter()
+            .filter(|func| func.func_type() == AbiFunctionType::Function)
+            .collect();
+
+        write_type(
+            &mut ts,
+            &format!("Arguments of the public functions of `{name}`."),
+            &format!("{name}FunctionArgs"),
+            funcs.iter().map(|func| {
+                let doc = format!(
+                    "`{}`, {}",
+                    func.selector().selector_signature(),
+                    func.state_mutability()
+                );
+                (doc, func.name.as_str(), args_type(&func.inputs))
+            }),
+        );
+
+        write_type(
+            &mut ts,
+            &format!("Return values of the public functions of `{name}`."),
+            &format!("{name}FunctionReturns"),
+            funcs.iter().map(|func| {
+                let ty = match func.outputs.first() {
+                    Some(output) => output.ty.typescript_type(),
+                    None => "void".to_string(),
+                };
+                (
+                    format!("`{}`", func.selector().selector_signature()),
+                    func.name.as_str(),
+                    ty,
+                )
+            }),
+        );
+
+        write_type(
+            &mut ts,
+            &format!("Fields of the events emitted by `{name}`."),
+            &format!("{name}Events"),
+            self.events.iter().map(|event| {
+                let indexed: Vec<_> = event
+                    .inputs
+                    .iter()
+                    .filter(|field| field.indexed)
+                    .map(|field| format!("`{}`", field.name))
+                    .collect();
+                let mut doc = format!("`{}`", event.signature().signature());
+                if !indexed.is_empty() {
+                    write!(doc, ", indexed: {}", indexed.join(", ")).unwrap();
+                }
+                if event.anonymous {
+                    doc.push_str(", anonymous");
+                }
+                let fields = event
+                    .inputs
+                    .iter()
+                    .map(|field| (field.name.as_str(), field.ty.typescript_type()));
+                (doc, event.name.as_str(), object_type(fields))
+            }),
+        );
+
+        write_type(
+            &mut ts,
+            &format!("Fields of the errors `{name}` may revert with."),
+            &format!("{name}Errors"),
+            errors.iter().map(|error| {
+                let fields = error
+                    .inputs
+                    .iter()
+                    .map(|param| (param.name.as_str(), param.ty.typescript_type()));
+                (
+                    format!("`{}`", error.selector().selector_signature()),
+                    error.name.as_str(),
+                    object_type(fields),
+                )
+            }),
+        );
+
+        ts
+    }
+}
+
+/// Writes an object type with a documented property per entry.
+fn write_type<'a>(
+    ts: &mut String,
+    doc: &str,
+    name: &str,
+    entries: impl Iterator<Item = (String, &'a str, String)>,
+) {
+    writeln!(ts, "\n/** {doc} */\nexport type {name} = {{").unwrap();
+    for (doc, key, ty) in entries {
+        writeln!(ts, "  /** {doc} */\n  {key}: {ty};").unwrap();
+    }
+    ts.push_str("};\n");
+}
+
+/// Returns a labeled tuple type, e.g. `readonly [to: `0x${string}`, value: bigint]`.
+fn args_type(params: &[AbiFunctionParamInner]) -> String {
+    let args: Vec<_> = params
+        .iter()
+        .map(|param| format!("{}: {}", param.name, param.ty.typescript_type()))
+        .collect();
+    format!("readonly [{}]", args.join(", "))
+}
+
+fn object_type<'a>(fields: impl Iterator<Item = (&'a str, String)>) -> String {
+    let fields: Vec<_> = fields.map(|(name, ty)| format!("{name}: {ty}")).collect();
+    if fields.is_empty() {
+        "{}".to_string()
+    } else {
+        format!("{{ {} }}", fields.join("; "))
+    }
+}
+
+fn to_json(value: &impl serde::Serialize) -> String {
+    serde_json::to_string_pretty(value).unwrap()
+}
+
+fn lower_camel_case(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        event::{AbiEvent, AbiEventField},
+        function::StateMutability,
+        types::AbiType,
+    };
+
+    #[test]
+    fn contract_bindings() {
+        let transfer = AbiFunction::new(
+            AbiFunctionType::Function,
+            "transfer".into(),
+            vec![
+                ("to".into(), AbiType::Address),
+                ("value".into(), AbiType::UInt(256)),
+            ],
+            Some(AbiType::Bool),
+            StateMutability::Payable,
+        );
+        let event = AbiEvent::new(
+            "Transfer".into(),
+            vec![
+                AbiEventField::new("from".into(), AbiType::Address, true),
+                AbiEventField::new("value".into(), AbiType::UInt(256), false),
+            ],
+            false,
+        );
+        let error = AbiFunction::new(
+            AbiFunctionType::Function,
+            "Unauthorized".into(),
+            vec![("caller".into(), AbiType::Address)],
+            None,
+            StateMutability::Pure,
+        );
+
+        let contract = AbiContract::new(vec![transfer], vec![event]);
+        let ts = contract.typescript_bindings("Token", &[error]);
+
+        assert!(ts.contains("export const tokenAbi = [\n"));
+        assert!(ts.contains("\"0xa9059cbb\"\n  }"));
+        assert!(ts.contains(
+            "export type TokenFunctionArgs = {\n  \
+             /** `transfer(address,uint256)`, payable */\n  \
+             transfer: readonly [to: `0x${string}`, value: bigint];\n};\n"
+        ));
+        assert!(ts.contains("  transfer: boolean;\n"));
+        assert!(ts.contains(
+            "  /** `Transfer(address,uint256)`, indexed: `from` */\n  \
+             Transfer: { from: `0x${string}`; value: bigint };\n"
+        ));
+        assert!(ts.contains("  Unauthorized: { caller: `0x${string}` };\n"));
+        assert!(!ts.contains("TokenConstructorArgs"));
+    }
+
+    #[test]
+    fn typescript_types() {
+        assert_eq!(AbiType::UInt(8).typescript_type(), "number");
+        assert_eq!(AbiType::Int(128).typescript_type(), "bigint");
+        assert_eq!(
+            AbiType::Array {
+                elem_ty: Box::new(AbiType::UInt(8)),
+                len: 32
+            }
+            .typescript_type(),
+            "`0x${string}`"
+        );
+        assert_eq!(
+            AbiType::Array {
+                elem_ty: Box::new(AbiType::Bool),
+                len: 2
+            }
+            .typescript_type(),
+            "readonly boolean[]"
+        );
+    }
+}
@@ -6,6 +6,16 @@ use strum::{AsRefStr, EnumIter, EnumString};
 pub enum ValueMethod {
     ToMem,
     AbiEncode,
+    LeadingZeros,
+    CountOnes,
+}
+
+impl ValueMethod {
+    /// Returns `true` for the methods of integers, which other types can
+    /// define methods of the same name as.
+    pub fn is_integer_method(&self) -> bool {
+        matches!(self, ValueMethod::LeadingZeros | ValueMethod::CountOnes)
+    }
 }
 
 #[derive(
@@ -114,6 +124,8 @@ pub enum Intrinsic {
     __number,         // ()
     __prevrandao,     // ()
     __gaslimit,       // ()
+    __blobhash,       // (i)
+    __blobbasefee,    // ()
 }
 
 impl Intrinsic {
@@ -123,10 +135,10 @@ impl Intrinsic {
             __stop | __basefee | __origin | __gasprice | __coinbase | __timestamp | __number
             | __prevrandao | __gaslimit | __pc | __msize | __gas | __address | __selfbalance
             | __caller | __callvalue | __calldatasize | __codesize | __returndatasize
-            | __invalid | __chainid => 0,
+            | __invalid | __chainid | __blobbasefee => 0,
 
             __not | __iszero | __pop | __mload | __balance | __sload | __calldataload
-            | __extcodesize | __extcodehash | __selfdestruct | __blockhash => 1,
+            | __extcodesize | __extcodehash | __selfdestruct | __blockhash | __blobhash => 1,
 
             __add | __sub | __mul | __div | __sdiv | __mod | __smod | __exp | __lt | __gt
             | __slt | __sgt | __eq | __and | __or | __xor | __byte | __shl | __shr | __sar
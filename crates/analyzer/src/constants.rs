@@ -1,4 +1,14 @@
 pub const EMITTABLE_TRAIT_NAME: &str = "Emittable";
 pub const EMIT_FN_NAME: &str = "emit";
+pub const CALLDATA_BYTES_STRUCT_NAME: &str = "CalldataBytes";
 pub const INDEXED: &str = "indexed";
+pub const SECRET: &str = "secret";
+pub const CHECK_SECRETS: &str = "check_secrets";
 pub const MAX_INDEXED_EVENT_FIELDS: usize = 3;
+pub const CODE_SIZE_BUDGET: &str = "code_size_budget";
+pub const STORAGE_GAP: &str = "storage_gap";
+pub const ABI_NAME: &str = "abi_name";
+pub const ONLY: &str = "only";
+pub const NONREENTRANT: &str = "nonreentrant";
+pub const WHEN_NOT_PAUSED: &str = "when_not_paused";
+pub const PAUSED: &str = "paused";
@@ -8,7 +8,7 @@ use crate::{
 use crate::namespace::items::{
     ContractId, DiagnosticSink, FunctionId, FunctionSigId, Item, TraitId,
 };
-use crate::namespace::types::{Generic, SelfDecl, Type, TypeId};
+use crate::namespace::types::{type_diff, Generic, SelfDecl, Type, TypeId};
 use crate::AnalyzerDb;
 use crate::{
     builtins::{ContractTypeMethod, GlobalFunction, Intrinsic, ValueMethod},
@@ -169,12 +169,17 @@ pub trait AnalyzerContext {
         expected: TypeId,
         actual: TypeId,
     ) -> DiagnosticVoucher {
-        self.register_diag(errors::type_error(
+        let mut diag = errors::type_error(
             message,
             span,
             expected.display(self.db()),
             actual.display(self.db()),
-        ))
+        );
+        if let Some((expected, actual)) = type_diff(self.db(), expected, actual) {
+            diag.notes.push(format!("expected `{expected}`"));
+            diag.notes.push(format!("   found `{actual}`"));
+        }
+        self.register_diag(diag)
     }
 
     fn not_yet_implemented(&self, feature: &str, span: Span) -> DiagnosticVoucher {
@@ -314,6 +319,19 @@ impl DiagnosticVoucher {
     pub fn assume_the_parser_handled_it() -> Self {
         Self(PhantomData)
     }
+
+    /// For uses of a variable of type [`Type::Error`](crate::namespace::types::Type::Error),
+    /// which is only created after the failure of its declaration is reported.
+    pub fn assume_the_declaration_error_was_reported() -> Self {
+        Self(PhantomData)
+    }
+
+    /// For shifts by a literal or a named constant that always overflow, which
+    /// are reported when the shift is analyzed, before it's evaluated as a
+    /// constant.
+    pub fn assume_the_shift_overflow_was_reported() -> Self {
+        Self(PhantomData)
+    }
 }
 
 #[derive(Default)]
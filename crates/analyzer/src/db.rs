@@ -1,8 +1,8 @@
 #![allow(clippy::arc_with_non_send_sync)]
 use crate::namespace::items::{
-    self, AttributeId, ContractFieldId, ContractId, DepGraphWrapper, EnumVariantKind, FunctionId,
-    FunctionSigId, ImplId, IngotId, Item, ModuleConstantId, ModuleId, StructFieldId, StructId,
-    TraitId, TypeAliasId,
+    self, AttributeId, ContractFieldId, ContractId, DepGraphWrapper, EnumVariantKind,
+    FunctionGuards, FunctionId, FunctionSigId, ImplId, IngotId, Item, ModuleConstantId, ModuleId,
+    StructFieldId, StructId, TraitId, TypeAliasId,
 };
 use crate::namespace::types::{self, Type, TypeId};
 use crate::{
@@ -75,6 +75,10 @@ pub trait AnalyzerDb: SourceDb + Upcast<dyn SourceDb> + UpcastMut<dyn SourceDb>
     fn ingot_modules(&self, ingot: IngotId) -> Rc<[ModuleId]>;
     #[salsa::invoke(queries::ingots::ingot_root_module)]
     fn ingot_root_module(&self, ingot: IngotId) -> Option<ModuleId>;
+    #[salsa::invoke(queries::ingots::ingot_orphan_modules)]
+    fn ingot_orphan_modules(&self, ingot: IngotId) -> Rc<[ModuleId]>;
+    #[salsa::invoke(queries::ingots::ingot_impl_map)]
+    fn ingot_impl_map(&self, ingot: IngotId) -> Analysis<Rc<IndexMap<(TraitId, TypeId), ImplId>>>;
 
     // Module
     #[salsa::invoke(queries::module::module_file_path)]
@@ -156,6 +160,8 @@ pub trait AnalyzerDb: SourceDb + Upcast<dyn SourceDb> + UpcastMut<dyn SourceDb>
     fn function_signature(&self, id: FunctionSigId) -> Analysis<Rc<types::FunctionSignature>>;
     #[salsa::invoke(queries::functions::function_body)]
     fn function_body(&self, id: FunctionId) -> Analysis<Rc<FunctionBody>>;
+    #[salsa::invoke(queries::functions::function_guards)]
+    fn function_guards(&self, id: FunctionId) -> Analysis<Rc<FunctionGuards>>;
     #[salsa::cycle(queries::functions::function_dependency_graph_cycle)]
     #[salsa::invoke(queries::functions::function_dependency_graph)]
     fn function_dependency_graph(&self, id: FunctionId) -> DepGraphWrapper;
@@ -197,6 +203,10 @@ pub trait AnalyzerDb: SourceDb + Upcast<dyn SourceDb> + UpcastMut<dyn SourceDb>
     fn trait_function_map(&self, id: TraitId) -> Analysis<Rc<IndexMap<SmolStr, FunctionSigId>>>;
     #[salsa::invoke(queries::traits::trait_is_implemented_for)]
     fn trait_is_implemented_for(&self, id: TraitId, typ: TypeId) -> bool;
+    #[salsa::invoke(queries::traits::trait_all_impls)]
+    fn trait_all_impls(&self, id: TraitId) -> Rc<[ImplId]>;
+    #[salsa::invoke(queries::traits::traits_with_function)]
+    fn traits_with_function(&self, ingot: IngotId, name: SmolStr) -> Rc<[TraitId]>;
 
     // Impl
     #[salsa::invoke(queries::impls::impl_all_functions)]
@@ -209,6 +219,8 @@ pub trait AnalyzerDb: SourceDb + Upcast<dyn SourceDb> + UpcastMut<dyn SourceDb>
     fn all_impls(&self, ty: TypeId) -> Rc<[ImplId]>;
     #[salsa::invoke(queries::types::impl_for)]
     fn impl_for(&self, ty: TypeId, treit: TraitId) -> Option<ImplId>;
+    #[salsa::invoke(queries::types::implemented_traits)]
+    fn implemented_traits(&self, ty: TypeId) -> Rc<[TraitId]>;
     #[salsa::invoke(queries::types::function_sigs)]
     fn function_sigs(&self, ty: TypeId, name: SmolStr) -> Rc<[FunctionSigId]>;
 
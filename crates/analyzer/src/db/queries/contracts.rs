@@ -15,18 +15,23 @@ use smol_str::SmolStr;
 use std::rc::Rc;
 
 /// A `Vec` of every function defined in the contract, including duplicates and
-/// the init function.
+/// the init function. Functions of `impl Trait for Self` blocks are included.
 pub fn contract_all_functions(db: &dyn AnalyzerDb, contract: ContractId) -> Rc<[FunctionId]> {
     let module = contract.module(db);
     let body = &contract.data(db).ast.kind.body;
     body.iter()
-        .map(|stmt| match stmt {
-            ast::ContractStmt::Function(node) => db.intern_function(Rc::new(items::Function::new(
+        .flat_map(|stmt| match stmt {
+            ast::ContractStmt::Function(node) => vec![node],
+            ast::ContractStmt::Impl(impl_node) => impl_node.kind.functions.iter().collect(),
+            ast::ContractStmt::Attribute(_) => vec![],
+        })
+        .map(|node| {
+            db.intern_function(Rc::new(items::Function::new(
                 db,
                 node,
                 Some(Item::Type(TypeDef::Contract(contract))),
                 module,
-            ))),
+            )))
         })
         .collect()
 }
@@ -310,6 +315,15 @@ pub fn contract_field_type(
 
     let node = &field.data(db).ast;
 
+    let typ = match typ {
+        Ok(typ) if typ.contains_calldata_bytes(db) => Err(errors::TypeError::new(scope.error(
+            "contract field type can't contain calldata bytes",
+            node.span,
+            "calldata bytes can't outlive the current call",
+        ))),
+        typ => typ,
+    };
+
     if node.kind.is_pub {
         scope.not_yet_implemented("contract `pub` fields", node.span);
     }
@@ -140,7 +140,8 @@ pub fn enum_function_map(
             continue;
         }
 
-        if builtins::ValueMethod::from_str(def_name).is_ok() {
+        if builtins::ValueMethod::from_str(def_name).is_ok_and(|method| !method.is_integer_method())
+        {
             scope.error(
                 &format!("function name `{def_name}` conflicts with built-in function"),
                 func.name_span(db),
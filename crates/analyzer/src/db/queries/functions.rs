@@ -1,20 +1,24 @@
-use crate::context::{AnalyzerContext, CallType, FunctionBody};
+use crate::constants::{NONREENTRANT, ONLY, PAUSED, WHEN_NOT_PAUSED};
+use crate::context::{AnalyzerContext, CallType, FunctionBody, NamedThing};
 use crate::db::{Analysis, AnalyzerDb};
 use crate::display::Displayable;
 use crate::errors::TypeError;
 use crate::namespace::items::{
-    DepGraph, DepGraphWrapper, DepLocality, FunctionId, FunctionSigId, Item, TypeDef,
+    AccessControl, AttributeId, ContractId, DepGraph, DepGraphWrapper, DepLocality, FunctionGuards,
+    FunctionId, FunctionSigId, Item, ModuleId, StructId, TypeDef,
 };
 use crate::namespace::scopes::{BlockScope, BlockScopeType, FunctionScope, ItemScope};
-use crate::namespace::types::{self, CtxDecl, Generic, SelfDecl, Type, TypeId};
+use crate::namespace::types::{self, Base, CtxDecl, Generic, SelfDecl, Type, TypeId};
 use crate::traversal::functions::traverse_statements;
+use crate::traversal::secrets::check_secret_branches;
 use crate::traversal::types::{type_desc, type_desc_to_trait};
 use fe_common::diagnostics::Label;
+use fe_common::edition::Feature;
 use fe_parser::ast::{self, GenericParameter};
-use fe_parser::node::Node;
+use fe_parser::node::{Node, Span};
 use if_chain::if_chain;
 use smol_str::SmolStr;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
 /// Gather context information for a function definition and check for type
@@ -52,14 +56,18 @@ pub fn function_signature(
         },
     );
 
-    if !matches!(fn_parent, Item::Type(TypeDef::Struct(_))) && function.is_generic(db) {
+    if !matches!(
+        fn_parent,
+        Item::Type(TypeDef::Struct(_)) | Item::Type(TypeDef::Enum(_))
+    ) && function.is_generic(db)
+    {
         scope.fancy_error(
-            "generic function parameters aren't yet supported outside of struct functions",
+            "generic function parameters aren't yet supported outside of struct or enum functions",
             vec![Label::primary(
                 function.data(db).ast.kind.generic_params.span,
                 "this cannot appear here",
             )],
-            vec!["Hint: Struct functions can have generic parameters".into()],
+            vec!["Hint: Struct and enum functions can have generic parameters".into()],
         );
     }
 
@@ -107,8 +115,11 @@ pub fn function_signature(
                 None
             }
             ast::FunctionArg::Regular { mut_, label, name, typ: typedesc } => {
-                let typ = resolve_function_param_type(db, function, &mut scope, typedesc).and_then(|typ| match typ {
-                    typ if typ.has_fixed_size(db) => {
+                let typ = resolve_function_param_type(db, function, &mut scope, typedesc)
+                    .and_then(|typ| check_calldata_bytes_param(db, function, &mut scope, typedesc, typ))
+                    .and_then(|typ| match typ {
+                    // A storage reference is a single word, even if it refers to a `Map`.
+                    typ if typ.has_fixed_size(db) || typ.is_sptr(db) => {
                         if let Some(mut_span) = mut_ {
                             if typ.is_primitive(db) {
                                 Err(TypeError::new(scope.error(
@@ -241,6 +252,16 @@ pub fn function_signature(
                 };
 
                 match type_desc(&mut scope, type_node, self_ty)? {
+                    typ if function.is_contract_func(db)
+                        && function.is_public(db)
+                        && typ.contains_calldata_bytes(db) =>
+                    {
+                        Err(TypeError::new(scope.error(
+                            "public contract functions can't return calldata bytes",
+                            type_node.span,
+                            "calldata bytes can't outlive the current call",
+                        )))
+                    }
                     typ if typ.has_fixed_size(scope.db()) => Ok(typ),
                     _ => Err(TypeError::new(scope.error(
                         "function return type must have a fixed size",
@@ -269,6 +290,10 @@ fn resolve_function_param_type(
     context: &mut dyn AnalyzerContext,
     desc: &Node<ast::TypeDesc>,
 ) -> Result<TypeId, TypeError> {
+    if let ast::TypeDesc::StorageRef { inner } = &desc.kind {
+        return resolve_storage_ref_param_type(db, function, context, desc, inner);
+    }
+
     // First check if the param type is a local generic of the function. This won't
     // hold when in the future generics can appear on the contract, struct or
     // module level but it could be good enough for now.
@@ -276,8 +301,16 @@ fn resolve_function_param_type(
         if let Some(val) = function.generic_param(db, base) {
             let bounds = match val {
                 ast::GenericParameter::Unbounded(_) => vec![].into(),
-                ast::GenericParameter::Bounded { bound, .. } => {
-                    vec![type_desc_to_trait(context, &bound)?].into()
+                ast::GenericParameter::Bounded { bounds, .. } => {
+                    let mut traits = vec![];
+                    for bound in bounds.iter() {
+                        let trait_id = type_desc_to_trait(context, bound)?;
+                        // `T: Foo + Foo` is the same constraint as `T: Foo`
+                        if !traits.contains(&trait_id) {
+                            traits.push(trait_id);
+                        }
+                    }
+                    traits.into()
                 }
             };
 
@@ -297,8 +330,80 @@ fn resolve_function_param_type(
     type_desc(context, desc, self_ty)
 }
 
+/// Checks where a contract function can take calldata bytes. A public
+/// function decodes them lazily from its own calldata, which is only possible
+/// for a parameter of type `CalldataBytes`, and constructor arguments aren't
+/// passed in calldata at all.
+fn check_calldata_bytes_param(
+    db: &dyn AnalyzerDb,
+    function: FunctionSigId,
+    context: &mut dyn AnalyzerContext,
+    desc: &Node<ast::TypeDesc>,
+    typ: TypeId,
+) -> Result<TypeId, TypeError> {
+    if !function.is_contract_func(db) || !typ.contains_calldata_bytes(db) {
+        Ok(typ)
+    } else if function.is_constructor(db) {
+        Err(TypeError::new(context.error(
+            "`__init__` can't take calldata bytes",
+            desc.span,
+            "constructor arguments aren't passed in calldata",
+        )))
+    } else if function.is_public(db) && !typ.is_calldata_bytes(db) {
+        Err(TypeError::new(context.error(
+            "public contract functions can only take calldata bytes as a separate parameter",
+            desc.span,
+            "calldata bytes can't be nested in another type here",
+        )))
+    } else {
+        Ok(typ)
+    }
+}
+
+/// Resolves the type of a `&storage T` parameter to a storage pointer to `T`.
+///
+/// Storage references let internal functions operate on contract fields in
+/// place, so they can't be used by functions that are called through the ABI.
+fn resolve_storage_ref_param_type(
+    db: &dyn AnalyzerDb,
+    function: FunctionSigId,
+    context: &mut dyn AnalyzerContext,
+    desc: &Node<ast::TypeDesc>,
+    inner: &Node<ast::TypeDesc>,
+) -> Result<TypeId, TypeError> {
+    if function.is_contract_func(db) && (function.is_public(db) || function.is_constructor(db)) {
+        return Err(TypeError::new(context.fancy_error(
+            "public contract functions can't take storage references",
+            vec![Label::primary(
+                desc.span,
+                "not allowed in a public contract function",
+            )],
+            vec![
+                "Hint: storage references can only be passed to functions that aren't called through the contract's ABI"
+                    .into(),
+            ],
+        )));
+    }
+
+    let self_ty = function.self_type(db).map(|ty| ty.as_trait_or_type());
+    let typ = type_desc(context, inner, self_ty)?;
+    if typ.is_primitive(db) {
+        return Err(TypeError::new(context.fancy_error(
+            "storage references to primitive types aren't supported",
+            vec![Label::primary(
+                desc.span,
+                format!("`{}` is a primitive type", typ.display(db)),
+            )],
+            vec!["Hint: pass the value instead, and assign the result to the field".into()],
+        )));
+    }
+
+    Ok(Type::SPtr(typ).id(db))
+}
+
 /// Gather context information for a function body and check for type errors.
 pub fn function_body(db: &dyn AnalyzerDb, function: FunctionId) -> Analysis<Rc<FunctionBody>> {
+    let _span = tracing::debug_span!("function_body", item = %function.name(db)).entered();
     let def = &function.data(db).ast.kind;
     let scope = FunctionScope::new(db, function);
 
@@ -338,13 +443,263 @@ pub fn function_body(db: &dyn AnalyzerDb, function: FunctionId) -> Analysis<Rc<F
     // has been emitted, either while analyzing this fn body or while analyzing
     // a type or fn used in this fn body, because of the `DiagnosticVoucher`
     // system. (See the definition of `FatalError`)
-    let _ = traverse_statements(&mut block_scope, &def.body);
+    if traverse_statements(&mut block_scope, &def.body).is_ok() && function.checks_secrets(db) {
+        check_secret_branches(&scope, &def.body);
+    }
     Analysis {
         value: Rc::new(scope.body.into_inner()),
         diagnostics: scope.diagnostics.into_inner().into(),
     }
 }
 
+/// Checks the attributes of a contract function, and resolves the checks
+/// that they add to the start of it.
+pub fn function_guards(db: &dyn AnalyzerDb, function: FunctionId) -> Analysis<Rc<FunctionGuards>> {
+    let scope = ItemScope::new(db, function.module(db));
+    let module = function.module(db);
+    let mut guards = FunctionGuards::default();
+    let mut seen = HashSet::new();
+    let edition = module.ingot(db).edition(db);
+
+    for attribute in function.contract_attributes(db) {
+        let name = attribute.name(db);
+        if ![ONLY, NONREENTRANT, WHEN_NOT_PAUSED].contains(&name.as_str()) {
+            let note = "Note: functions of a contract support `#only(field)`, `#nonreentrant` and `#when_not_paused`";
+            scope.fancy_error(
+                &format!("unknown attribute `{name}`"),
+                vec![Label::primary(attribute.span(db), "unknown attribute")],
+                vec![note.into()],
+            );
+            continue;
+        }
+        if let Some(diag) = Feature::FunctionGuards.gate(edition, attribute.span(db)) {
+            scope.add_diagnostic(diag);
+            continue;
+        }
+        if !seen.insert(name.clone()) {
+            scope.error(
+                &format!("duplicate `{name}` attribute"),
+                attribute.span(db),
+                &format!("a function can only have one `{name}` attribute"),
+            );
+            continue;
+        }
+        if name != ONLY && attribute.data(db).ast.kind.args.is_some() {
+            scope.error(
+                &format!("invalid `{name}` attribute"),
+                attribute.span(db),
+                "this attribute takes no arguments",
+            );
+            continue;
+        }
+
+        match name.as_str() {
+            ONLY => guards.access_control = access_control(&scope, function, attribute),
+            NONREENTRANT => {
+                let label = "the contract is locked while the function runs";
+                if is_valid_guard(&scope, function, attribute, label, true) {
+                    guards.nonreentrant = Some(std_error(db, module, "ReentrantCall"));
+                }
+            }
+            _ => {
+                let label = "the `paused` field is checked before the function runs";
+                if is_valid_guard(&scope, function, attribute, label, false)
+                    && has_paused_field(&scope, function, attribute)
+                {
+                    guards.when_not_paused = Some(std_error(db, module, "Paused"));
+                }
+            }
+        }
+    }
+
+    Analysis {
+        value: Rc::new(guards),
+        diagnostics: scope.diagnostics.take().into(),
+    }
+}
+
+/// Resolves the caller check of an `#only(field)` attribute.
+fn access_control(
+    scope: &ItemScope,
+    function: FunctionId,
+    attribute: AttributeId,
+) -> Option<AccessControl> {
+    let db = scope.db();
+    let field = if let Some(field) = attribute.name_arg(db) {
+        field
+    } else {
+        scope.fancy_error(
+            "invalid `only` attribute",
+            vec![Label::primary(
+                attribute.span(db),
+                "expected a single contract field name",
+            )],
+            vec!["Example: `#only(owner)`".into()],
+        );
+        return None;
+    };
+    if !is_valid_guard(
+        scope,
+        function,
+        attribute,
+        "the caller is checked against a field",
+        false,
+    ) {
+        return None;
+    }
+
+    let contract = function_contract(db, function);
+    let is_role = match contract.field_type(db, &field.kind) {
+        Some(Ok(ty)) => match ty.typ(db) {
+            Type::Base(Base::Address) => false,
+            Type::Map(map)
+                if matches!(map.key.typ(db), Type::Base(Base::Address))
+                    && map.value.is_bool(db) =>
+            {
+                true
+            }
+            _ => {
+                scope.fancy_error(
+                    "invalid `only` field",
+                    vec![Label::primary(
+                        field.span,
+                        format!("this has type `{}`", ty.display(db)),
+                    )],
+                    vec!["Note: the field must be an `address`, or a `Map<address, bool>` of allowed callers".into()],
+                );
+                return None;
+            }
+        },
+        Some(Err(_)) => return None,
+        None => {
+            scope.error(
+                &format!(
+                    "No field `{}` exists on contract `{}`",
+                    field.kind,
+                    contract.name(db)
+                ),
+                field.span,
+                "undefined field",
+            );
+            return None;
+        }
+    };
+
+    Some(AccessControl {
+        field: field.kind,
+        is_role,
+        error: std_error(db, function.module(db), "Unauthorized"),
+    })
+}
+
+/// Reports attributes that add a check to the start of `__init__`, or of a
+/// function that doesn't take `self`, or `mut self` if the check writes to
+/// the contract.
+fn is_valid_guard(
+    scope: &ItemScope,
+    function: FunctionId,
+    attribute: AttributeId,
+    label: &str,
+    needs_mut_self: bool,
+) -> bool {
+    let db = scope.db();
+    let (span, conflict, notes) = match function.signature(db).self_decl {
+        _ if function.is_constructor(db) => (
+            function.name_span(db),
+            "`__init__` can't have this attribute",
+            vec![],
+        ),
+        None => (
+            function.name_span(db),
+            "this function doesn't take `self`",
+            vec![],
+        ),
+        Some(self_decl) if needs_mut_self && !self_decl.is_mut() => (
+            self_decl.span,
+            "`self` isn't mutable",
+            vec![
+                "Hint: the lock is written to storage, so the function must take `mut self`".into(),
+            ],
+        ),
+        Some(_) => return true,
+    };
+    scope.fancy_error(
+        &format!("invalid use of the `{}` attribute", attribute.name(db)),
+        vec![
+            Label::primary(attribute.span(db), label),
+            Label::secondary(span, conflict),
+        ],
+        notes,
+    );
+    false
+}
+
+/// Reports a missing or non-`bool` `paused` field of the contract of a
+/// `#when_not_paused` function.
+fn has_paused_field(scope: &ItemScope, function: FunctionId, attribute: AttributeId) -> bool {
+    let db = scope.db();
+    let contract = function_contract(db, function);
+    match contract.fields(db).get(PAUSED) {
+        Some(field) => match field.typ(db) {
+            Ok(ty) if ty.is_bool(db) => true,
+            Ok(ty) => {
+                scope.fancy_error(
+                    "invalid `paused` field",
+                    vec![
+                        Label::primary(attribute.span(db), "`paused` is checked here"),
+                        Label::secondary(
+                            field.data(db).ast.kind.typ.span,
+                            format!("this has type `{}`", ty.display(db)),
+                        ),
+                    ],
+                    vec!["Note: the field must be a `bool`".into()],
+                );
+                false
+            }
+            Err(_) => false,
+        },
+        None => {
+            scope.fancy_error(
+                &format!(
+                    "No field `paused` exists on contract `{}`",
+                    contract.name(db)
+                ),
+                vec![Label::primary(
+                    attribute.span(db),
+                    "`paused` is checked here",
+                )],
+                vec![format!(
+                    "Hint: add a `paused: bool` field to contract `{}`",
+                    contract.name(db)
+                )],
+            );
+            false
+        }
+    }
+}
+
+fn function_contract(db: &dyn AnalyzerDb, function: FunctionId) -> ContractId {
+    match function.parent(db) {
+        Item::Type(TypeDef::Contract(contract)) => contract,
+        _ => unreachable!("only contract functions have attributes"),
+    }
+}
+
+/// Resolves a struct of `std::error`, e.g. `Unauthorized`, which the checks
+/// of function attributes revert with.
+fn std_error(db: &dyn AnalyzerDb, module: ModuleId, name: &str) -> StructId {
+    let path = ast::Path {
+        segments: ["std", "error", name]
+            .into_iter()
+            .map(|segment| Node::new(segment.into(), Span::dummy()))
+            .collect(),
+    };
+    match module.resolve_path_non_used_internal(db, &path).value {
+        Some(NamedThing::Item(Item::Type(TypeDef::Struct(id)))) => id,
+        _ => panic!("`std::error::{name}` is missing"),
+    }
+}
+
 fn all_paths_return_or_revert(block: &[Node<ast::FuncStmt>]) -> bool {
     for statement in block.iter().rev() {
         match &statement.kind {
@@ -405,6 +760,7 @@ pub fn function_dependency_graph(db: &dyn AnalyzerDb, function: FunctionId) -> D
                 Type::Struct(id) => {
                     Some((root, Item::Type(TypeDef::Struct(id)), DepLocality::Local))
                 }
+                Type::Enum(id) => Some((root, Item::Type(TypeDef::Enum(id)), DepLocality::Local)),
                 _ => None,
             }),
     );
@@ -479,6 +835,7 @@ pub fn function_dependency_graph(db: &dyn AnalyzerDb, function: FunctionId) -> D
                 Type::Struct(id) => {
                     Some((root, Item::Type(TypeDef::Struct(id)), DepLocality::Local))
                 }
+                Type::Enum(id) => Some((root, Item::Type(TypeDef::Enum(id)), DepLocality::Local)),
                 _ => None,
             }),
     );
@@ -8,22 +8,46 @@ use crate::namespace::scopes::ItemScope;
 use crate::AnalyzerDb;
 use std::rc::Rc;
 
+/// The functions of the `impl` block, followed by the default functions of the
+/// trait that it doesn't define. A default function is checked and compiled
+/// for each `impl` block that uses it, with `Self` standing for the receiver.
 pub fn impl_all_functions(db: &dyn AnalyzerDb, impl_: ImplId) -> Rc<[FunctionId]> {
     let impl_data = impl_.data(db);
-    impl_data
+    let trait_ = impl_.trait_id(db);
+    let defined = |name: &str| {
+        impl_data
+            .ast
+            .kind
+            .functions
+            .iter()
+            .any(|node| node.kind.sig.kind.name.kind == name)
+    };
+
+    let functions = impl_data.ast.kind.functions.iter().map(|node| {
+        db.intern_function(Rc::new(Function::new(
+            db,
+            node,
+            Some(Item::Impl(impl_)),
+            impl_data.module,
+        )))
+    });
+    let default_fns = trait_
+        .data(db)
         .ast
         .kind
-        .functions
+        .default_fns
         .iter()
+        .filter(|node| !defined(&node.kind.sig.kind.name.kind))
         .map(|node| {
             db.intern_function(Rc::new(Function::new(
                 db,
                 node,
                 Some(Item::Impl(impl_)),
-                impl_data.module,
+                trait_.module(db),
             )))
         })
-        .collect()
+        .collect::<Vec<_>>();
+    functions.chain(default_fns).collect()
 }
 
 pub fn impl_function_map(
@@ -1,6 +1,13 @@
-use crate::namespace::items::{IngotId, IngotMode, ModuleId, ModuleSource};
+use crate::db::Analysis;
+use crate::display::Displayable;
+use crate::errors;
+use crate::namespace::items::{ImplId, IngotId, IngotMode, ModuleId, ModuleSource, TraitId};
+use crate::namespace::types::TypeId;
 use crate::AnalyzerDb;
+use fe_common::diagnostics::Label;
 use fe_common::files::{SourceFileId, Utf8Path, Utf8PathBuf};
+use fe_parser::lexer::{Lexer, TokenKind};
+use indexmap::map::{Entry, IndexMap};
 use indexmap::IndexSet;
 use std::rc::Rc;
 
@@ -81,3 +88,75 @@ pub fn ingot_root_module(db: &dyn AnalyzerDb, ingot: IngotId) -> Option<ModuleId
         .find(|modid| modid.file_path_relative_to_src_dir(db) == filename)
         .copied()
 }
+
+/// Returns the source file modules that can't be reached from the root module,
+/// either because they're missing from the module tree, or because their name
+/// (or the name of a parent directory) isn't a valid identifier and so can't
+/// appear in a `use` path.
+pub fn ingot_orphan_modules(db: &dyn AnalyzerDb, ingot: IngotId) -> Rc<[ModuleId]> {
+    let root = match ingot.root_module(db) {
+        Some(root) => root,
+        None => return Rc::new([]),
+    };
+
+    let mut reachable = IndexSet::new();
+    let mut queue = vec![root];
+    while let Some(module) = queue.pop() {
+        if (module == root || is_valid_module_name(&module.name(db))) && reachable.insert(module) {
+            queue.extend(module.submodules(db).iter().copied());
+        }
+    }
+
+    ingot
+        .all_modules(db)
+        .iter()
+        .copied()
+        .filter(|module| {
+            matches!(module.data(db).source, ModuleSource::File(_)) && !reachable.contains(module)
+        })
+        .collect()
+}
+
+/// Returns the `impl` block of each trait for each type in the ingot.
+///
+/// A trait can be implemented only once for a type, so an `impl` block that
+/// conflicts with one in another module of the ingot is reported here.
+/// Conflicts within a module are reported by `module_impl_map`, and the orphan
+/// rule keeps other ingots from implementing the same trait for the same type.
+pub fn ingot_impl_map(
+    db: &dyn AnalyzerDb,
+    ingot: IngotId,
+) -> Analysis<Rc<IndexMap<(TraitId, TypeId), ImplId>>> {
+    let mut map = IndexMap::<(TraitId, TypeId), ImplId>::new();
+    let mut diagnostics = vec![];
+
+    for module in ingot.all_modules(db).iter() {
+        for (key, impl_) in db.module_impl_map(*module).value.iter() {
+            match map.entry(*key) {
+                Entry::Occupied(entry) => diagnostics.push(errors::fancy_error(
+                    format!(
+                        "conflicting `impl` blocks for trait `{}` for type `{}`",
+                        key.0.name(db),
+                        key.1.display(db)
+                    ),
+                    vec![
+                        Label::primary(entry.get().span(db), "first implemented here"),
+                        Label::secondary(impl_.span(db), "implemented again here"),
+                    ],
+                    vec!["Note: a trait can only be implemented once for a type".into()],
+                )),
+                Entry::Vacant(entry) => {
+                    entry.insert(*impl_);
+                }
+            }
+        }
+    }
+
+    Analysis::new(Rc::new(map), diagnostics.into())
+}
+
+fn is_valid_module_name(name: &str) -> bool {
+    Lexer::new(SourceFileId::dummy_file(), name)
+        .map(|token| token.kind)
+        .eq([TokenKind::Name])
+}
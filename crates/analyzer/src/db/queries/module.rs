@@ -37,7 +37,9 @@ pub fn module_parse(db: &dyn AnalyzerDb, module: ModuleId) -> Analysis<Rc<ast::M
     let data = module.data(db);
     match data.source {
         ModuleSource::File(file) => {
-            let (ast, diags) = fe_parser::parse_file(file, &file.content(db.upcast()));
+            let edition = module.ingot(db).edition(db);
+            let (ast, diags) =
+                fe_parser::parse_file_with_edition(file, &file.content(db.upcast()), edition);
             Analysis::new(ast.into(), diags.into())
         }
         ModuleSource::Dir(_) => {
@@ -52,12 +52,12 @@ pub fn struct_field_map(
 
         // Multiple attributes are currently still rejected by the parser so we only
         // need to check the name here
-        if !field.attributes(db).is_empty() && !field.is_indexed(db) {
+        if !field.attributes(db).is_empty() && !field.is_indexed(db) && !field.is_secret(db) {
             let span = field.data(db).ast.kind.attributes.first().unwrap().span;
             scope.error(
                 "Invalid attribute",
                 span,
-                "illegal name. Only `indexed` supported.",
+                "illegal name. Only `indexed` and `secret` supported.",
             );
         }
 
@@ -134,6 +134,11 @@ pub fn struct_field_type(
                 );
                 Ok(typ)
             }
+            _ if typ.contains_calldata_bytes(db) => Err(TypeError::new(scope.error(
+                "struct field type can't contain calldata bytes",
+                field_data.ast.span,
+                "calldata bytes can't outlive the current call",
+            ))),
             t if t.has_fixed_size(db) => Ok(typ),
             _ => Err(TypeError::new(scope.error(
                 "struct field type must have a fixed size",
@@ -190,7 +195,8 @@ pub fn struct_function_map(
             continue;
         }
 
-        if builtins::ValueMethod::from_str(def_name).is_ok() {
+        if builtins::ValueMethod::from_str(def_name).is_ok_and(|method| !method.is_integer_method())
+        {
             scope.error(
                 &format!("function name `{def_name}` conflicts with built-in function"),
                 func.name_span(db),
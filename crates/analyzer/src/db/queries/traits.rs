@@ -3,7 +3,7 @@ use indexmap::IndexMap;
 use smol_str::SmolStr;
 
 use crate::context::{Analysis, AnalyzerContext};
-use crate::namespace::items::{FunctionSig, FunctionSigId, Item, TraitId};
+use crate::namespace::items::{FunctionSig, FunctionSigId, ImplId, IngotId, Item, TraitId};
 use crate::namespace::scopes::ItemScope;
 use crate::namespace::types::TypeId;
 use crate::AnalyzerDb;
@@ -60,3 +60,36 @@ pub fn trait_is_implemented_for(db: &dyn AnalyzerDb, trait_: TraitId, ty: TypeId
         .iter()
         .any(|val| val.trait_id(db) == trait_ && val.receiver(db) == ty)
 }
+
+/// All `impl` blocks of the trait in the root ingot and its dependencies.
+///
+/// They're taken from the `ingot_impl_map` of each ingot, so only the first of
+/// conflicting `impl` blocks is included.
+pub fn trait_all_impls(db: &dyn AnalyzerDb, trait_: TraitId) -> Rc<[ImplId]> {
+    let root = db.root_ingot();
+    std::iter::once(root)
+        .chain(db.ingot_external_ingots(root).values().copied())
+        .flat_map(|ingot| {
+            db.ingot_impl_map(ingot)
+                .value
+                .iter()
+                .filter(|((treit, _), _)| *treit == trait_)
+                .map(|(_, impl_)| *impl_)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// All traits in the ingot and its dependencies that declare a function named
+/// `name`, whether or not they're in scope anywhere.
+pub fn traits_with_function(db: &dyn AnalyzerDb, ingot: IngotId, name: SmolStr) -> Rc<[TraitId]> {
+    std::iter::once(ingot)
+        .chain(ingot.external_ingots(db).values().copied())
+        .flat_map(|ingot| ingot.all_modules(db).to_vec())
+        .flat_map(|module| module.all_items(db).to_vec())
+        .filter_map(|item| match item {
+            Item::Trait(id) if id.function(db, &name).is_some() => Some(id),
+            _ => None,
+        })
+        .collect()
+}
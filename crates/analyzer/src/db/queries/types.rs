@@ -31,11 +31,22 @@ pub fn all_impls(db: &dyn AnalyzerDb, ty: TypeId) -> Rc<[ImplId]> {
         .collect()
 }
 
+/// Relies on `ingot_impl_map` to find the only `impl` of `treit` for `ty`.
 pub fn impl_for(db: &dyn AnalyzerDb, ty: TypeId, treit: TraitId) -> Option<ImplId> {
+    let key = (treit, ty.deref(db));
+    let root = db.root_ingot();
+    std::iter::once(root)
+        .chain(db.ingot_external_ingots(root).values().copied())
+        .find_map(|ingot| db.ingot_impl_map(ingot).value.get(&key).copied())
+}
+
+/// Returns the traits that `ty` has an `impl` block of, in the current ingot
+/// and its dependencies.
+pub fn implemented_traits(db: &dyn AnalyzerDb, ty: TypeId) -> Rc<[TraitId]> {
     db.all_impls(ty)
         .iter()
-        .find(|impl_| impl_.trait_id(db) == treit)
-        .cloned()
+        .map(|impl_| impl_.trait_id(db))
+        .collect()
 }
 
 pub fn function_sigs(db: &dyn AnalyzerDb, ty: TypeId, name: SmolStr) -> Rc<[FunctionSigId]> {
@@ -14,7 +14,8 @@ use std::fmt::Display;
 /// If, for example, a function parameter has an undefined type, we emit a [`Diagnostic`] message,
 /// give that parameter a "type" of `Err(TypeError)`, and carry on. If/when that parameter is
 /// used in the function body, we assume that a diagnostic message about the undefined type
-/// has already been emitted, and halt the analysis of the function body.
+/// has already been emitted, and halt the analysis of the statement that uses it. Local
+/// variables whose declaration fails are treated the same way, via `Type::Error`.
 ///
 /// To ensure that that assumption is sound, a diagnostic *must* be emitted before creating
 /// a `TypeError`. So that the rust compiler can help us enforce this rule, a `TypeError`
@@ -192,6 +193,7 @@ pub fn fancy_error(
         message: message.into(),
         labels,
         notes,
+        suggestions: vec![],
     }
 }
 
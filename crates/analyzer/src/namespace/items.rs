@@ -1,4 +1,6 @@
-use crate::constants::{EMITTABLE_TRAIT_NAME, INDEXED};
+use crate::constants::{
+    ABI_NAME, CHECK_SECRETS, CODE_SIZE_BUDGET, EMITTABLE_TRAIT_NAME, INDEXED, SECRET, STORAGE_GAP,
+};
 use crate::context::{self, Analysis, Constant, NamedThing};
 use crate::display::{DisplayWithDb, Displayable};
 use crate::errors::{self, IncompleteItem, TypeError};
@@ -8,9 +10,10 @@ use crate::AnalyzerDb;
 use crate::{builtins, errors::ConstEvalError};
 use fe_common::diagnostics::Diagnostic;
 use fe_common::diagnostics::Label;
+use fe_common::edition::Edition;
 use fe_common::files::{common_prefix, Utf8Path};
 use fe_common::utils::files::{BuildFiles, ProjectMode};
-use fe_common::{impl_intern_key, FileKind, SourceFileId};
+use fe_common::{impl_intern_key, numeric, FileKind, SourceFileId};
 use fe_parser::ast::GenericParameter;
 use fe_parser::node::{Node, Span};
 use fe_parser::{ast, node::NodeId};
@@ -241,6 +244,7 @@ impl Item {
         match self {
             Item::Type(TypeDef::Contract(id)) => id.function(db, name).map(|fun| fun.sig(db)),
             Item::Type(TypeDef::Struct(id)) => id.function(db, name).map(|fun| fun.sig(db)),
+            Item::Type(TypeDef::Enum(id)) => id.function(db, name).map(|fun| fun.sig(db)),
             Item::Impl(id) => id.function(db, name).map(|fun| fun.sig(db)),
             Item::Trait(id) => id.function(db, name),
             _ => None,
@@ -321,6 +325,7 @@ pub struct Ingot {
     // pub version: SmolStr,
     pub mode: IngotMode,
     pub src_dir: SmolStr,
+    pub edition: Edition,
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
@@ -333,6 +338,7 @@ impl IngotId {
             "std",
             IngotMode::Lib,
             FileKind::Std,
+            Edition::LATEST,
             &fe_library::std_src_files(),
         );
         db.set_ingot_external_ingots(ingot, Rc::new(indexmap! {}));
@@ -355,6 +361,7 @@ impl IngotId {
                         &project_files.name,
                         mode,
                         FileKind::Local,
+                        project_files.edition,
                         &project_files.src,
                     ),
                 )
@@ -386,6 +393,7 @@ impl IngotId {
         name: &str,
         mode: IngotMode,
         file_kind: FileKind,
+        edition: Edition,
         files: &[(impl AsRef<str>, impl AsRef<str>)],
     ) -> Self {
         // The common prefix of all file paths will be stored as the ingot
@@ -410,6 +418,7 @@ impl IngotId {
             name: name.into(),
             mode,
             src_dir: file_path_prefix.as_str().into(),
+            edition,
         }));
 
         // Intern the source files
@@ -445,6 +454,10 @@ impl IngotId {
         self.data(db).name.clone()
     }
 
+    pub fn edition(&self, db: &dyn AnalyzerDb) -> Edition {
+        self.data(db).edition
+    }
+
     /// Returns the `main.fe`, or `lib.fe` module, depending on the ingot "mode"
     /// (IngotMode).
     pub fn root_module(&self, db: &dyn AnalyzerDb) -> Option<ModuleId> {
@@ -476,6 +489,15 @@ impl IngotId {
                 file_name,
             )));
         }
+        for module in db.ingot_orphan_modules(*self).iter() {
+            sink.push(&Diagnostic::error(format!(
+                "The file `{}` is not reachable from the root module of the ingot named \"{}\". \
+                 \nModule files and directories must be named with valid identifiers.",
+                module.file_path_relative_to_src_dir(db),
+                self.name(db),
+            )));
+        }
+        sink.push_all(db.ingot_impl_map(*self).diagnostics.iter());
         for module in self.all_modules(db).iter() {
             module.sink_diagnostics(db, sink)
         }
@@ -519,6 +541,7 @@ impl ModuleId {
             "",
             IngotMode::StandaloneModule,
             FileKind::Local,
+            Edition::LATEST,
             &[(path, content)],
         );
 
@@ -1054,6 +1077,9 @@ impl ContractId {
     pub fn name_span(&self, db: &dyn AnalyzerDb) -> Span {
         self.data(db).ast.kind.name.span
     }
+    pub fn doc_comment(&self, db: &dyn AnalyzerDb) -> Option<SmolStr> {
+        doc_comment(db, self.span(db))
+    }
 
     pub fn module(&self, db: &dyn AnalyzerDb) -> ModuleId {
         self.data(db).module
@@ -1121,6 +1147,16 @@ impl ContractId {
         Item::Module(self.data(db).module)
     }
 
+    /// The maximum size of the runtime bytecode of the contract in bytes, as
+    /// set with `#code_size_budget(bytes)`, and the span of the attribute.
+    pub fn code_size_budget(&self, db: &dyn AnalyzerDb) -> Option<(usize, Span)> {
+        Item::Type(TypeDef::Contract(*self))
+            .attributes(db)
+            .into_iter()
+            .find(|attribute| attribute.name(db) == CODE_SIZE_BUDGET)
+            .and_then(|attribute| Some((attribute.int_arg(db)?, attribute.span(db))))
+    }
+
     /// Dependency graph of the contract type, which consists of the field types
     /// and the dependencies of those types.
     ///
@@ -1149,6 +1185,96 @@ impl ContractId {
         db.contract_all_functions(*self)
             .iter()
             .for_each(|id| id.sink_diagnostics(db, sink));
+
+        // trait impls
+        for stmt in self.data(db).ast.kind.body.iter() {
+            if let ast::ContractStmt::Impl(impl_node) = stmt {
+                self.validate_trait_impl(db, sink, impl_node);
+            }
+        }
+
+        // attributes of functions
+        let body = &self.data(db).ast.kind.body;
+        for (idx, stmt) in body.iter().enumerate() {
+            if let ast::ContractStmt::Attribute(attribute) = stmt {
+                let next = body[idx + 1..]
+                    .iter()
+                    .find(|stmt| !matches!(stmt, ast::ContractStmt::Attribute(_)));
+                if !matches!(next, Some(ast::ContractStmt::Function(_))) {
+                    sink.push(&errors::error(
+                        "attribute isn't followed by a function",
+                        attribute.span,
+                        "attributes of a contract can only be used on fields and functions",
+                    ));
+                }
+            }
+        }
+
+        for attribute in Item::Type(TypeDef::Contract(*self)).attributes(db) {
+            if attribute.name(db) == CODE_SIZE_BUDGET && attribute.int_arg(db).is_none() {
+                sink.push(&errors::fancy_error(
+                    "invalid `code_size_budget` attribute",
+                    vec![Label::primary(
+                        attribute.span(db),
+                        "expected a single size in bytes",
+                    )],
+                    vec!["Example: `#code_size_budget(24576)`".into()],
+                ));
+            }
+        }
+    }
+
+    /// Checks that the functions of an `impl Trait for Self` block inside of the
+    /// contract match the functions declared by the trait.
+    fn validate_trait_impl(
+        &self,
+        db: &dyn AnalyzerDb,
+        sink: &mut impl DiagnosticSink,
+        impl_node: &Node<ast::Impl>,
+    ) {
+        let trait_name = &impl_node.kind.impl_trait;
+        let trait_id = match self.module(db).items(db).get(&trait_name.kind) {
+            Some(Item::Trait(id)) => *id,
+            _ => {
+                sink.push(&errors::error(
+                    format!("cannot find trait `{}` in this scope", trait_name.kind),
+                    trait_name.span,
+                    "not found",
+                ));
+                return;
+            }
+        };
+
+        let impl_fns = db
+            .contract_all_functions(*self)
+            .iter()
+            .filter(|func| {
+                impl_node
+                    .kind
+                    .functions
+                    .iter()
+                    .any(|node| node.id == func.data(db).ast.id)
+            })
+            .copied()
+            .collect::<Vec<_>>();
+
+        // Types in the trait may refer to `Self`, which stands for the contract here.
+        validate_trait_impl_fns(
+            db,
+            sink,
+            trait_id,
+            self.module(db),
+            &impl_fns,
+            false,
+            |type_in_impl, type_in_trait| {
+                type_in_impl == type_in_trait
+                    || (type_in_trait.is_self_ty(db)
+                        && matches!(
+                            type_in_impl.typ(db),
+                            Type::Contract(id) | Type::SelfContract(id) if id == *self
+                        ))
+            },
+        );
     }
 }
 
@@ -1171,8 +1297,57 @@ impl ContractFieldId {
     pub fn typ(&self, db: &dyn AnalyzerDb) -> Result<types::TypeId, TypeError> {
         db.contract_field_type(*self).value
     }
+
+    /// The attributes that come right before the field, e.g.
+    /// `#storage_gap(48)`.
+    pub fn attributes(&self, db: &dyn AnalyzerDb) -> Vec<AttributeId> {
+        let data = self.data(db);
+        let module = data.parent.module(db);
+        data.ast
+            .kind
+            .attributes
+            .iter()
+            .map(|ast| {
+                db.intern_attribute(Rc::new(Attribute {
+                    ast: ast.clone(),
+                    module,
+                }))
+            })
+            .collect()
+    }
+
+    /// The number of unused storage slots that are reserved right before the
+    /// field with `#storage_gap(slots)`, so that fields can be added there in
+    /// a later version of the contract without moving this one.
+    pub fn storage_gap(&self, db: &dyn AnalyzerDb) -> Option<usize> {
+        self.attributes(db)
+            .into_iter()
+            .find(|attribute| attribute.name(db) == STORAGE_GAP)
+            .and_then(|attribute| attribute.int_arg(db))
+    }
+
     pub fn sink_diagnostics(&self, db: &dyn AnalyzerDb, sink: &mut impl DiagnosticSink) {
-        sink.push_all(db.contract_field_type(*self).diagnostics.iter())
+        sink.push_all(db.contract_field_type(*self).diagnostics.iter());
+
+        for attribute in self.attributes(db) {
+            let name = attribute.name(db);
+            if name != STORAGE_GAP {
+                sink.push(&errors::fancy_error(
+                    format!("unknown attribute `{name}`"),
+                    vec![Label::primary(attribute.span(db), "unknown attribute")],
+                    vec!["Note: fields of a contract support `#storage_gap(slots)`".into()],
+                ));
+            } else if attribute.int_arg(db).is_none() {
+                sink.push(&errors::fancy_error(
+                    "invalid `storage_gap` attribute",
+                    vec![Label::primary(
+                        attribute.span(db),
+                        "expected a single number of storage slots",
+                    )],
+                    vec!["Example: `#storage_gap(48)`".into()],
+                ));
+            }
+        }
     }
 }
 
@@ -1214,7 +1389,10 @@ impl FunctionSigId {
     }
 
     pub fn is_public(&self, db: &dyn AnalyzerDb) -> bool {
-        self.is_trait_fn(db) || self.is_impl_fn(db) || self.pub_span(db).is_some()
+        self.is_trait_fn(db)
+            || self.is_impl_fn(db)
+            || self.is_contract_impl_fn(db)
+            || self.pub_span(db).is_some()
     }
     pub fn name(&self, db: &dyn AnalyzerDb) -> SmolStr {
         self.data(db).ast.kind.name.kind.clone()
@@ -1265,6 +1443,21 @@ impl FunctionSigId {
         matches!(self.parent(db), Item::Impl(_))
     }
 
+    /// Returns `true` if the function is defined in an `impl Trait for Self`
+    /// block of a contract.
+    pub fn is_contract_impl_fn(&self, db: &dyn AnalyzerDb) -> bool {
+        let sig_id = self.data(db).ast.id;
+        match self.parent(db) {
+            Item::Type(TypeDef::Contract(contract)) => {
+                contract.data(db).ast.kind.body.iter().any(|stmt| {
+                    matches!(stmt, ast::ContractStmt::Impl(impl_node)
+                        if impl_node.kind.functions.iter().any(|func| func.kind.sig.id == sig_id))
+                })
+            }
+            _ => false,
+        }
+    }
+
     pub fn is_generic(&self, db: &dyn AnalyzerDb) -> bool {
         !self.data(db).ast.kind.generic_params.kind.is_empty()
     }
@@ -1338,6 +1531,9 @@ impl FunctionId {
     pub fn name_span(&self, db: &dyn AnalyzerDb) -> Span {
         self.sig(db).name_span(db)
     }
+    pub fn doc_comment(&self, db: &dyn AnalyzerDb) -> Option<SmolStr> {
+        doc_comment(db, self.span(db))
+    }
     pub fn module(&self, db: &dyn AnalyzerDb) -> ModuleId {
         self.sig(db).module(db)
     }
@@ -1383,18 +1579,64 @@ impl FunctionId {
     }
     pub fn sink_diagnostics(&self, db: &dyn AnalyzerDb, sink: &mut impl DiagnosticSink) {
         sink.push_all(db.function_signature(self.data(db).sig).diagnostics.iter());
+        sink.push_all(db.function_guards(*self).diagnostics.iter());
         sink.push_all(db.function_body(*self).diagnostics.iter());
     }
     pub fn is_contract_func(self, db: &dyn AnalyzerDb) -> bool {
         self.sig(db).is_contract_func(db)
     }
 
+    /// The attributes that come right before the function in the body of its
+    /// contract, e.g. `#only(owner)`.
+    pub fn contract_attributes(&self, db: &dyn AnalyzerDb) -> Vec<AttributeId> {
+        let contract = match self.parent(db) {
+            Item::Type(TypeDef::Contract(contract)) => contract,
+            _ => return vec![],
+        };
+        let module = self.module(db);
+        let id = self.data(db).ast.id;
+        let mut attributes = vec![];
+        for stmt in contract.data(db).ast.kind.body.iter() {
+            match stmt {
+                ast::ContractStmt::Attribute(node) => attributes.push(node.clone()),
+                ast::ContractStmt::Function(node) if node.id == id => {
+                    return attributes
+                        .into_iter()
+                        .map(|ast| db.intern_attribute(Rc::new(Attribute { ast, module })))
+                        .collect();
+                }
+                _ => attributes.clear(),
+            }
+        }
+        vec![]
+    }
+
+    /// The checks that the attributes of the function add to the start of it,
+    /// e.g. `#only(owner)`.
+    pub fn guards(&self, db: &dyn AnalyzerDb) -> Rc<FunctionGuards> {
+        db.function_guards(*self).value
+    }
+
     pub fn is_test(&self, db: &dyn AnalyzerDb) -> bool {
         Item::Function(*self)
             .attributes(db)
             .iter()
             .any(|attribute| attribute.name(db) == "test")
     }
+
+    /// Whether the function is checked for branches on `#secret` values, which
+    /// is enabled with `#check_secrets` on the function itself or on the
+    /// contract, struct or enum that it belongs to.
+    pub fn checks_secrets(&self, db: &dyn AnalyzerDb) -> bool {
+        let parent = match self.parent(db) {
+            parent @ Item::Type(_) => Some(parent),
+            _ => None,
+        };
+        std::iter::once(Item::Function(*self))
+            .chain(parent)
+            .flat_map(|item| item.attributes(db))
+            .any(|attribute| attribute.name(db) == CHECK_SECRETS)
+    }
 }
 
 trait FunctionsAsItems {
@@ -1456,6 +1698,20 @@ impl StructId {
     pub fn name_span(&self, db: &dyn AnalyzerDb) -> Span {
         self.data(db).ast.kind.name.span
     }
+    pub fn doc_comment(&self, db: &dyn AnalyzerDb) -> Option<SmolStr> {
+        doc_comment(db, self.span(db))
+    }
+
+    /// The name of the struct in contract ABIs when it's used as an event or
+    /// an error, which can be set with `#abi_name("Name")`.
+    pub fn abi_name(&self, db: &dyn AnalyzerDb) -> SmolStr {
+        Item::Type(TypeDef::Struct(*self))
+            .attributes(db)
+            .into_iter()
+            .find(|attribute| attribute.name(db) == ABI_NAME)
+            .and_then(|attribute| attribute.str_arg(db))
+            .unwrap_or_else(|| self.name(db))
+    }
 
     pub fn is_public(&self, db: &dyn AnalyzerDb) -> bool {
         self.data(db).ast.kind.pub_qual.is_some()
@@ -1509,6 +1765,19 @@ impl StructId {
         db.struct_all_functions(*self)
             .iter()
             .for_each(|id| id.sink_diagnostics(db, sink));
+
+        for attribute in Item::Type(TypeDef::Struct(*self)).attributes(db) {
+            if attribute.name(db) == ABI_NAME && attribute.str_arg(db).is_none() {
+                sink.push(&errors::fancy_error(
+                    "invalid `abi_name` attribute",
+                    vec![Label::primary(
+                        attribute.span(db),
+                        "expected a single identifier in quotes",
+                    )],
+                    vec!["Example: `#abi_name(\"TokenTransfer\")`".into()],
+                ));
+            }
+        }
     }
 }
 
@@ -1537,7 +1806,7 @@ impl StructFieldId {
             .kind
             .attributes
             .iter()
-            .map(|node| node.kind.clone())
+            .map(|node| node.kind.name.kind.clone())
             .collect()
     }
 
@@ -1545,6 +1814,12 @@ impl StructFieldId {
         self.attributes(db).contains(&INDEXED.into())
     }
 
+    /// Whether the field is marked `#secret`, which makes branching on its
+    /// value an error.
+    pub fn is_secret(&self, db: &dyn AnalyzerDb) -> bool {
+        self.attributes(db).contains(&SECRET.into())
+    }
+
     pub fn typ(&self, db: &dyn AnalyzerDb) -> Result<types::TypeId, TypeError> {
         db.struct_field_type(*self).value
     }
@@ -1557,9 +1832,34 @@ impl StructFieldId {
     }
 }
 
+/// The checks that the attributes of a contract function add to the start of
+/// it, each of which reverts with an error of `std::error` if it fails.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct FunctionGuards {
+    pub access_control: Option<AccessControl>,
+    /// The error that `#nonreentrant` reverts with when the contract is
+    /// locked by a call that hasn't returned yet.
+    pub nonreentrant: Option<StructId>,
+    /// The error that `#when_not_paused` reverts with while the `paused`
+    /// field of the contract is `true`.
+    pub when_not_paused: Option<StructId>,
+}
+
+/// The check that `#only(field)` adds to the start of a contract function,
+/// which reverts with `std::error::Unauthorized` unless the caller is allowed
+/// by the contract field.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct AccessControl {
+    pub field: SmolStr,
+    /// Whether the field is a `Map<address, bool>` of allowed callers, rather
+    /// than the `address` of the only allowed caller.
+    pub is_role: bool,
+    pub error: StructId,
+}
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct Attribute {
-    pub ast: Node<SmolStr>,
+    pub ast: Node<ast::Attribute>,
     pub module: ModuleId,
 }
 #[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
@@ -1574,7 +1874,49 @@ impl AttributeId {
         self.data(db).ast.span
     }
     pub fn name(self, db: &dyn AnalyzerDb) -> SmolStr {
-        self.data(db).ast.kind.to_owned()
+        self.data(db).ast.kind.name.kind.to_owned()
+    }
+
+    /// Returns the argument of an attribute that takes a single integer,
+    /// e.g. `#storage_gap(48)`, or `None` if the attribute has a different
+    /// form.
+    pub fn int_arg(self, db: &dyn AnalyzerDb) -> Option<usize> {
+        let args = self.data(db).ast.kind.args.clone()?;
+        match args.kind.as_slice() {
+            [Node {
+                kind: ast::Expr::Num(num),
+                ..
+            }] => numeric::Literal::new(num).parse::<usize>().ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the argument of an attribute that takes a single identifier
+    /// in quotes, e.g. `#abi_name("TokenTransfer")`, or `None` if the
+    /// attribute has a different form.
+    pub fn str_arg(self, db: &dyn AnalyzerDb) -> Option<SmolStr> {
+        let args = self.data(db).ast.kind.args.clone()?;
+        match args.kind.as_slice() {
+            [Node {
+                kind: ast::Expr::Str(name),
+                ..
+            }] if is_identifier(name) => Some(name.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the argument of an attribute that takes a single name, e.g.
+    /// `#only(owner)`, or `None` if the attribute has a different form.
+    pub fn name_arg(self, db: &dyn AnalyzerDb) -> Option<Node<SmolStr>> {
+        let args = self.data(db).ast.kind.args.clone()?;
+        match args.kind.as_slice() {
+            [Node {
+                kind: ast::Expr::Name(name),
+                span,
+                ..
+            }] => Some(Node::new(name.clone(), *span)),
+            _ => None,
+        }
     }
 
     pub fn module(self, db: &dyn AnalyzerDb) -> ModuleId {
@@ -1586,6 +1928,35 @@ impl AttributeId {
     }
 }
 
+/// Returns the `///` comments on the lines above the item at `span`, without
+/// the slashes. Attributes between the comments and the item are skipped.
+fn doc_comment(db: &dyn AnalyzerDb, span: Span) -> Option<SmolStr> {
+    let content = span.file_id.content(db.upcast());
+    let mut lines = vec![];
+    // The last line is the one the item starts on.
+    for line in content[..span.start].split('\n').rev().skip(1) {
+        let line = line.trim();
+        if let Some(doc) = line.strip_prefix("///") {
+            lines.push(doc.strip_prefix(' ').unwrap_or(doc));
+        } else if !line.starts_with('#') {
+            break;
+        }
+    }
+
+    if lines.is_empty() {
+        None
+    } else {
+        lines.reverse();
+        Some(lines.join("\n").into())
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct Enum {
     pub ast: Node<ast::Enum>,
@@ -1777,6 +2148,18 @@ impl ImplId {
         db.impl_all_functions(*self)
     }
 
+    /// Returns `true` if `function` is a default function of the trait that
+    /// the `impl` block doesn't define.
+    pub fn is_default_fn(&self, db: &dyn AnalyzerDb, function: FunctionId) -> bool {
+        !self
+            .data(db)
+            .ast
+            .kind
+            .functions
+            .iter()
+            .any(|node| node.kind.sig.kind.name.kind == function.name(db))
+    }
+
     pub fn trait_id(&self, db: &dyn AnalyzerDb) -> TraitId {
         self.data(db).trait_id
     }
@@ -1885,162 +2268,211 @@ impl ImplId {
             Type::Base(_) | Type::Array(_) | Type::Tuple(_) | Type::String(_) => {
                 self.validate_type_or_trait_is_in_ingot(db, sink, None)
             }
-            Type::SPtr(_) | Type::Mut(_) => unreachable!(),
+            Type::SPtr(_) | Type::Mut(_) | Type::Error => unreachable!(),
         }
 
-        if !self.trait_id(db).is_public(db) && self.trait_id(db).module(db) != self.module(db) {
-            let trait_module_name = self.trait_id(db).module(db).name(db);
-            let trait_name = self.trait_id(db).name(db);
-            sink.push(&errors::fancy_error(
-                     format!(
-                         "the trait `{trait_name}` is private",
-                     ),
-                     vec![
-                         Label::primary(self.trait_id(db).data(db).ast.kind.name.span, "this trait is not `pub`"),
-                     ],
-                     vec![
-                         format!("`{trait_name}` can only be used within `{trait_module_name}`"),
-                         format!("Hint: use `pub trait {trait_name}` to make `{trait_name}` visible from outside of `{trait_module_name}`"),
-                     ],
-                 ));
-        }
+        // The default functions may call the missing ones, which would only
+        // repeat the error about them.
+        let is_complete = self
+            .trait_id(db)
+            .all_functions(db)
+            .iter()
+            .all(|trait_fn| self.function(db, &trait_fn.name(db)).is_some());
 
+        let mut impl_fns = vec![];
         for impl_fn in self.all_functions(db).iter() {
-            impl_fn.sink_diagnostics(db, sink);
-
-            if let Some(trait_fn) = self.trait_id(db).function(db, &impl_fn.name(db)) {
-                for (impl_param, trait_param) in impl_fn
-                    .signature(db)
-                    .params
-                    .iter()
-                    .zip(trait_fn.signature(db).params.iter())
-                {
-                    let impl_param_ty = impl_param.typ.clone().unwrap();
-                    let trait_param_ty = trait_param.typ.clone().unwrap();
-                    if self.can_stand_in_for(db, impl_param_ty, trait_param_ty) {
-                        continue;
-                    } else {
-                        sink.push(&errors::fancy_error(
-                            format!(
-                                "method `{}` has incompatible parameters for `{}` of trait `{}`",
-                                impl_fn.name(db),
-                                trait_fn.name(db),
-                                self.trait_id(db).name(db)
-                            ),
-                            vec![
-                                Label::primary(
-                                    impl_fn.data(db).ast.kind.sig.span,
-                                    "signature of method in `impl` block",
-                                ),
-                                Label::primary(
-                                    trait_fn.data(db).ast.span,
-                                    format!(
-                                        "signature of method in trait `{}`",
-                                        self.trait_id(db).name(db)
-                                    ),
-                                ),
-                            ],
-                            vec![],
-                        ));
-                        break;
-                    }
+            if self.is_default_fn(db, *impl_fn) {
+                if is_complete {
+                    impl_fn.sink_diagnostics(db, sink);
                 }
+            } else {
+                impl_fn.sink_diagnostics(db, sink);
+                impl_fns.push(*impl_fn);
+            }
+        }
 
-                let impl_fn_return_ty = impl_fn.signature(db).return_type.clone().unwrap();
-                let trait_fn_return_ty = trait_fn.signature(db).return_type.clone().unwrap();
-
-                if !self.can_stand_in_for(db, impl_fn_return_ty, trait_fn_return_ty) {
-                    // TODO: This could be a nicer, more detailed report
-                    sink.push(&errors::fancy_error(
-                        format!(
-                            "method `{}` has an incompatible return type for `{}` of trait `{}`",
-                            impl_fn.name(db),
-                            trait_fn.name(db),
-                            self.trait_id(db).name(db)
-                        ),
-                        vec![
-                            Label::primary(
-                                impl_fn.data(db).ast.kind.sig.span,
-                                "signature of method in `impl` block",
-                            ),
-                            Label::primary(
-                                trait_fn.data(db).ast.span,
-                                format!(
-                                    "signature of method in trait `{}`",
-                                    self.trait_id(db).name(db)
-                                ),
-                            ),
-                        ],
-                        vec![],
-                    ));
-                }
+        validate_trait_impl_fns(
+            db,
+            sink,
+            self.trait_id(db),
+            self.module(db),
+            &impl_fns,
+            true,
+            |type_in_impl, type_in_trait| self.can_stand_in_for(db, type_in_impl, type_in_trait),
+        );
+    }
+}
+
+/// Checks the functions defined in an `impl` block against the functions
+/// declared by its trait. This is shared by `impl` blocks of modules and of
+/// contracts. `can_stand_in_for` decides whether a type used by the `impl`
+/// block matches a type used by the trait, and `default_fns` whether the
+/// default functions of the trait count as implemented.
+fn validate_trait_impl_fns(
+    db: &dyn AnalyzerDb,
+    sink: &mut impl DiagnosticSink,
+    trait_id: TraitId,
+    module: ModuleId,
+    impl_fns: &[FunctionId],
+    default_fns: bool,
+    can_stand_in_for: impl Fn(TypeId, TypeId) -> bool,
+) {
+    if !trait_id.is_public(db) && trait_id.module(db) != module {
+        let trait_module_name = trait_id.module(db).name(db);
+        let trait_name = trait_id.name(db);
+        sink.push(&errors::fancy_error(
+                 format!(
+                     "the trait `{trait_name}` is private",
+                 ),
+                 vec![
+                     Label::primary(trait_id.data(db).ast.kind.name.span, "this trait is not `pub`"),
+                 ],
+                 vec![
+                     format!("`{trait_name}` can only be used within `{trait_module_name}`"),
+                     format!("Hint: use `pub trait {trait_name}` to make `{trait_name}` visible from outside of `{trait_module_name}`"),
+                 ],
+             ));
+    }
+
+    // Type errors of the signatures have already been reported.
+    let types_match = |impl_ty: &Result<TypeId, TypeError>,
+                       trait_ty: &Result<TypeId, TypeError>| {
+        match (impl_ty, trait_ty) {
+            (Ok(impl_ty), Ok(trait_ty)) => can_stand_in_for(*impl_ty, *trait_ty),
+            _ => true,
+        }
+    };
 
-                if impl_fn.takes_self(db) != trait_fn.takes_self(db) {
-                    let ((selfy_thing, selfy_span), (non_selfy_thing, non_selfy_span)) =
-                        if impl_fn.takes_self(db) {
-                            (
-                                ("impl", impl_fn.name_span(db)),
-                                ("trait", trait_fn.name_span(db)),
-                            )
-                        } else {
-                            (
-                                ("trait", trait_fn.name_span(db)),
-                                ("impl", impl_fn.name_span(db)),
-                            )
-                        };
-                    sink.push(&errors::fancy_error(
-                        format!(
-                            "method `{}` has a `self` declaration in the {}, but not in the `{}`",
-                            impl_fn.name(db),
-                            selfy_thing,
-                            non_selfy_thing
+    for impl_fn in impl_fns {
+        if let Some(trait_fn) = trait_id.function(db, &impl_fn.name(db)) {
+            let impl_sig = impl_fn.signature(db);
+            let trait_sig = trait_fn.signature(db);
+            let params_match = impl_sig.params.len() == trait_sig.params.len()
+                && impl_sig.params.iter().zip(trait_sig.params.iter()).all(
+                    |(impl_param, trait_param)| types_match(&impl_param.typ, &trait_param.typ),
+                );
+            if !params_match {
+                sink.push(&errors::fancy_error(
+                    format!(
+                        "method `{}` has incompatible parameters for `{}` of trait `{}`",
+                        impl_fn.name(db),
+                        trait_fn.name(db),
+                        trait_id.name(db)
+                    ),
+                    vec![
+                        Label::primary(
+                            impl_fn.data(db).ast.kind.sig.span,
+                            "signature of method in `impl` block",
                         ),
-                        vec![
-                            Label::primary(
-                                selfy_span,
-                                format!("`self` declared on the `{selfy_thing}`"),
-                            ),
-                            Label::primary(
-                                non_selfy_span,
-                                format!("no `self` declared on the `{non_selfy_thing}`"),
-                            ),
-                        ],
-                        vec![],
-                    ));
-                }
-            } else {
+                        Label::primary(
+                            trait_fn.data(db).ast.span,
+                            format!("signature of method in trait `{}`", trait_id.name(db)),
+                        ),
+                    ],
+                    vec![],
+                ));
+            }
+
+            if !types_match(&impl_sig.return_type, &trait_sig.return_type) {
+                // TODO: This could be a nicer, more detailed report
                 sink.push(&errors::fancy_error(
                     format!(
-                        "method `{}` is not a member of trait `{}`",
+                        "method `{}` has an incompatible return type for `{}` of trait `{}`",
                         impl_fn.name(db),
-                        self.trait_id(db).name(db)
+                        trait_fn.name(db),
+                        trait_id.name(db)
                     ),
-                    vec![Label::primary(
-                        impl_fn.data(db).ast.span,
-                        format!("not a member of trait `{}`", self.trait_id(db).name(db)),
-                    )],
+                    vec![
+                        Label::primary(
+                            impl_fn.data(db).ast.kind.sig.span,
+                            "signature of method in `impl` block",
+                        ),
+                        Label::primary(
+                            trait_fn.data(db).ast.span,
+                            format!("signature of method in trait `{}`", trait_id.name(db)),
+                        ),
+                    ],
                     vec![],
-                ))
+                ));
             }
-        }
 
-        for trait_fn in self.trait_id(db).all_functions(db).iter() {
-            if self.function(db, &trait_fn.name(db)).is_none() {
+            if impl_fn.takes_self(db) != trait_fn.takes_self(db) {
+                let ((selfy_thing, selfy_span), (non_selfy_thing, non_selfy_span)) =
+                    if impl_fn.takes_self(db) {
+                        (
+                            ("impl", impl_fn.name_span(db)),
+                            ("trait", trait_fn.name_span(db)),
+                        )
+                    } else {
+                        (
+                            ("trait", trait_fn.name_span(db)),
+                            ("impl", impl_fn.name_span(db)),
+                        )
+                    };
                 sink.push(&errors::fancy_error(
                     format!(
-                        "not all members of trait `{}` implemented, missing: `{}`",
-                        self.trait_id(db).name(db),
-                        trait_fn.name(db)
+                        "method `{}` has a `self` declaration in the {}, but not in the `{}`",
+                        impl_fn.name(db),
+                        selfy_thing,
+                        non_selfy_thing
                     ),
-                    vec![Label::primary(
-                        trait_fn.data(db).ast.span,
-                        "this trait function is missing in `impl` block",
-                    )],
+                    vec![
+                        Label::primary(
+                            selfy_span,
+                            format!("`self` declared on the `{selfy_thing}`"),
+                        ),
+                        Label::primary(
+                            non_selfy_span,
+                            format!("no `self` declared on the `{non_selfy_thing}`"),
+                        ),
+                    ],
                     vec![],
-                ))
+                ));
             }
+        } else {
+            sink.push(&errors::fancy_error(
+                format!(
+                    "method `{}` is not a member of trait `{}`",
+                    impl_fn.name(db),
+                    trait_id.name(db)
+                ),
+                vec![Label::primary(
+                    impl_fn.data(db).ast.span,
+                    format!("not a member of trait `{}`", trait_id.name(db)),
+                )],
+                vec![],
+            ))
         }
     }
+
+    for trait_fn in trait_id.all_functions(db).iter() {
+        let trait_fn_name = trait_fn.name(db);
+        let is_default = trait_id.has_default_fn(db, &trait_fn_name);
+        if (default_fns && is_default)
+            || impl_fns
+                .iter()
+                .any(|impl_fn| impl_fn.name(db) == trait_fn_name)
+        {
+            continue;
+        }
+        let notes = if is_default {
+            vec!["Note: `impl` blocks in contracts can't use default functions yet".into()]
+        } else {
+            vec![]
+        };
+        sink.push(&errors::fancy_error(
+            format!(
+                "not all members of trait `{}` implemented, missing: `{}`",
+                trait_id.name(db),
+                trait_fn_name
+            ),
+            vec![Label::primary(
+                trait_fn.data(db).ast.span,
+                "this trait function is missing in `impl` block",
+            )],
+            notes,
+        ))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
@@ -2086,6 +2518,22 @@ impl TraitId {
         db.all_impls(ty).iter().any(|val| &val.trait_id(db) == self)
     }
 
+    /// Returns `true` if the trait provides a default body for the function.
+    pub fn has_default_fn(&self, db: &dyn AnalyzerDb, name: &str) -> bool {
+        self.data(db)
+            .ast
+            .kind
+            .default_fns
+            .iter()
+            .any(|node| node.kind.sig.kind.name.kind == name)
+    }
+
+    /// Returns the `impl` blocks of the trait in the current ingot and its
+    /// dependencies.
+    pub fn all_impls(&self, db: &dyn AnalyzerDb) -> Rc<[ImplId]> {
+        db.trait_all_impls(*self)
+    }
+
     pub fn is_in_std(&self, db: &dyn AnalyzerDb) -> bool {
         self.module(db).is_in_std(db)
     }
@@ -1,3 +1,4 @@
+use crate::constants::CALLDATA_BYTES_STRUCT_NAME;
 use crate::context::AnalyzerContext;
 use crate::display::DisplayWithDb;
 use crate::display::Displayable;
@@ -62,6 +63,10 @@ pub enum Type {
     Generic(Generic),
     SPtr(TypeId),
     Mut(TypeId),
+    /// The type of a variable whose declaration failed to type check. The
+    /// failure has already been reported, so uses of the variable are skipped
+    /// silently instead of producing follow-on errors.
+    Error,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -82,6 +87,9 @@ impl TypeId {
     pub fn bool(db: &dyn AnalyzerDb) -> Self {
         db.intern_type(Type::bool())
     }
+    pub fn error(db: &dyn AnalyzerDb) -> Self {
+        db.intern_type(Type::Error)
+    }
     pub fn int(db: &dyn AnalyzerDb, int: Integer) -> Self {
         db.intern_type(Type::int(int))
     }
@@ -165,10 +173,38 @@ impl TypeId {
         matches!(self.deref(db).typ(db), Type::Generic(_))
     }
 
+    /// `true` if the type is `std::buf::CalldataBytes`, which points into the
+    /// calldata of the current call.
+    pub fn is_calldata_bytes(&self, db: &dyn AnalyzerDb) -> bool {
+        match self.typ(db) {
+            Type::Struct(id) => {
+                id.module(db).is_in_std(db) && id.name(db) == CALLDATA_BYTES_STRUCT_NAME
+            }
+            Type::Mut(inner) => inner.is_calldata_bytes(db),
+            _ => false,
+        }
+    }
+
+    /// `true` if a value of the type may hold calldata bytes. Structs can't
+    /// have calldata bytes fields, so they aren't searched.
+    pub fn contains_calldata_bytes(&self, db: &dyn AnalyzerDb) -> bool {
+        match self.typ(db) {
+            Type::Array(arr) => arr.inner.contains_calldata_bytes(db),
+            Type::Map(map) => map.value.contains_calldata_bytes(db),
+            Type::Tuple(tup) => tup.items.iter().any(|ty| ty.contains_calldata_bytes(db)),
+            Type::SPtr(inner) | Type::Mut(inner) => inner.contains_calldata_bytes(db),
+            _ => self.is_calldata_bytes(db),
+        }
+    }
+
     pub fn is_mut(&self, db: &dyn AnalyzerDb) -> bool {
         matches!(self.typ(db), Type::Mut(_))
     }
 
+    pub fn is_error(&self, db: &dyn AnalyzerDb) -> bool {
+        matches!(self.deref_typ(db), Type::Error)
+    }
+
     pub fn name(&self, db: &dyn AnalyzerDb) -> SmolStr {
         self.typ(db).name(db)
     }
@@ -183,6 +219,12 @@ impl TypeId {
         }
     }
 
+    /// Returns the traits that the type has an `impl` block of, in the current
+    /// ingot and its dependencies.
+    pub fn implemented_traits(&self, db: &dyn AnalyzerDb) -> Rc<[TraitId]> {
+        db.implemented_traits(*self)
+    }
+
     /// Return the `impl` for the given trait. There can only ever be a single
     /// implementation per concrete type and trait.
     pub fn get_impl_for(&self, db: &dyn AnalyzerDb, trait_: TraitId) -> Option<ImplId> {
@@ -231,11 +273,10 @@ impl TypeId {
             Type::SelfContract(id) => id.function(db, name).map(|fun| fun.sig(db)),
             Type::Struct(id) => id.function(db, name).map(|fun| fun.sig(db)),
             Type::Enum(id) => id.function(db, name).map(|fun| fun.sig(db)),
-            // TODO: This won't hold when we support multiple bounds
             Type::Generic(inner) => inner
                 .bounds
-                .first()
-                .and_then(|bound| bound.function(db, name)),
+                .iter()
+                .find_map(|bound| bound.function(db, name)),
             _ => None,
         }
     }
@@ -300,7 +341,8 @@ impl TypeId {
             | Type::SelfContract(_)
             | Type::Generic(_)
             | Type::Enum(_)
-            | Type::SPtr(_) => Ok(false),
+            | Type::SPtr(_)
+            | Type::Error => Ok(false),
         }
     }
 }
@@ -669,7 +711,8 @@ impl Type {
             | Type::Struct(_)
             | Type::Enum(_)
             | Type::Generic(_)
-            | Type::Contract(_) => true,
+            | Type::Contract(_)
+            | Type::Error => true,
             Type::Map(_) | Type::SelfContract(_) => false,
             Type::SelfType(inner) => match inner {
                 TraitOrType::TraitId(_) => true,
@@ -756,6 +799,7 @@ impl DisplayWithDb for Type {
             Type::SPtr(inner) => write!(f, "SPtr<{}>", inner.display(db)),
             Type::Mut(inner) => write!(f, "mut {}", inner.display(db)),
             Type::SelfType(_) => write!(f, "Self"),
+            Type::Error => write!(f, "{{unknown}}"),
         }
     }
 }
@@ -765,6 +809,92 @@ impl DisplayWithDb for TypeId {
     }
 }
 
+/// Types whose rendering is shorter than this are shown in full in type
+/// mismatch errors.
+const TYPE_DIFF_MIN_LEN: usize = 32;
+
+/// Renders two mismatched types with the parts they have in common replaced by
+/// `_`, e.g. `Map<_, Array<_, 10>>` and `Map<_, Array<_, 12>>`.
+///
+/// Returns `None` if both types are short, or if they differ at the top
+/// level, since the full types are just as easy to compare then.
+pub fn type_diff(db: &dyn AnalyzerDb, expected: TypeId, found: TypeId) -> Option<(String, String)> {
+    let long = |typ: TypeId| typ.display(db).to_string().len() >= TYPE_DIFF_MIN_LEN;
+    if expected == found || !(long(expected) || long(found)) {
+        return None;
+    }
+    diff_inner(db, expected, found)
+}
+
+/// Returns `None` if the outermost type constructors differ.
+fn diff_inner(db: &dyn AnalyzerDb, expected: TypeId, found: TypeId) -> Option<(String, String)> {
+    let diff = |expected: TypeId, found: TypeId| {
+        if expected == found {
+            ("_".to_string(), "_".to_string())
+        } else {
+            diff_inner(db, expected, found).unwrap_or_else(|| {
+                (
+                    expected.display(db).to_string(),
+                    found.display(db).to_string(),
+                )
+            })
+        }
+    };
+    let diff_size = |expected: usize, found: usize| {
+        if expected == found {
+            ("_".to_string(), "_".to_string())
+        } else {
+            (expected.to_string(), found.to_string())
+        }
+    };
+
+    match (expected.typ(db), found.typ(db)) {
+        (Type::Array(expected), Type::Array(found)) => {
+            let inner = diff(expected.inner, found.inner);
+            let size = diff_size(expected.size, found.size);
+            Some((
+                format!("Array<{}, {}>", inner.0, size.0),
+                format!("Array<{}, {}>", inner.1, size.1),
+            ))
+        }
+        (Type::Map(expected), Type::Map(found)) => {
+            let key = diff(expected.key, found.key);
+            let value = diff(expected.value, found.value);
+            Some((
+                format!("Map<{}, {}>", key.0, value.0),
+                format!("Map<{}, {}>", key.1, value.1),
+            ))
+        }
+        (Type::Tuple(expected), Type::Tuple(found))
+            if expected.items.len() == found.items.len() =>
+        {
+            let (expected, found): (Vec<_>, Vec<_>) = expected
+                .items
+                .iter()
+                .zip(found.items.iter())
+                .map(|(expected, found)| diff(*expected, *found))
+                .unzip();
+            Some((
+                format!("({})", expected.join(", ")),
+                format!("({})", found.join(", ")),
+            ))
+        }
+        (Type::String(expected), Type::String(found)) => {
+            let size = diff_size(expected.max_size, found.max_size);
+            Some((format!("String<{}>", size.0), format!("String<{}>", size.1)))
+        }
+        (Type::SPtr(expected), Type::SPtr(found)) => {
+            let inner = diff(expected, found);
+            Some((format!("SPtr<{}>", inner.0), format!("SPtr<{}>", inner.1)))
+        }
+        (Type::Mut(expected), Type::Mut(found)) => {
+            let inner = diff(expected, found);
+            Some((format!("mut {}", inner.0), format!("mut {}", inner.1)))
+        }
+        _ => None,
+    }
+}
+
 impl fmt::Display for Base {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let name = match self {
@@ -34,7 +34,8 @@ pub fn index(
         | Type::SelfContract(_)
         | Type::Generic(_)
         | Type::Struct(_)
-        | Type::Enum(_) => Err(IndexingError::NotSubscriptable),
+        | Type::Enum(_)
+        | Type::Error => Err(IndexingError::NotSubscriptable),
     }
 }
 
@@ -54,7 +55,8 @@ pub fn expected_index_type(context: &mut dyn AnalyzerContext, obj: TypeId) -> Op
         | Type::SelfContract(_)
         | Type::Generic(_)
         | Type::Enum(_)
-        | Type::Struct(_) => None,
+        | Type::Struct(_)
+        | Type::Error => None,
     }
 }
 
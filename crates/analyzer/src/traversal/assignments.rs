@@ -88,11 +88,20 @@ fn is_valid_assign_target(
                 bad_assign_target_error(scope, expr, "invalid assignment target");
                 Ok(false)
             }
-            Ok(Some(NamedThing::Variable { is_const, .. })) => {
+            Ok(Some(NamedThing::Variable { is_const, typ, .. })) => {
                 if is_const {
                     bad_assign_target_error(scope, expr, "cannot assign to a constant value");
+                    return Ok(false);
+                }
+                if matches!(typ, Ok(typ) if typ.is_sptr(scope.db())) {
+                    scope.fancy_error(
+                        &format!("cannot assign to storage reference `{name}`"),
+                        vec![Label::primary(expr.span, "")],
+                        vec![format!("Hint: assign to the fields of `{name}` instead")],
+                    );
+                    return Ok(false);
                 }
-                Ok(!is_const)
+                Ok(true)
             }
             Err(e) => Err(e.into()),
         },
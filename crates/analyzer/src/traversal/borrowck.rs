@@ -5,6 +5,7 @@ use fe_common::diagnostics::Label;
 use fe_parser::ast;
 use fe_parser::node::{Node, Span};
 use smallvec::{smallvec, SmallVec};
+use smol_str::SmolStr;
 
 // NOTE: This is a temporary solution to the only borrowing bug that's possible
 // in the current semantics of Fe, namely passing a mutable reference to a
@@ -48,7 +49,7 @@ pub fn check_fn_call_arg_borrows(
         args.insert(0, (target_expr, ty));
     }
 
-    for (idx, (arg, _)) in args
+    for (idx, (arg, ty)) in args
         .iter()
         .enumerate()
         .filter(|(_, (_, ty))| ty.is_mut(context.db()))
@@ -59,11 +60,24 @@ pub fn check_fn_call_arg_borrows(
         let vars = resolve_expr_root_vars(context, arg);
 
         // Check all other non-primitive args for the same root var.
-        for (_, (other, _)) in args
+        for (_, (other, other_ty)) in args
             .iter()
             .enumerate()
             .filter(|(i, (_, ty))| *i != idx && !ty.is_primitive(context.db()))
         {
+            // Storage references to disjoint fields, eg `self.a` and `self.b`,
+            // don't alias.
+            if ty.is_sptr(context.db()) && other_ty.is_sptr(context.db()) {
+                if let (Some(place), Some(other_place)) = (
+                    resolve_expr_storage_place(context, arg),
+                    resolve_expr_storage_place(context, other),
+                ) {
+                    if !places_overlap(&place, &other_place) {
+                        continue;
+                    }
+                }
+            }
+
             let other_vars = resolve_expr_root_vars(context, other);
             for (var, var_span) in &vars {
                 if let Some((_, other_span)) = other_vars.iter().find(|(nt, _)| nt == var) {
@@ -108,3 +122,42 @@ fn resolve_expr_root_vars(
         _ => smallvec![],
     }
 }
+
+/// The root var of a place expr and the fields that it projects, eg `self.a[i].b`
+/// is `self` with `[Some(a), None, Some(b)]`. The index of a subscript isn't
+/// known, so it's `None`.
+type Place = (NamedThing, Vec<Option<SmolStr>>);
+
+fn resolve_expr_storage_place(
+    context: &dyn AnalyzerContext,
+    expr: &Node<ast::Expr>,
+) -> Option<Place> {
+    match &expr.kind {
+        ast::Expr::Name(name) => match context.resolve_name(name, expr.span) {
+            Ok(
+                Some(nt @ NamedThing::Variable { .. }) | Some(nt @ NamedThing::SelfValue { .. }),
+            ) => Some((nt, vec![])),
+            _ => None,
+        },
+        ast::Expr::Attribute { value, attr } => {
+            let (root, mut fields) = resolve_expr_storage_place(context, value)?;
+            fields.push(Some(attr.kind.clone()));
+            Some((root, fields))
+        }
+        ast::Expr::Subscript { value, .. } => {
+            let (root, mut fields) = resolve_expr_storage_place(context, value)?;
+            fields.push(None);
+            Some((root, fields))
+        }
+        _ => None,
+    }
+}
+
+/// Returns `true` if one place may contain the other.
+fn places_overlap((root, fields): &Place, (other_root, other_fields): &Place) -> bool {
+    root == other_root
+        && fields
+            .iter()
+            .zip(other_fields)
+            .all(|(field, other)| field.is_none() || other.is_none() || field == other)
+}
@@ -3,8 +3,10 @@ use super::types::try_coerce_type;
 use crate::context::{AnalyzerContext, DiagnosticVoucher};
 use crate::display::Displayable;
 use crate::errors::{self, FatalError, TypeCoercionError, TypeError};
+use crate::namespace::items::TraitId;
 use crate::namespace::types::{FunctionParam, Generic, Type, TypeId};
-use fe_common::{diagnostics::Label, utils::humanize::pluralize_conditionally};
+use fe_common::diagnostics::{Applicability, Label, Suggestion};
+use fe_common::utils::humanize::pluralize_conditionally;
 use fe_common::{Span, Spanned};
 use fe_parser::ast as fe;
 use fe_parser::node::Node;
@@ -168,19 +170,24 @@ pub fn validate_named_args(
                 let arg_type = expr_type(context, &arg.kind.value)?;
                 for bound in bounds.iter() {
                     if !bound.is_implemented_for(context.db(), arg_type) {
-                        context.error(
-                            &format!(
+                        let mut diag = errors::error(
+                            format!(
                                 "the trait bound `{}: {}` is not satisfied",
                                 arg_type.display(context.db()),
                                 bound.name(context.db())
                             ),
                             arg.span,
-                            &format!(
+                            format!(
                                 "the trait `{}` is not implemented for `{}`",
                                 bound.name(context.db()),
                                 arg_type.display(context.db()),
                             ),
                         );
+                        diag.notes
+                            .extend(implementors_note(context, arg_type, *bound));
+                        diag.suggestions
+                            .extend(missing_bound_suggestion(context, arg_type, *bound));
+                        context.register_diag(diag);
                     }
                 }
                 arg_type
@@ -215,6 +222,22 @@ pub fn validate_named_args(
                 arg_attr.typ
             };
 
+        if param_type.is_sptr(context.db()) && !arg_type.is_sptr(context.db()) {
+            let msg = if let Some(label) = param.label() {
+                format!("`{name}` argument `{label}` must be a storage reference")
+            } else {
+                format!("`{name}` argument at position {index} must be a storage reference")
+            };
+            context.fancy_error(
+                &msg,
+                vec![Label::primary(
+                    arg.kind.value.span,
+                    "this value isn't in storage",
+                )],
+                vec!["Hint: pass a contract field, e.g. `self.balances`".into()],
+            );
+        }
+
         if param_type.is_mut(context.db()) && !arg_type.is_mut(context.db()) {
             let msg = if let Some(label) = param.label() {
                 format!("`{name}` argument `{label}` must be mutable")
@@ -226,3 +249,82 @@ pub fn validate_named_args(
     }
     Ok(())
 }
+
+/// If `typ` is a type parameter of the function being checked, suggests adding
+/// `bound` to the parameter's bounds.
+/// The number of types that are listed in the note of an unsatisfied trait
+/// bound error.
+const MAX_IMPLEMENTORS_SHOWN: usize = 4;
+
+/// Returns a note that lists the types that implement `bound`, with the types
+/// of the same kind as `typ` (e.g. other structs, or other integers) first.
+fn implementors_note(context: &dyn AnalyzerContext, typ: TypeId, bound: TraitId) -> Option<String> {
+    let db = context.db();
+    let kind = std::mem::discriminant(&typ.deref_typ(db));
+    let mut receivers: Vec<_> = bound
+        .all_impls(db)
+        .iter()
+        .map(|impl_| impl_.receiver(db))
+        .collect();
+    if receivers.is_empty() {
+        return None;
+    }
+    receivers.sort_by_key(|receiver| std::mem::discriminant(&receiver.typ(db)) != kind);
+
+    let mut names: Vec<_> = receivers
+        .iter()
+        .take(MAX_IMPLEMENTORS_SHOWN)
+        .map(|receiver| format!("`{}`", receiver.display(db)))
+        .collect();
+    if receivers.len() > MAX_IMPLEMENTORS_SHOWN {
+        names.push(format!(
+            "{} other {}",
+            receivers.len() - MAX_IMPLEMENTORS_SHOWN,
+            pluralize_conditionally("type", receivers.len() - MAX_IMPLEMENTORS_SHOWN)
+        ));
+    }
+    Some(format!(
+        "Note: the trait `{}` is implemented for {}",
+        bound.name(db),
+        names.join(", ")
+    ))
+}
+
+fn missing_bound_suggestion(
+    context: &dyn AnalyzerContext,
+    typ: TypeId,
+    bound: TraitId,
+) -> Option<Suggestion> {
+    if !context.is_in_function() {
+        return None;
+    }
+    let name = match typ.deref_typ(context.db()) {
+        Type::Generic(Generic { name, .. }) => name,
+        _ => return None,
+    };
+    let param = context
+        .parent_function()
+        .sig(context.db())
+        .generic_param(context.db(), &name)?;
+
+    let mut bounds = match &param {
+        fe::GenericParameter::Unbounded(_) => vec![],
+        fe::GenericParameter::Bounded { bounds, .. } => {
+            bounds.iter().map(|bound| bound.kind.to_string()).collect()
+        }
+    };
+    let original = if bounds.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}: {}", name, bounds.join(" + "))
+    };
+    bounds.push(bound.name(context.db()).to_string());
+    let replacement = format!("{}: {}", name, bounds.join(" + "));
+
+    Some(Suggestion {
+        message: format!("change `{original}` to `{replacement}`"),
+        span: param.span(),
+        replacement,
+        applicability: Applicability::MachineApplicable,
+    })
+}
@@ -1,20 +1,22 @@
 //! This module provides evaluator for constant expression to resolve const
 //! generics.
 
-use num_bigint::BigInt;
+use num_bigint::{BigInt, Sign};
 use num_traits::{One, ToPrimitive, Zero};
 
 use crate::{
-    context::{AnalyzerContext, Constant},
+    builtins::GlobalFunction,
+    context::{AnalyzerContext, CallType, Constant, DiagnosticVoucher},
     errors::ConstEvalError,
     namespace::types::{self, Base, Type},
 };
 
-use fe_common::{numeric, Span};
+use fe_common::{numeric, utils::keccak, Span};
 use fe_parser::{
     ast::{self, BinOperator, BoolOperator, CompOperator, UnaryOperator},
     node::Node,
 };
+use smol_str::SmolStr;
 
 /// Evaluate expression.
 ///
@@ -53,6 +55,18 @@ pub(crate) fn eval_expr(
 
         ast::Expr::Str(s) => Ok(Constant::Str(s.clone())),
 
+        ast::Expr::Call { func, args, .. } if context.is_in_function() => {
+            match context.get_call(func) {
+                Some(CallType::BuiltinFunction(GlobalFunction::Keccak256)) => args
+                    .kind
+                    .first()
+                    .and_then(|arg| eval_keccak256(context, &arg.kind.value))
+                    .map(Constant::Int)
+                    .ok_or_else(|| not_const_error(context, expr.span)),
+                _ => Err(not_const_error(context, expr.span)),
+            }
+        }
+
         // TODO: Need to evaluate attribute getter, constant constructor and const fn call.
         ast::Expr::Subscript { .. }
         | ast::Expr::Path(_)
@@ -118,7 +132,14 @@ fn eval_bin_op(
     typ: &Type,
 ) -> Result<Constant, ConstEvalError> {
     let span = lhs.span + rhs.span;
+    if let Type::String(_) = typ {
+        return match (eval_expr(context, lhs)?, eval_expr(context, rhs)?) {
+            (Constant::Str(lhs), Constant::Str(rhs)) => Ok(Constant::Str(concat_str(&lhs, &rhs))),
+            _ => panic!("an operand of a string concatenation is not a string"),
+        };
+    }
     let lhs_ty = extract_int_typ(&context.expr_typ(lhs));
+    let shift_amount = rhs;
 
     let (lhs, rhs) = (eval_expr(context, lhs)?, eval_expr(context, rhs)?);
     let (lhs, rhs) = (lhs.extract_numeric(), rhs.extract_numeric());
@@ -167,14 +188,14 @@ fn eval_bin_op(
                 // If rhs is larger than or equal to lhs type bits, then we emits overflow
                 // error.
                 if exponent >= type_bits {
-                    return Err(overflow_error(context, span));
+                    return Err(shift_overflow_error(context, span, shift_amount));
                 } else {
                     let mask = make_mask(typ);
                     (lhs * BigInt::from(2_u8).pow(exponent as u32)) & mask
                 }
             } else {
                 // If exponent is larger than usize::MAX, it causes trivially overflow.
-                return Err(overflow_error(context, span));
+                return Err(shift_overflow_error(context, span, shift_amount));
             }
         }
 
@@ -184,14 +205,14 @@ fn eval_bin_op(
                 // If rhs is larger than or equal to lhs type bits, then we emits overflow
                 // error.
                 if exponent >= type_bits {
-                    return Err(overflow_error(context, span));
+                    return Err(shift_overflow_error(context, span, shift_amount));
                 } else {
                     let mask = make_mask(typ);
                     (lhs / BigInt::from(2_u8).pow(exponent as u32)) & mask
                 }
             } else {
                 // If exponent is larger than usize::MAX, it causes trivially overflow.
-                return Err(overflow_error(context, span));
+                return Err(shift_overflow_error(context, span, shift_amount));
             }
         }
 
@@ -337,6 +358,21 @@ fn overflow_error(context: &mut dyn AnalyzerContext, span: Span) -> ConstEvalErr
     ))
 }
 
+/// Shifts by a literal or a named constant that always overflow are already
+/// reported when the shift is analyzed, so only other shift amounts are
+/// reported here.
+fn shift_overflow_error(
+    context: &mut dyn AnalyzerContext,
+    span: Span,
+    amount: &Node<ast::Expr>,
+) -> ConstEvalError {
+    if matches!(amount.kind, ast::Expr::Num(_) | ast::Expr::Name(_)) {
+        ConstEvalError::new(DiagnosticVoucher::assume_the_shift_overflow_was_reported())
+    } else {
+        overflow_error(context, span)
+    }
+}
+
 fn zero_division_error(context: &mut dyn AnalyzerContext, span: Span) -> ConstEvalError {
     ConstEvalError::new(context.error(
         "zero division error",
@@ -345,6 +381,45 @@ fn zero_division_error(context: &mut dyn AnalyzerContext, span: Span) -> ConstEv
     ))
 }
 
+/// Concatenates two constant strings.
+pub(crate) fn concat_str(lhs: &str, rhs: &str) -> SmolStr {
+    format!("{lhs}{rhs}").into()
+}
+
+/// Returns the keccak256 hash of `arg` if it is an array literal whose
+/// elements are all constant bytes, e.g. `[0x66, 0x65]` or `[A, B]` where `A`
+/// and `B` are constants.
+///
+/// Unlike [`eval_expr`], this doesn't report an error if `arg` is not a
+/// constant.
+pub(crate) fn eval_keccak256(
+    context: &mut dyn AnalyzerContext,
+    arg: &Node<ast::Expr>,
+) -> Option<BigInt> {
+    let elts = match &arg.kind {
+        ast::Expr::List { elts } => elts,
+        _ => return None,
+    };
+
+    let mut bytes = Vec::with_capacity(elts.len());
+    for elt in elts {
+        let value = match &elt.kind {
+            ast::Expr::Num(num) => numeric::Literal::new(num).parse::<BigInt>().ok()?,
+            ast::Expr::Name(name) => match context.constant_value_by_name(name, elt.span) {
+                Ok(Some(Constant::Int(value))) => value,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        bytes.push(value.to_u8()?);
+    }
+
+    Some(BigInt::from_bytes_be(
+        Sign::Plus,
+        &keccak::full_as_bytes(&bytes),
+    ))
+}
+
 /// Returns integer types embedded in `typ`.
 ///
 /// # Panic
@@ -7,6 +7,7 @@ use crate::traversal::{const_expr, expressions, types};
 use fe_common::{diagnostics::Label, utils::humanize::pluralize_conditionally};
 use fe_parser::ast as fe;
 use fe_parser::node::Node;
+use smol_str::SmolStr;
 
 /// Gather context information for var declarations and check for type errors.
 pub fn var_decl(scope: &mut BlockScope, stmt: &Node<fe::FuncStmt>) -> Result<(), FatalError> {
@@ -25,17 +26,38 @@ pub fn var_decl(scope: &mut BlockScope, stmt: &Node<fe::FuncStmt>) -> Result<(),
         .clone()
         .self_type(scope.db())
         .map(|val| val.as_trait_or_type());
-    let declared_type = types::type_desc(scope, typ, self_ty)?;
+    let declared_type = match types::type_desc(scope, typ, self_ty) {
+        Ok(typ) => typ,
+        Err(err) => {
+            add_var(scope, target, TypeId::error(scope.db()))?;
+            return Err(err.into());
+        }
+    };
     if let Type::Map(_) = declared_type.typ(scope.db()) {
-        return Err(FatalError::new(scope.error(
+        let err = FatalError::new(scope.error(
             "invalid variable type",
             typ.span,
             "`Map` type can only be used as a contract field",
-        )));
+        ));
+        add_var(scope, target, TypeId::error(scope.db()))?;
+        return Err(err);
     }
 
+    let var_type = if mut_.is_some() {
+        Type::Mut(declared_type).id(scope.db())
+    } else {
+        declared_type
+    };
+
     if let Some(value) = value {
-        let rhs = expressions::expr(scope, value, Some(declared_type))?;
+        // The variable has a known type even if its value doesn't type check.
+        let rhs = match expressions::expr(scope, value, Some(declared_type)) {
+            Ok(rhs) => rhs,
+            Err(err) => {
+                add_var(scope, target, var_type)?;
+                return Err(err);
+            }
+        };
         let should_copy = mut_.is_some() || rhs.typ.is_mut(scope.db());
         match types::try_coerce_type(scope, Some(value), rhs.typ, declared_type, should_copy) {
             Err(TypeCoercionError::RequiresToMem) => {
@@ -71,12 +93,7 @@ pub fn var_decl(scope: &mut BlockScope, stmt: &Node<fe::FuncStmt>) -> Result<(),
         );
     }
 
-    if mut_.is_some() {
-        add_var(scope, target, Type::Mut(declared_type).id(scope.db()))?;
-    } else {
-        add_var(scope, target, declared_type)?;
-    }
-    Ok(())
+    add_var(scope, target, var_type)
 }
 
 pub fn const_decl(scope: &mut BlockScope, stmt: &Node<fe::FuncStmt>) -> Result<(), FatalError> {
@@ -91,16 +108,24 @@ pub fn const_decl(scope: &mut BlockScope, stmt: &Node<fe::FuncStmt>) -> Result<(
             Ok(typ) if typ.has_fixed_size(scope.db()) => typ,
             _ => {
                 // If this conversion fails, the type must be a map (for now at least)
-                return Err(FatalError::new(scope.error(
+                let err = FatalError::new(scope.error(
                     "invalid constant type",
                     typ.span,
                     "`Map` type can only be used as a contract field",
-                )));
+                ));
+                add_error_const(scope, name);
+                return Err(err);
             }
         };
 
         // Perform semantic analysis before const evaluation.
-        let value_attributes = expressions::expr(scope, value, Some(declared_type))?;
+        let value_attributes = match expressions::expr(scope, value, Some(declared_type)) {
+            Ok(attributes) => attributes,
+            Err(err) => {
+                add_error_const(scope, name);
+                return Err(err);
+            }
+        };
 
         if declared_type != value_attributes.typ {
             scope.type_error(
@@ -112,7 +137,13 @@ pub fn const_decl(scope: &mut BlockScope, stmt: &Node<fe::FuncStmt>) -> Result<(
         }
 
         // Perform constant evaluation.
-        let const_value = const_expr::eval_expr(scope, value)?;
+        let const_value = match const_expr::eval_expr(scope, value) {
+            Ok(const_value) => const_value,
+            Err(err) => {
+                add_error_const(scope, name);
+                return Err(err.into());
+            }
+        };
 
         scope.root.map_variable_type(name, declared_type);
         // this logs a message on err, so it's safe to ignore here.
@@ -124,7 +155,20 @@ pub fn const_decl(scope: &mut BlockScope, stmt: &Node<fe::FuncStmt>) -> Result<(
     unreachable!()
 }
 
-/// Add declared variables to the scope.
+/// Add a constant whose declaration failed to the scope, so that its uses
+/// aren't reported as undefined.
+fn add_error_const(scope: &mut BlockScope, name: &Node<SmolStr>) {
+    // this logs a message on err, so it's safe to ignore here.
+    let _ = scope.add_var(
+        name.kind.as_str(),
+        TypeId::error(scope.db()),
+        true,
+        name.span,
+    );
+}
+
+/// Add declared variables to the scope. Variables of a declaration that failed
+/// to type check are added with the error type.
 fn add_var(
     scope: &mut BlockScope,
     target: &Node<fe::VarDeclTarget>,
@@ -137,38 +181,48 @@ fn add_var(
             let _ = scope.add_var(name, typ, false, target.span);
             Ok(())
         }
-        fe::VarDeclTarget::Tuple(items) => {
-            match typ.typ(scope.db()) {
-                Type::Tuple(items_ty) => {
-                    let items_ty = items_ty.items;
-                    let items_ty_len = items_ty.len();
-                    if items.len() != items_ty_len {
-                        return Err(FatalError::new(scope.fancy_error(
-                            "invalid declaration",
-                            vec![Label::primary(target.span, "")],
-                            vec![format!(
-                                "Tuple declaration has {} {} but the specified tuple type has {} {}",
-                                items.len(),
-                                pluralize_conditionally("item", items.len()),
-                                items_ty_len,
-                                pluralize_conditionally("item", items_ty_len),
-                            )],
-                        )));
-                    }
-                    for (item, item_ty) in items.iter().zip(items_ty.iter()) {
-                        add_var(scope, item, *item_ty)?;
-                    }
-                    Ok(())
+        fe::VarDeclTarget::Tuple(items) => match typ.typ(scope.db()) {
+            Type::Error => {
+                for item in items {
+                    add_var(scope, item, typ)?;
                 }
-                _ => Err(FatalError::new(scope.fancy_error(
-                    "invalid declaration",
-                    vec![Label::primary(target.span, "")],
-                    vec![format!(
-                        "Tuple declaration targets need to be declared with the tuple type but here the type is {}",
-                        typ.display(scope.db())
-                    )]
-                )))
+                Ok(())
             }
-        }
+            Type::Tuple(items_ty) => {
+                let items_ty = items_ty.items;
+                let items_ty_len = items_ty.len();
+                if items.len() != items_ty_len {
+                    let err = FatalError::new(scope.fancy_error(
+                        "invalid declaration",
+                        vec![Label::primary(target.span, "")],
+                        vec![format!(
+                            "Tuple declaration has {} {} but the specified tuple type has {} {}",
+                            items.len(),
+                            pluralize_conditionally("item", items.len()),
+                            items_ty_len,
+                            pluralize_conditionally("item", items_ty_len),
+                        )],
+                    ));
+                    add_var(scope, target, TypeId::error(scope.db()))?;
+                    return Err(err);
+                }
+                for (item, item_ty) in items.iter().zip(items_ty.iter()) {
+                    add_var(scope, item, *item_ty)?;
+                }
+                Ok(())
+            }
+            _ => {
+                let err = FatalError::new(scope.fancy_error(
+                        "invalid declaration",
+                        vec![Label::primary(target.span, "")],
+                        vec![format!(
+                            "Tuple declaration targets need to be declared with the tuple type but here the type is {}",
+                            typ.display(scope.db())
+                        )]
+                    ));
+                add_var(scope, target, TypeId::error(scope.db()))?;
+                Err(err)
+            }
+        },
     }
 }
@@ -1,10 +1,13 @@
 use super::borrowck;
 use crate::builtins::{ContractTypeMethod, GlobalFunction, Intrinsic, ValueMethod};
-use crate::context::{AnalyzerContext, CallType, Constant, ExpressionAttributes, NamedThing};
+use crate::context::{
+    AnalyzerContext, CallType, Constant, DiagnosticVoucher, ExpressionAttributes, NamedThing,
+};
 use crate::display::Displayable;
 use crate::errors::{self, FatalError, IndexingError, TypeCoercionError};
 use crate::namespace::items::{
-    EnumVariantId, EnumVariantKind, FunctionId, FunctionSigId, ImplId, Item, StructId, TypeDef,
+    EnumVariantId, EnumVariantKind, FunctionId, FunctionSigId, ImplId, Item, StructId, TraitId,
+    TypeDef,
 };
 use crate::namespace::scopes::{check_visibility, BlockScopeType};
 use crate::namespace::types::{
@@ -12,13 +15,13 @@ use crate::namespace::types::{
 };
 use crate::operations;
 use crate::traversal::call_args::{validate_arg_count, validate_named_args};
-use crate::traversal::const_expr::eval_expr;
+use crate::traversal::const_expr::{concat_str, eval_expr, eval_keccak256};
 use crate::traversal::types::{
-    apply_generic_type_args, deref_type, try_cast_type, try_coerce_type,
+    apply_generic_type_args, const_name_expr, deref_type, try_cast_type, try_coerce_type,
 };
 use crate::traversal::utils::add_bin_operations_errors;
 
-use fe_common::diagnostics::Label;
+use fe_common::diagnostics::{Applicability, Label, Suggestion};
 use fe_common::{numeric, Span};
 use fe_parser::ast as fe;
 use fe_parser::ast::GenericArg;
@@ -222,16 +225,17 @@ fn expr_repeat(
 
     let value = expr(context, value, expected_inner)?;
 
-    let size = match &len.kind {
-        GenericArg::Int(size) => Ok(size.kind),
-        GenericArg::TypeDesc(_) => Err(context.fancy_error(
-            "expected a constant u256 value",
-            vec![Label::primary(len.span, "Array length")],
-            vec!["Note: Array length must be a constant u256".to_string()],
-        )),
-        GenericArg::ConstExpr(exp) => {
-            expr(context, exp, None)?;
-            if let Constant::Int(len) = eval_expr(context, exp)? {
+    let const_expr = match &len.kind {
+        GenericArg::Int(_) => None,
+        GenericArg::TypeDesc(type_node) => const_name_expr(context, type_node),
+        GenericArg::ConstExpr(exp) => Some(exp.clone()),
+    };
+
+    let size = match (&len.kind, const_expr) {
+        (GenericArg::Int(size), _) => Ok(size.kind),
+        (_, Some(exp)) => {
+            expr(context, &exp, None)?;
+            if let Constant::Int(len) = eval_expr(context, &exp)? {
                 Ok(len.to_usize().unwrap())
             } else {
                 Err(context.fancy_error(
@@ -241,6 +245,11 @@ fn expr_repeat(
                 ))
             }
         }
+        (_, None) => Err(context.fancy_error(
+            "expected a constant u256 value",
+            vec![Label::primary(len.span, "Array length")],
+            vec!["Note: Array length must be a constant u256".to_string()],
+        )),
     };
 
     match size {
@@ -340,7 +349,15 @@ fn expr_named_thing(
     expected_type: Option<TypeId>,
 ) -> Result<ExpressionAttributes, FatalError> {
     let ty = match named_thing {
-        Some(NamedThing::Variable { typ, .. }) => Ok(typ?),
+        Some(NamedThing::Variable { typ, .. }) => {
+            let typ = typ?;
+            if typ.is_error(context.db()) {
+                return Err(FatalError::new(
+                    DiagnosticVoucher::assume_the_declaration_error_was_reported(),
+                ));
+            }
+            Ok(typ)
+        }
         Some(NamedThing::SelfValue { decl, parent, .. }) => {
             if let Some(target) = parent {
                 if decl.is_none() {
@@ -767,6 +784,36 @@ fn expr_bin_operation(
     let left_attributes = expr(context, left, left_expected)?;
     let right_attributes = expr(context, right, right_expected)?;
 
+    if matches!(op.kind, fe::BinOperator::Add)
+        && left_attributes
+            .typ
+            .deref(context.db())
+            .is_string(context.db())
+        && right_attributes
+            .typ
+            .deref(context.db())
+            .is_string(context.db())
+    {
+        return expr_str_concat(context, left, right, expected_type);
+    }
+
+    if let Some((trait_name, method_name)) = overloading_trait(op.kind) {
+        if matches!(
+            left_attributes.typ.deref_typ(context.db()),
+            Type::Struct(_) | Type::Generic(_)
+        ) {
+            return expr_overloaded_bin_operation(
+                context,
+                exp,
+                (left, left_attributes.typ),
+                op,
+                (right, right_attributes.typ),
+                trait_name,
+                method_name,
+            );
+        }
+    }
+
     match operations::bin(
         context,
         left_attributes.typ,
@@ -791,7 +838,207 @@ fn expr_bin_operation(
                 Err(FatalError::new(diag))
             }
         }
-        Ok(typ) => Ok(ExpressionAttributes::new(typ)),
+        Ok(typ) => {
+            if matches!(op.kind, fe::BinOperator::LShift | fe::BinOperator::RShift) {
+                check_shift_amount(context, typ, op, right);
+            }
+            Ok(ExpressionAttributes::new(typ))
+        }
+    }
+}
+
+/// Folds the concatenation of two constant strings into a single static
+/// string. Both operands must be constant, so the result has no runtime cost.
+fn expr_str_concat(
+    context: &mut dyn AnalyzerContext,
+    left: &Node<fe::Expr>,
+    right: &Node<fe::Expr>,
+    expected_type: Option<TypeId>,
+) -> Result<ExpressionAttributes, FatalError> {
+    let string = match (eval_expr(context, left)?, eval_expr(context, right)?) {
+        (Constant::Str(left), Constant::Str(right)) => concat_str(&left, &right),
+        _ => unreachable!(),
+    };
+    let str_len = string.len();
+
+    let max_size = expected_type
+        .and_then(|id| id.deref(context.db()).as_string(context.db()))
+        .map(|s| s.max_size)
+        .map_or(str_len, |expected_len| expected_len.max(str_len));
+
+    Ok(ExpressionAttributes {
+        typ: Type::String(FeString { max_size }).id(context.db()),
+        const_value: Some(Constant::Str(string)),
+        type_adjustments: vec![],
+    })
+}
+
+/// Emits an error if the right hand side of a shift is a constant that is at
+/// least as large as the bit width of the shifted type, in which case the
+/// result is always zero (or `-1` for right shifts of negative numbers).
+/// Returns the name of the trait in `std::ops` that overloads the operator
+/// for structs and generic parameters, and the name of its method.
+fn overloading_trait(op: fe::BinOperator) -> Option<(&'static str, &'static str)> {
+    match op {
+        fe::BinOperator::Add => Some(("Add", "add")),
+        fe::BinOperator::Sub => Some(("Sub", "sub")),
+        fe::BinOperator::Mult => Some(("Mul", "mul")),
+        fe::BinOperator::Div => Some(("Div", "div")),
+        _ => None,
+    }
+}
+
+/// Checks an arithmetic operation on a struct or a generic parameter as a call
+/// of the method of the overloading trait on the left operand, e.g. `a + b`
+/// as `a.add(b)`. The call is recorded for the operation expression.
+fn expr_overloaded_bin_operation(
+    context: &mut dyn AnalyzerContext,
+    exp: &Node<fe::Expr>,
+    (left, left_type): (&Node<fe::Expr>, TypeId),
+    op: &Node<fe::BinOperator>,
+    (right, right_type): (&Node<fe::Expr>, TypeId),
+    trait_name: &str,
+    method_name: &str,
+) -> Result<ExpressionAttributes, FatalError> {
+    if left_type.is_sptr(context.db()) {
+        return Err(FatalError::new(
+            context.register_diag(errors::to_mem_error(left.span)),
+        ));
+    }
+    let typ = left_type.deref(context.db());
+
+    let call = match typ.typ(context.db()) {
+        Type::Generic(generic) => generic
+            .bounds
+            .iter()
+            .find(|bound| bound.is_std_trait(context.db(), trait_name))
+            .and_then(|trait_id| {
+                let method = trait_id.function(context.db(), method_name)?;
+                Some((
+                    method,
+                    CallType::TraitValueMethod {
+                        trait_id: *trait_id,
+                        method,
+                        generic_type: generic.clone(),
+                    },
+                ))
+            }),
+        _ => context
+            .db()
+            .all_impls(typ)
+            .iter()
+            .find(|impl_| {
+                impl_
+                    .trait_id(context.db())
+                    .is_std_trait(context.db(), trait_name)
+            })
+            .and_then(|impl_| impl_.function(context.db(), method_name))
+            .map(|method| {
+                (
+                    method.sig(context.db()),
+                    CallType::ValueMethod { typ, method },
+                )
+            }),
+    };
+
+    let (sig, call) = match call {
+        Some(call) => call,
+        None => {
+            let hint = if typ.is_generic(context.db()) {
+                format!(
+                    "Hint: add the bound `std::ops::{trait_name}` to `{}`",
+                    typ.display(context.db())
+                )
+            } else {
+                format!(
+                    "Hint: implement `std::ops::{trait_name}` for `{}` to use `{}`",
+                    typ.display(context.db()),
+                    op.kind
+                )
+            };
+            return Err(FatalError::new(context.fancy_error(
+                &format!(
+                    "cannot apply `{}` to `{}`",
+                    op.kind,
+                    typ.display(context.db())
+                ),
+                vec![Label::primary(
+                    op.span,
+                    format!(
+                        "`{}` doesn't implement `std::ops::{trait_name}`",
+                        typ.display(context.db())
+                    ),
+                )],
+                vec![hint],
+            )));
+        }
+    };
+
+    match try_coerce_type(context, Some(right), right_type, typ, false) {
+        Err(TypeCoercionError::RequiresToMem) => {
+            context.register_diag(errors::to_mem_error(right.span));
+        }
+        Err(_) => {
+            context.type_error(
+                &format!("incorrect type for the right operand of `{}`", op.kind),
+                right.span,
+                typ,
+                right_type,
+            );
+        }
+        Ok(_) => {}
+    }
+
+    let return_type = sig.signature(context.db()).return_type.clone()?;
+    context.add_call(exp, call);
+    if return_type.is_self_ty(context.db()) {
+        Ok(ExpressionAttributes::new(typ))
+    } else {
+        Ok(ExpressionAttributes::new(return_type))
+    }
+}
+
+fn check_shift_amount(
+    context: &mut dyn AnalyzerContext,
+    typ: TypeId,
+    op: &Node<fe::BinOperator>,
+    amount: &Node<fe::Expr>,
+) {
+    let int = match typ.deref(context.db()).as_int(context.db()) {
+        Some(int) => int,
+        None => return,
+    };
+
+    let value = match &amount.kind {
+        fe::Expr::Num(num) => numeric::Literal::new(num).parse::<BigInt>().ok(),
+        fe::Expr::Name(name) => match context.constant_value_by_name(name, amount.span) {
+            Ok(Some(Constant::Int(value))) => Some(value),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    if let Some(value) = value {
+        if value >= BigInt::from(int.bits()) {
+            let direction = if op.kind == fe::BinOperator::LShift {
+                "left"
+            } else {
+                "right"
+            };
+            context.fancy_error(
+                &format!("this shift of `{}` will always overflow", int),
+                vec![Label::primary(
+                    op.span + amount.span,
+                    format!("attempt to shift {direction} by `{value}`, which would overflow"),
+                )],
+                vec![format!(
+                    "Note: `{}` is {} bits wide, so the shift amount must be less than {}",
+                    int,
+                    int.bits(),
+                    int.bits()
+                )],
+            );
+        }
     }
 }
 
@@ -1253,16 +1500,25 @@ fn expr_call_builtin_function(
         );
     }
 
-    let argument_attributes = expr_call_args(context, args)?;
+    let argument_attributes = if function == GlobalFunction::Keccak256 {
+        expr_keccak256_args(context, args)?
+    } else {
+        expr_call_args(context, args)?
+    };
 
     let attrs = match function {
         GlobalFunction::Keccak256 => {
             validate_arg_count(context, function.as_ref(), name_span, args, 1, "argument");
             expect_no_label_on_arg(context, args, 0);
 
+            let mut const_value = None;
             if let Some(arg_typ) = argument_attributes.first().map(|attr| &attr.typ) {
                 match arg_typ.typ(context.db()) {
-                    Type::Array(Array { inner, .. }) if inner.typ(context.db()) == Type::u8() => {}
+                    Type::Array(Array { inner, .. }) if inner.typ(context.db()) == Type::u8() => {
+                        // Hash literal byte arrays at compile time.
+                        const_value =
+                            eval_keccak256(context, &args.kind[0].kind.value).map(Constant::Int);
+                    }
                     _ => {
                         context.fancy_error(
                             &format!(
@@ -1279,7 +1535,11 @@ fn expr_call_builtin_function(
                     }
                 }
             };
-            ExpressionAttributes::new(TypeId::int(context.db(), Integer::U256))
+            ExpressionAttributes {
+                typ: TypeId::int(context.db(), Integer::U256),
+                const_value,
+                type_adjustments: vec![],
+            }
         }
     };
     Ok((attrs, CallType::BuiltinFunction(function)))
@@ -1561,8 +1821,16 @@ fn expr_call_method(
 
     let target_attributes = expr(context, target, None)?;
 
-    // Check built-in methods.
-    if let Ok(method) = ValueMethod::from_str(&field.kind) {
+    // Check built-in methods. The methods of integers don't shadow methods of
+    // the same name that other types define.
+    let is_integer = target_attributes
+        .typ
+        .deref(context.db())
+        .is_integer(context.db());
+    if let Some(method) = ValueMethod::from_str(&field.kind)
+        .ok()
+        .filter(|method| is_integer || !method.is_integer_method())
+    {
         return expr_call_builtin_value_method(
             context,
             target_attributes,
@@ -1578,6 +1846,10 @@ fn expr_call_method(
         check_for_call_to_special_fns(context, &field.kind, field.span)?;
     }
 
+    if let Type::Generic(generic) = obj_type.typ(context.db()) {
+        validate_single_bound_defines_method(context, &generic, field);
+    }
+
     match obj_type.function_sigs(context.db(), &field.kind).as_ref() {
         [] => Err(FatalError::new(context.fancy_error(
             &format!(
@@ -1641,6 +1913,19 @@ fn expr_call_method(
                             method,
                         }
                     } else {
+                        if sig.params.iter().any(|param| {
+                            matches!(&param.typ, Ok(typ) if typ.is_calldata_bytes(context.db()))
+                        }) {
+                            context.fancy_error(
+                                &format!("`{}` can't be called on another contract", &field.kind),
+                                vec![Label::primary(
+                                    field.span,
+                                    "this function takes calldata bytes",
+                                )],
+                                vec!["Note: calldata bytes only exist in the current call".into()],
+                            );
+                        }
+
                         // Contract address needs to be on the stack
                         deref_type(context, target, target_attributes.typ);
                         CallType::External {
@@ -1650,7 +1935,10 @@ fn expr_call_method(
                     }
                 }
                 Type::Generic(inner) => CallType::TraitValueMethod {
-                    trait_id: *inner.bounds.first().expect("expected trait bound"),
+                    trait_id: match method.parent(context.db()) {
+                        Item::Trait(id) => id,
+                        _ => unreachable!("generic method must be defined by a trait bound"),
+                    },
                     method: *method,
                     generic_type: inner,
                 },
@@ -1723,6 +2011,54 @@ fn expr_call_method(
     }
 }
 
+/// Reports a call of a method that more than one bound of the generic
+/// parameter defines. The call resolves to the method of the first bound.
+fn validate_single_bound_defines_method(
+    context: &mut dyn AnalyzerContext,
+    generic: &types::Generic,
+    field: &Node<SmolStr>,
+) {
+    let candidates: Vec<_> = generic
+        .bounds
+        .iter()
+        .filter_map(|bound| {
+            bound
+                .function(context.db(), &field.kind)
+                .map(|fun| (*bound, fun))
+        })
+        .collect();
+    if candidates.len() < 2 {
+        return;
+    }
+
+    let labels = std::iter::once(Label::primary(
+        field.span,
+        format!(
+            "multiple bounds of `{}` define `{}`",
+            generic.name, field.kind
+        ),
+    ))
+    .chain(candidates.iter().enumerate().map(|(idx, (bound, fun))| {
+        Label::secondary(
+            fun.name_span(context.db()),
+            format!(
+                "candidate #{} is defined here on trait `{}`",
+                idx + 1,
+                bound.name(context.db())
+            ),
+        )
+    }))
+    .collect();
+    context.fancy_error(
+        "multiple applicable items in scope",
+        labels,
+        vec![format!(
+            "Hint: rename one of the methods or remove one of the bounds of `{}`",
+            generic.name
+        )],
+    );
+}
+
 fn validate_trait_in_scope(
     context: &mut dyn AnalyzerContext,
     name_span: Span,
@@ -1730,14 +2066,15 @@ fn validate_trait_in_scope(
     impl_: ImplId,
 ) {
     let treit = impl_.trait_id(context.db());
-    let type_name = impl_.receiver(context.db()).name(context.db());
+    let receiver = impl_.receiver(context.db());
+    let type_name = receiver.name(context.db());
 
     if !context
         .module()
         .is_in_scope(context.db(), Item::Trait(treit))
     {
-        context.fancy_error(
-            &format!(
+        let mut diag = errors::fancy_error(
+            format!(
                 "No method named `{}` found for type `{}` in the current scope",
                 called_fn.name(context.db()),
                 type_name
@@ -1751,9 +2088,65 @@ fn validate_trait_in_scope(
                 format!("Hint: the following trait is implemented but not in scope; perhaps add a `use` for it: `trait {}`", treit.name(context.db()))
             ],
         );
+
+        // Every trait that provides a method of this name for the receiver
+        // could be imported to fix the call. Only a single one can be imported
+        // without the user choosing between them.
+        let db = context.db();
+        let file_start = Span::new(name_span.file_id, 0, 0);
+        let paths: Vec<_> = db
+            .traits_with_function(context.module().ingot(db), called_fn.name(db))
+            .iter()
+            .filter(|treit| treit.is_implemented_for(db, receiver))
+            .filter_map(|treit| trait_import_path(context, *treit))
+            .collect();
+        let applicability = if paths.len() == 1 {
+            Applicability::MachineApplicable
+        } else {
+            Applicability::MaybeIncorrect
+        };
+        diag.suggestions
+            .extend(paths.into_iter().map(|path| Suggestion {
+                message: format!("import the trait with `use {path}`"),
+                span: file_start,
+                replacement: format!("use {path}\n"),
+                applicability,
+            }));
+        context.register_diag(diag);
     }
 }
 
+/// Returns the path that imports `treit` into the current module, if the trait
+/// is visible there.
+fn trait_import_path(context: &dyn AnalyzerContext, treit: TraitId) -> Option<String> {
+    let db = context.db();
+    let mut module = treit.module(db);
+    if !treit.is_public(db) && module != context.module() {
+        return None;
+    }
+
+    let current_ingot = context.module().ingot(db);
+    let ingot_name = if module.ingot(db) == current_ingot {
+        "ingot".into()
+    } else {
+        current_ingot
+            .external_ingots(db)
+            .iter()
+            .find(|(_, ingot)| **ingot == module.ingot(db))?
+            .0
+            .clone()
+    };
+
+    let mut segments = vec![treit.name(db)];
+    while let Some(parent) = module.parent_module(db) {
+        segments.push(module.name(db));
+        module = parent;
+    }
+    segments.push(ingot_name);
+    segments.reverse();
+    Some(segments.join("::"))
+}
+
 fn expr_call_builtin_value_method(
     context: &mut dyn AnalyzerContext,
     mut value_attrs: ExpressionAttributes,
@@ -1835,6 +2228,16 @@ fn expr_call_builtin_value_method(
         ValueMethod::AbiEncode => {
             abi_encoded_type(context, value_attrs.typ, value.span).map(|attr| (attr, calltype))
         }
+        ValueMethod::LeadingZeros | ValueMethod::CountOnes => {
+            let ty = deref_type(context, value, ty);
+            // The number of bits of a `u256` doesn't fit in a `u8`, so the
+            // result is always a `u256`.
+            let calltype = CallType::BuiltinValueMethod { method, typ: ty };
+            Ok((
+                ExpressionAttributes::new(TypeId::int(context.db(), Integer::U256)),
+                calltype,
+            ))
+        }
     }
 }
 
@@ -2007,17 +2410,23 @@ fn expr_call_type_attribute(
                 vec![],
             )));
         } else {
-            context.fancy_error(
+            // `Type.function()` is the call syntax of older versions of Fe, so
+            // the fix is machine-applicable for `fe fix`.
+            let mut diag = errors::fancy_error(
                 "Static functions need to be called with `::` not `.`",
                 vec![Label::primary(
                     field.span,
                     "This is a static function (doesn't take a `self` parameter)",
                 )],
-                vec![format!(
-                    "Try `{}::{}(...)` instead",
-                    &target_name, &field.kind
-                )],
+                vec![],
             );
+            diag.suggestions.push(Suggestion {
+                message: format!("use `{}::{}(...)` instead", &target_name, &field.kind),
+                span: Span::new(target_span.file_id, target_span.end, field.span.start),
+                replacement: "::".into(),
+                applicability: Applicability::MachineApplicable,
+            });
+            context.register_diag(diag);
         }
 
         validate_visibility_of_called_fn(context, field.span, sig);
@@ -2056,6 +2465,27 @@ fn expr_call_args(
         .collect::<Result<Vec<_>, _>>()
 }
 
+/// Like [`expr_call_args`], but a list literal argument is analyzed as a byte
+/// array, so that e.g. `keccak256([0x61, 0x62])` hashes two bytes.
+fn expr_keccak256_args(
+    context: &mut dyn AnalyzerContext,
+    args: &Node<Vec<Node<fe::CallArg>>>,
+) -> Result<Vec<ExpressionAttributes>, FatalError> {
+    args.kind
+        .iter()
+        .map(|arg| {
+            let expected = match &arg.kind.value.kind {
+                fe::Expr::List { elts } => Some(context.db().intern_type(Type::Array(Array {
+                    size: elts.len(),
+                    inner: TypeId::int(context.db(), Integer::U8),
+                }))),
+                _ => None,
+            };
+            expr(context, &arg.kind.value, expected)
+        })
+        .collect::<Result<Vec<_>, _>>()
+}
+
 fn expect_no_label_on_arg(
     context: &mut dyn AnalyzerContext,
     args: &Node<Vec<Node<fe::CallArg>>>,
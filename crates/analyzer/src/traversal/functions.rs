@@ -15,14 +15,21 @@ use smol_str::SmolStr;
 
 use super::matching_anomaly;
 
+/// Checks every statement, even after one of them fails, so that independent
+/// errors are all reported. Returns the first error.
 pub fn traverse_statements(
     scope: &mut BlockScope,
     body: &[Node<fe::FuncStmt>],
 ) -> Result<(), FatalError> {
+    let mut result = Ok(());
     for stmt in body.iter() {
-        func_stmt(scope, stmt)?
+        if let Err(err) = func_stmt(scope, stmt) {
+            if result.is_ok() {
+                result = Err(err);
+            }
+        }
     }
-    Ok(())
+    result
 }
 
 fn func_stmt(scope: &mut BlockScope, stmt: &Node<fe::FuncStmt>) -> Result<(), FatalError> {
@@ -712,6 +719,25 @@ fn revert(scope: &mut BlockScope, stmt: &Node<fe::FuncStmt>) -> Result<(), Fatal
     unreachable!()
 }
 
+/// The span of the return type written in the function signature, if it
+/// determines the expected type of `return` statements. `__init__` and
+/// `__call__` always return `()`, regardless of their signature.
+fn declared_return_type_span(scope: &BlockScope) -> Option<Span> {
+    let function = scope.root.function;
+    if matches!(function.name(scope.db()).as_str(), "__init__" | "__call__") {
+        return None;
+    }
+    function
+        .data(scope.db())
+        .ast
+        .kind
+        .sig
+        .kind
+        .return_type
+        .as_ref()
+        .map(|typ| typ.span)
+}
+
 fn func_return(scope: &mut BlockScope, stmt: &Node<fe::FuncStmt>) -> Result<(), FatalError> {
     if let fe::FuncStmt::Return { value } = &stmt.kind {
         let expected_type = scope.root.function_return_type()?.deref(scope.db());
@@ -727,14 +753,24 @@ fn func_return(scope: &mut BlockScope, stmt: &Node<fe::FuncStmt>) -> Result<(),
                 scope.add_diagnostic(errors::to_mem_error(value.span));
             }
             Err(TypeCoercionError::Incompatible) => {
-                scope.error(
+                let mut labels = vec![Label::primary(stmt.span, "")];
+                if let Some(return_type) = declared_return_type_span(scope) {
+                    labels.push(Label::secondary(
+                        return_type,
+                        format!(
+                            "expected `{}` because of return type",
+                            expected_type.display(scope.db())
+                        ),
+                    ));
+                }
+                scope.fancy_error(
                     &format!(
                         "expected function to return `{}` but was `{}`",
                         expected_type.display(scope.db()),
                         value_attr.typ.deref(scope.db()).display(scope.db())
                     ),
-                    stmt.span,
-                    "",
+                    labels,
+                    vec![],
                 );
             }
             Err(TypeCoercionError::SelfContractType) => {
@@ -5,6 +5,7 @@ pub mod types;
 
 pub(crate) mod const_expr;
 pub(crate) mod expressions;
+pub(crate) mod secrets;
 
 mod assignments;
 mod borrowck;
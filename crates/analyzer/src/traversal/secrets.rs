@@ -0,0 +1,324 @@
+//! Checks that functions don't branch on secret values, i.e. values read from
+//! `#secret` struct fields and values computed from them. The gas used by a
+//! call reveals which branches it took, and so the secret values they
+//! depended on. The check is opt-in, and only runs for functions that are
+//! marked `#check_secrets`, or that belong to a contract, struct or enum that
+//! is.
+//!
+//! Secret values are tracked through local variables without regard to the
+//! order of statements, so a variable that is ever assigned a secret value is
+//! secret everywhere in the function. Values returned from function calls are
+//! only secret if the arguments of the call are.
+use crate::context::AnalyzerContext;
+use crate::namespace::scopes::FunctionScope;
+use crate::namespace::types::TypeId;
+use fe_common::diagnostics::Label;
+use fe_parser::ast;
+use fe_parser::node::{Node, Span};
+use smol_str::SmolStr;
+use std::collections::HashSet;
+
+pub fn check_secret_branches(scope: &FunctionScope, body: &[Node<ast::FuncStmt>]) {
+    let mut checker = SecretChecker {
+        scope,
+        secrets: HashSet::new(),
+    };
+
+    // Propagate secrets until there are no new secret variables, so that
+    // assignments in loops reach the conditions before them.
+    loop {
+        let count = checker.secrets.len();
+        checker.collect_secrets(body);
+        if checker.secrets.len() == count {
+            break;
+        }
+    }
+
+    checker.check_stmts(body);
+}
+
+struct SecretChecker<'a, 'b> {
+    scope: &'a FunctionScope<'b>,
+    secrets: HashSet<SmolStr>,
+}
+
+impl<'a, 'b> SecretChecker<'a, 'b> {
+    fn collect_secrets(&mut self, body: &[Node<ast::FuncStmt>]) {
+        for stmt in body {
+            match &stmt.kind {
+                ast::FuncStmt::VarDecl {
+                    target,
+                    value: Some(value),
+                    ..
+                } if self.is_secret(value) => self.add_target(target),
+                ast::FuncStmt::Assign { target, value }
+                | ast::FuncStmt::AugAssign { target, value, .. }
+                    if self.is_secret(value) =>
+                {
+                    if let Some(name) = root_name(target) {
+                        self.secrets.insert(name);
+                    }
+                }
+                ast::FuncStmt::For { target, iter, body } => {
+                    if self.is_secret(iter) {
+                        self.secrets.insert(target.kind.clone());
+                    }
+                    self.collect_secrets(body);
+                }
+                ast::FuncStmt::While { body, .. } | ast::FuncStmt::Unsafe(body) => {
+                    self.collect_secrets(body)
+                }
+                ast::FuncStmt::If { body, or_else, .. } => {
+                    self.collect_secrets(body);
+                    self.collect_secrets(or_else);
+                }
+                ast::FuncStmt::Match { expr, arms } => {
+                    let is_secret = self.is_secret(expr);
+                    let ty = self.expr_type(expr);
+                    for arm in arms {
+                        self.add_pattern(&arm.kind.pat, is_secret, ty);
+                        self.collect_secrets(&arm.kind.body);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn add_target(&mut self, target: &Node<ast::VarDeclTarget>) {
+        match &target.kind {
+            ast::VarDeclTarget::Name(name) => {
+                self.secrets.insert(name.clone());
+            }
+            ast::VarDeclTarget::Tuple(items) => items.iter().for_each(|item| self.add_target(item)),
+        }
+    }
+
+    /// Marks the variables bound by `pat` as secret if the matched value is,
+    /// or if they're bound to `#secret` fields of the matched struct.
+    fn add_pattern(&mut self, pat: &Node<ast::Pattern>, is_secret: bool, ty: Option<TypeId>) {
+        match &pat.kind {
+            ast::Pattern::Path(path) if is_secret => {
+                if let [name] = path.kind.segments.as_slice() {
+                    self.secrets.insert(name.kind.clone());
+                }
+            }
+            ast::Pattern::Tuple(pats) | ast::Pattern::PathTuple(_, pats) => pats
+                .iter()
+                .for_each(|pat| self.add_pattern(pat, is_secret, None)),
+            ast::Pattern::PathStruct { fields, .. } => {
+                for (name, pat) in fields {
+                    let is_secret = is_secret || self.is_secret_field(ty, name);
+                    self.add_pattern(pat, is_secret, None);
+                }
+            }
+            ast::Pattern::Or(pats) => pats
+                .iter()
+                .for_each(|pat| self.add_pattern(pat, is_secret, ty)),
+            _ => {}
+        }
+    }
+
+    fn check_stmts(&self, body: &[Node<ast::FuncStmt>]) {
+        for stmt in body {
+            match &stmt.kind {
+                ast::FuncStmt::If {
+                    test,
+                    body,
+                    or_else,
+                } => {
+                    self.check_expr(test);
+                    if self.is_secret(test) {
+                        let exit = first_exit(body).or_else(|| first_exit(or_else));
+                        self.branch_error(test.span, exit);
+                    }
+                    self.check_stmts(body);
+                    self.check_stmts(or_else);
+                }
+                ast::FuncStmt::While { test, body } => {
+                    self.check_expr(test);
+                    if self.is_secret(test) {
+                        self.branch_error(test.span, first_exit(body));
+                    }
+                    self.check_stmts(body);
+                }
+                ast::FuncStmt::Match { expr, arms } => {
+                    self.check_expr(expr);
+                    if self.is_secret(expr) {
+                        let exit = arms.iter().find_map(|arm| first_exit(&arm.kind.body));
+                        self.branch_error(expr.span, exit);
+                    }
+                    arms.iter().for_each(|arm| self.check_stmts(&arm.kind.body));
+                }
+                ast::FuncStmt::Assert { test, msg } => {
+                    self.check_expr(test);
+                    if self.is_secret(test) {
+                        self.branch_error(test.span, None);
+                    }
+                    if let Some(msg) = msg {
+                        self.check_expr(msg);
+                    }
+                }
+                ast::FuncStmt::For { iter, body, .. } => {
+                    self.check_expr(iter);
+                    self.check_stmts(body);
+                }
+                ast::FuncStmt::Unsafe(body) => self.check_stmts(body),
+                ast::FuncStmt::Return { value: Some(expr) }
+                | ast::FuncStmt::Revert { error: Some(expr) }
+                | ast::FuncStmt::Expr { value: expr }
+                | ast::FuncStmt::VarDecl {
+                    value: Some(expr), ..
+                }
+                | ast::FuncStmt::ConstantDecl { value: expr, .. } => self.check_expr(expr),
+                ast::FuncStmt::Assign { target, value }
+                | ast::FuncStmt::AugAssign { target, value, .. } => {
+                    self.check_expr(target);
+                    self.check_expr(value);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Reports ternary expressions with secret conditions inside of `expr`.
+    fn check_expr(&self, expr: &Node<ast::Expr>) {
+        match &expr.kind {
+            ast::Expr::Ternary {
+                if_expr,
+                test,
+                else_expr,
+            } => {
+                if self.is_secret(test) {
+                    self.branch_error(test.span, None);
+                }
+                self.check_expr(if_expr);
+                self.check_expr(test);
+                self.check_expr(else_expr);
+            }
+            ast::Expr::BoolOperation { left, right, .. }
+            | ast::Expr::BinOperation { left, right, .. }
+            | ast::Expr::CompOperation { left, right, .. } => {
+                self.check_expr(left);
+                self.check_expr(right);
+            }
+            ast::Expr::UnaryOperation { operand: value, .. }
+            | ast::Expr::Attribute { value, .. }
+            | ast::Expr::Repeat { value, .. } => self.check_expr(value),
+            ast::Expr::Subscript { value, index } => {
+                self.check_expr(value);
+                self.check_expr(index);
+            }
+            ast::Expr::Call { func, args, .. } => {
+                self.check_expr(func);
+                args.kind
+                    .iter()
+                    .for_each(|arg| self.check_expr(&arg.kind.value));
+            }
+            ast::Expr::List { elts } | ast::Expr::Tuple { elts } => {
+                elts.iter().for_each(|elt| self.check_expr(elt))
+            }
+            ast::Expr::Bool(_)
+            | ast::Expr::Name(_)
+            | ast::Expr::Path(_)
+            | ast::Expr::Num(_)
+            | ast::Expr::Str(_)
+            | ast::Expr::Unit => {}
+        }
+    }
+
+    fn is_secret(&self, expr: &Node<ast::Expr>) -> bool {
+        match &expr.kind {
+            ast::Expr::Name(name) => self.secrets.contains(name),
+            ast::Expr::Attribute { value, attr } => {
+                self.is_secret_field(self.expr_type(value), attr) || self.is_secret(value)
+            }
+            ast::Expr::Ternary {
+                if_expr,
+                test,
+                else_expr,
+            } => self.is_secret(if_expr) || self.is_secret(test) || self.is_secret(else_expr),
+            ast::Expr::BoolOperation { left, right, .. }
+            | ast::Expr::BinOperation { left, right, .. }
+            | ast::Expr::CompOperation { left, right, .. }
+            | ast::Expr::Subscript {
+                value: left,
+                index: right,
+            } => self.is_secret(left) || self.is_secret(right),
+            ast::Expr::UnaryOperation { operand: value, .. } | ast::Expr::Repeat { value, .. } => {
+                self.is_secret(value)
+            }
+            ast::Expr::Call { func, args, .. } => {
+                let receiver_is_secret = match &func.kind {
+                    ast::Expr::Attribute { value, .. } => self.is_secret(value),
+                    _ => false,
+                };
+                receiver_is_secret || args.kind.iter().any(|arg| self.is_secret(&arg.kind.value))
+            }
+            ast::Expr::List { elts } | ast::Expr::Tuple { elts } => {
+                elts.iter().any(|elt| self.is_secret(elt))
+            }
+            ast::Expr::Bool(_)
+            | ast::Expr::Path(_)
+            | ast::Expr::Num(_)
+            | ast::Expr::Str(_)
+            | ast::Expr::Unit => false,
+        }
+    }
+
+    fn is_secret_field(&self, ty: Option<TypeId>, name: &Node<SmolStr>) -> bool {
+        let db = self.scope.db();
+        ty.and_then(|ty| ty.deref(db).as_struct(db))
+            .and_then(|struct_| struct_.field(db, &name.kind))
+            .is_some_and(|field| field.is_secret(db))
+    }
+
+    fn expr_type(&self, expr: &Node<ast::Expr>) -> Option<TypeId> {
+        self.scope
+            .body
+            .borrow()
+            .expressions
+            .get(&expr.id)
+            .map(|attributes| attributes.typ)
+    }
+
+    fn branch_error(&self, span: Span, exit: Option<Span>) {
+        let mut labels = vec![Label::primary(span, "this depends on a secret value")];
+        if let Some(exit) = exit {
+            labels.push(Label::secondary(exit, "exits early depending on it"));
+        }
+        self.scope.fancy_error(
+            "branching on a secret value",
+            labels,
+            vec![
+                "Note: the gas used by a call reveals which branches it took".into(),
+                "Hint: values read from `#secret` struct fields, and values computed from them, are secret".into(),
+            ],
+        );
+    }
+}
+
+/// Returns the root variable of an assignment target, e.g. `a` for `a.b[c]`.
+fn root_name(expr: &Node<ast::Expr>) -> Option<SmolStr> {
+    match &expr.kind {
+        ast::Expr::Name(name) => Some(name.clone()),
+        ast::Expr::Attribute { value, .. } | ast::Expr::Subscript { value, .. } => root_name(value),
+        _ => None,
+    }
+}
+
+/// Returns the span of the first `return` or `revert` statement in `body`,
+/// or of an `assert` that may revert.
+fn first_exit(body: &[Node<ast::FuncStmt>]) -> Option<Span> {
+    body.iter().find_map(|stmt| match &stmt.kind {
+        ast::FuncStmt::Return { .. }
+        | ast::FuncStmt::Revert { .. }
+        | ast::FuncStmt::Assert { .. } => Some(stmt.span),
+        ast::FuncStmt::If { body, or_else, .. } => first_exit(body).or_else(|| first_exit(or_else)),
+        ast::FuncStmt::While { body, .. }
+        | ast::FuncStmt::For { body, .. }
+        | ast::FuncStmt::Unsafe(body) => first_exit(body),
+        ast::FuncStmt::Match { arms, .. } => arms.iter().find_map(|arm| first_exit(&arm.kind.body)),
+        _ => None,
+    })
+}
@@ -375,31 +375,21 @@ pub fn apply_generic_type_args(
                 Ok(GenericArg::Int(int_node.kind))
             }
 
-            (GenericParamKind::Int, ast::GenericArg::TypeDesc(_)) => {
-                Err(TypeError::new(context.fancy_error(
-                    &format!("`{}` {} must be an integer", generic.name(), param.name),
-                    vec![Label::primary(arg.span(), "expected an integer")],
-                    vec![],
-                )))
+            (GenericParamKind::Int, ast::GenericArg::TypeDesc(type_node)) => {
+                // A constant name (eg. `MAX` in `Array<u8, MAX>`) is parsed as a type.
+                if let Some(expr) = const_name_expr(context, type_node) {
+                    eval_int_generic_arg(context, &expr)
+                } else {
+                    Err(TypeError::new(context.fancy_error(
+                        &format!("`{}` {} must be an integer", generic.name(), param.name),
+                        vec![Label::primary(arg.span(), "expected an integer")],
+                        vec![],
+                    )))
+                }
             }
 
             (GenericParamKind::Int, ast::GenericArg::ConstExpr(expr)) => {
-                // Performs semantic analysis on `expr`.
-                super::expressions::expr(context, expr, None)?;
-
-                // Evaluates expression.
-                let const_value = super::const_expr::eval_expr(context, expr)?;
-
-                // TODO: Fix me when `GenericArg` can represent literals not only `Int`.
-                match const_value {
-                    Constant::Int(val) => Ok(GenericArg::Int(val.try_into().unwrap())),
-                    Constant::Address(_) | Constant::Bool(_) | Constant::Str(_) => {
-                        Err(TypeError::new(context.not_yet_implemented(
-                            "non numeric type const generics",
-                            expr.span,
-                        )))
-                    }
-                }
+                eval_int_generic_arg(context, expr)
             }
 
             (GenericParamKind::PrimitiveType, ast::GenericArg::TypeDesc(type_node)) => {
@@ -441,6 +431,47 @@ pub fn apply_generic_type_args(
         .expect("failed to construct generic type after checking args"))
 }
 
+fn eval_int_generic_arg(
+    context: &mut dyn AnalyzerContext,
+    expr: &Node<ast::Expr>,
+) -> Result<GenericArg, TypeError> {
+    // Performs semantic analysis on `expr`.
+    super::expressions::expr(context, expr, None)?;
+
+    // Evaluates expression.
+    let const_value = super::const_expr::eval_expr(context, expr)?;
+
+    // TODO: Fix me when `GenericArg` can represent literals not only `Int`.
+    match const_value {
+        Constant::Int(val) => Ok(GenericArg::Int(val.try_into().unwrap())),
+        Constant::Address(_) | Constant::Bool(_) | Constant::Str(_) => Err(TypeError::new(
+            context.not_yet_implemented("non numeric type const generics", expr.span),
+        )),
+    }
+}
+
+/// Returns an expression referring to the constant named by `type_node`, if
+/// `type_node` is a name or path that resolves to a module or local constant.
+pub fn const_name_expr(
+    context: &dyn AnalyzerContext,
+    type_node: &Node<ast::TypeDesc>,
+) -> Option<Node<ast::Expr>> {
+    let expr = match &type_node.kind {
+        ast::TypeDesc::Base { base } => match context.resolve_name(base, type_node.span) {
+            Ok(Some(
+                NamedThing::Item(Item::Constant(_)) | NamedThing::Variable { is_const: true, .. },
+            )) => ast::Expr::Name(base.clone()),
+            _ => return None,
+        },
+        ast::TypeDesc::Path(path) => match context.resolve_visible_path(path) {
+            Some(NamedThing::Item(Item::Constant(_))) => ast::Expr::Path(path.clone()),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(Node::new(expr, type_node.span))
+}
+
 fn friendly_generic_arg_example_string(generic: GenericType) -> String {
     let example_args = generic
         .params()
@@ -566,6 +597,11 @@ pub fn type_desc(
             })))
         }
         ast::TypeDesc::Unit => Ok(TypeId::unit(context.db())),
+        ast::TypeDesc::StorageRef { .. } => Err(TypeError::new(context.fancy_error(
+            "storage references can only be used as function parameter types",
+            vec![Label::primary(desc.span, "not allowed here")],
+            vec!["Example: `fn reset(mut balances: &storage Map<address, u256>)`".into()],
+        ))),
         ast::TypeDesc::SelfType => {
             if let Some(val) = self_type {
                 Ok(Type::SelfType(val).id(context.db()))
@@ -2,6 +2,7 @@ use fe_analyzer::display::Displayable;
 use fe_analyzer::namespace::items::{self, IngotId, IngotMode, Item, ModuleId, TypeDef};
 use fe_analyzer::{AnalyzerDb, TestDb};
 use fe_common::diagnostics::{diagnostics_string, print_diagnostics, Diagnostic, Label, Severity};
+use fe_common::edition::Edition;
 use fe_common::files::{FileKind, Utf8Path};
 use fe_common::utils::files::BuildFiles;
 use fe_parser::node::{NodeId, Span};
@@ -38,6 +39,7 @@ fn ingot_files_to_modules() {
         "libcool",
         IngotMode::Lib,
         FileKind::Local,
+        Edition::LATEST,
         &[
             ("foo/fee.fe", ""),
             ("lib.fe", ""),
@@ -71,6 +73,35 @@ fn ingot_files_to_modules() {
     assert_eq!(d.name(&db), "d");
 }
 
+#[test]
+fn trait_impls_across_ingots() {
+    let mut db = TestDb::default();
+    let module = ModuleId::new_standalone(
+        &mut db,
+        "wei.fe",
+        "use std::traits::Min
+
+pub struct Wei {}
+
+impl Min for Wei {
+    fn min() -> Self {
+        return Wei()
+    }
+}
+",
+    );
+    assert!(module.diagnostics(&db).is_empty());
+
+    let impl_ = module.all_impls(&db)[0];
+    let min = impl_.trait_id(&db);
+    let impls = min.all_impls(&db);
+    assert!(impls.contains(&impl_));
+    // The `impl` blocks of `std` for the integer types are included.
+    assert!(impls.iter().any(|impl_| impl_.module(&db).is_in_std(&db)));
+
+    assert_eq!(&*impl_.receiver(&db).implemented_traits(&db), &[min]);
+}
+
 macro_rules! test_analysis {
     ($name:ident, $path:expr) => {
         #[test]
@@ -339,6 +370,7 @@ fn new_diagnostic(labels: Vec<Label>) -> Diagnostic {
         message: String::new(),
         labels,
         notes: vec![],
+        suggestions: vec![],
     }
 }
 
@@ -423,5 +455,6 @@ fn build_display_diagnostic<T: Display>(span: Span, attributes: &T) -> Diagnosti
         message: String::new(),
         labels: vec![label],
         notes: vec![],
+        suggestions: vec![],
     }
 }
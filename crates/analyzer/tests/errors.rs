@@ -104,6 +104,9 @@ test_stmt! { aug_assign_non_numeric, "let mut a: u256 = 1\nlet b: bool = true\na
 test_stmt! { binary_op_add_sign_mismatch, "let a: u256 = 1\nlet b: i256 = 2\na + b" }
 test_stmt! { binary_op_lshift_bool, "let a: bool = true\nlet b: i256\na << b" }
 test_stmt! { binary_op_lshift_with_int, "let a: u256 = 1\nlet b: i256 = 2\na << b" }
+test_stmt! { binary_op_shift_overflow, "let a: u8 = 1\na << 8" }
+test_stmt! { count_ones_on_bool, "let a: bool = true\na.count_ones()" }
+test_stmt! { string_concat_not_const, "let a: String<3> = \"abc\"\nlet b: String<6> = a + \"def\"" }
 test_stmt! { binary_op_pow_int, "let a: u256 = 1\nlet b: i256 = 2\na ** b" }
 test_stmt! { binary_op_boolean_mismatch1, "10 and true" }
 test_stmt! { binary_op_boolean_mismatch2, "false or 1" }
@@ -171,6 +174,7 @@ test_stmt! { overflow_u8_assignment, "let x: u8 = 260" }
 test_stmt! { pow_with_signed_exponent, "let base: i128\nlet xp: i128\nbase ** exp" }
 // Exponent can be unsigned but needs to be same size or smaller
 test_stmt! { pow_with_wrong_capacity, "let base: i128\nlet exp: u256\nbase ** exp" }
+test_stmt! { poisoned_variable, "let x: MysteryType = 1\nlet mut y: u256 = x + 1\ny = address(0)" }
 test_stmt! { shadow_builtin_type_with_var, "let u8: u8 = 10" }
 test_stmt! { shadow_builtin_fn_with_var, "let keccak256: u8 = 10" }
 test_file! { shadow_builtin_type }
@@ -209,8 +213,10 @@ test_file! { ambiguous_traits }
 test_file! { ambiguous_traits2 }
 test_file! { ambiguous_traits3 }
 test_file! { ambiguous_traits4 }
+test_file! { ambiguous_bound_method }
 test_ingot! { trait_not_in_scope }
 test_ingot! { trait_not_in_scope2 }
+test_ingot! { trait_import_suggestion }
 test_ingot! { call_trait_assoc_fn_on_invisible_type }
 test_file! { bad_enums }
 test_file! { enum_match }
@@ -222,6 +228,8 @@ test_file! { bad_tuple_attr1 }
 test_file! { bad_tuple_attr2 }
 test_file! { bad_tuple_attr3 }
 test_file! { call_generic_function_with_unsatisfied_bound}
+test_file! { unsatisfied_bound_of_type_param }
+test_file! { unsatisfied_bound_with_impls }
 test_file! { call_builtin_object }
 test_file! { call_create_with_wrong_type }
 test_file! { call_create2_with_wrong_type }
@@ -257,8 +265,11 @@ test_file! { emittable_not_implementable }
 test_file! { contract_function_with_generic_params }
 test_file! { indexed_event }
 test_file! { invalid_compiler_version }
+test_file! { invalid_abi_name }
 test_file! { invalid_block_field }
 test_file! { invalid_chain_field }
+test_file! { invalid_code_size_budget }
+test_file! { invalid_storage_gap }
 test_file! { invalid_contract_field }
 test_file! { invalid_generic_bound }
 test_file! { invalid_impl_type }
@@ -266,6 +277,9 @@ test_file! { invalid_impl_location }
 test_file! { invalid_msg_field }
 test_file! { invalid_string_field }
 test_file! { invalid_struct_attribute }
+test_file! { secret_branch }
+test_file! { only_attribute }
+test_file! { function_guards }
 test_file! { invalid_struct_field }
 test_file! { invalid_tuple_field }
 test_file! { invalid_tx_field }
@@ -276,6 +290,7 @@ test_file! { mislabeled_call_args }
 test_file! { mislabeled_call_args_self }
 test_file! { mislabeled_call_args_external_contract_call }
 test_file! { mismatch_return_type }
+test_file! { missing_operator_trait }
 test_file! { missing_return }
 test_file! { missing_return_in_else }
 test_file! { missing_return_after_if }
@@ -304,10 +319,16 @@ test_file! { struct_call_bad_args }
 test_file! { struct_call_without_kw_args }
 test_file! { struct_recursive_cycles }
 test_file! { trait_impl_mismatch }
+test_file! { trait_default_fns }
 test_file! { trait_fn_with_generic_params }
 test_file! { traits_as_fields }
 test_file! { trait_conflicting_impls }
 test_file! { traits_with_wrong_bounds }
+test_file! { contract_impl_non_self_receiver }
+test_file! { contract_impl_missing_member }
+test_file! { contract_impl_signature_mismatch }
+test_ingot! { contract_impl_private_trait }
+test_file! { type_mismatch_diff }
 test_file! { non_pub_init }
 test_file! { init_wrong_return_type }
 test_file! { init_duplicate_def }
@@ -332,6 +353,8 @@ test_file! { call_to_pure_struct_fn_on_instance }
 test_file! { missing_self }
 test_file! { self_not_first }
 test_file! { self_in_standalone_fn }
+test_file! { storage_refs }
+test_file! { calldata_bytes }
 test_file! { unsafe_misuse }
 test_file! { unsafe_nesting }
 
@@ -341,7 +364,10 @@ test_ingot! { bad_visibility }
 test_ingot! { missing_dep }
 test_ingot! { name_mismatch }
 test_ingot! { version_mismatch }
+test_ingot! { edition_gates }
 test_ingot! { main_dep }
+test_ingot! { orphan_module }
+test_ingot! { conflicting_impls }
 
 test_file! { ctx_not_first }
 test_file! { ctx_not_ctx_type }
@@ -1,28 +1,62 @@
 #![allow(clippy::arc_with_non_send_sync)]
 use std::rc::Rc;
 
-use fe_abi::{contract::AbiContract, event::AbiEvent, function::AbiFunction, types::AbiType};
+use fe_abi::{
+    contract::AbiContract, event::AbiEvent, function::AbiFunction, natspec::NatspecDocs,
+    storage::StorageLayout, types::AbiType,
+};
 use fe_analyzer::{
     db::AnalyzerDbStorage,
     namespace::items::{ContractId, ModuleId},
     AnalyzerDb,
 };
+use fe_common::chain::ChainProfile;
 use fe_common::db::{SourceDb, SourceDbStorage, Upcast, UpcastMut};
+use fe_common::evm::EvmVersion;
 use fe_mir::{
     db::{MirDb, MirDbStorage},
-    ir::{FunctionBody, FunctionId, FunctionSignature, TypeId},
+    ir::{FunctionBody, FunctionId, FunctionSignature, InstId, TypeId},
 };
+use smol_str::SmolStr;
 
 mod queries;
 
 #[salsa::query_group(CodegenDbStorage)]
 pub trait CodegenDb: MirDb + Upcast<dyn MirDb> + UpcastMut<dyn MirDb> {
+    /// Whether the external function dispatcher rejects calldata whose size
+    /// doesn't match the ABI of the selected function.
+    #[salsa::input]
+    fn codegen_calldata_validation(&self) -> bool;
+    /// The step sequence passed to the Yul optimizer, e.g. `"dhfoDgvulfnTUtnIf"`.
+    /// The default sequence of solc is used if this is `None`.
+    #[salsa::input]
+    fn codegen_yul_optimizer_steps(&self) -> Option<SmolStr>;
+    /// The EVM version that the code is compiled for. The default version of
+    /// solc is used if this is `None`.
+    #[salsa::input]
+    fn codegen_evm_version(&self) -> Option<EvmVersion>;
+    /// The profile of the chain that the code is compiled for. Calls of
+    /// precompiles that the chain doesn't have are rejected.
+    #[salsa::input]
+    fn codegen_chain_profile(&self) -> Option<ChainProfile>;
+
     #[salsa::invoke(queries::function::legalized_signature)]
     fn codegen_legalized_signature(&self, function_id: FunctionId) -> Rc<FunctionSignature>;
     #[salsa::invoke(queries::function::legalized_body)]
     fn codegen_legalized_body(&self, function_id: FunctionId) -> Rc<FunctionBody>;
     #[salsa::invoke(queries::function::symbol_name)]
     fn codegen_function_symbol_name(&self, function_id: FunctionId) -> Rc<String>;
+    /// The `MemCopy` instructions through which the function returns strings
+    /// or bytes that it copies out of storage. If every return of the
+    /// function is such a copy, the dispatcher calls a variant of the function
+    /// that returns the storage pointers instead, and encodes the return data
+    /// straight from storage. Empty if any return isn't such a copy.
+    #[salsa::invoke(queries::function::storage_return_copies)]
+    fn codegen_storage_return_copies(&self, function_id: FunctionId) -> Rc<Vec<InstId>>;
+    /// The symbol name of the variant of the function that returns storage
+    /// pointers, see `codegen_storage_return_copies`.
+    #[salsa::invoke(queries::function::storage_return_symbol_name)]
+    fn codegen_storage_return_symbol_name(&self, function_id: FunctionId) -> Rc<String>;
 
     #[salsa::invoke(queries::types::legalized_type)]
     fn codegen_legalized_type(&self, ty: TypeId) -> TypeId;
@@ -35,6 +69,16 @@ pub trait CodegenDb: MirDb + Upcast<dyn MirDb> + UpcastMut<dyn MirDb> {
     fn codegen_abi_event(&self, ty: TypeId) -> AbiEvent;
     #[salsa::invoke(queries::abi::abi_contract)]
     fn codegen_abi_contract(&self, contract: ContractId) -> AbiContract;
+    #[salsa::invoke(queries::abi::abi_contract_errors)]
+    fn codegen_abi_contract_errors(&self, contract: ContractId) -> Vec<AbiFunction>;
+    #[salsa::invoke(queries::abi::abi_contract_natspec)]
+    fn codegen_abi_contract_natspec(&self, contract: ContractId) -> NatspecDocs;
+    #[salsa::invoke(queries::abi::contract_event_types)]
+    fn codegen_contract_event_types(&self, contract: ContractId) -> Rc<Vec<TypeId>>;
+    #[salsa::invoke(queries::abi::contract_error_types)]
+    fn codegen_contract_error_types(&self, contract: ContractId) -> Rc<Vec<TypeId>>;
+    #[salsa::invoke(queries::abi::abi_error)]
+    fn codegen_abi_error(&self, ty: TypeId) -> AbiFunction;
     #[salsa::invoke(queries::abi::abi_module_events)]
     fn codegen_abi_module_events(&self, module: ModuleId) -> Vec<AbiEvent>;
     #[salsa::invoke(queries::abi::abi_type_maximum_size)]
@@ -50,6 +94,8 @@ pub trait CodegenDb: MirDb + Upcast<dyn MirDb> + UpcastMut<dyn MirDb> {
     fn codegen_contract_symbol_name(&self, contract: ContractId) -> Rc<String>;
     #[salsa::invoke(queries::contract::deployer_symbol_name)]
     fn codegen_contract_deployer_symbol_name(&self, contract: ContractId) -> Rc<String>;
+    #[salsa::invoke(queries::contract::storage_layout)]
+    fn codegen_contract_storage_layout(&self, contract: ContractId) -> StorageLayout;
 
     #[salsa::invoke(queries::constant::string_symbol_name)]
     fn codegen_constant_string_symbol_name(&self, data: String) -> Rc<String>;
@@ -57,12 +103,24 @@ pub trait CodegenDb: MirDb + Upcast<dyn MirDb> + UpcastMut<dyn MirDb> {
 
 // TODO: Move this to driver.
 #[salsa::database(SourceDbStorage, AnalyzerDbStorage, MirDbStorage, CodegenDbStorage)]
-#[derive(Default)]
 pub struct Db {
     storage: salsa::Storage<Db>,
 }
 impl salsa::Database for Db {}
 
+impl Default for Db {
+    fn default() -> Self {
+        let mut db = Self {
+            storage: Default::default(),
+        };
+        db.set_codegen_calldata_validation(true);
+        db.set_codegen_yul_optimizer_steps(None);
+        db.set_codegen_evm_version(None);
+        db.set_codegen_chain_profile(None);
+        db
+    }
+}
+
 impl Upcast<dyn MirDb> for Db {
     fn upcast(&self) -> &(dyn MirDb + 'static) {
         self
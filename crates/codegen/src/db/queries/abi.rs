@@ -1,7 +1,10 @@
+use std::rc::Rc;
+
 use fe_abi::{
     contract::AbiContract,
     event::{AbiEvent, AbiEventField},
     function::{AbiFunction, AbiFunctionType, CtxParam, SelfParam, StateMutability},
+    natspec::{Natspec, NatspecDocs},
     types::{AbiTupleField, AbiType},
 };
 use fe_analyzer::{
@@ -11,7 +14,10 @@ use fe_analyzer::{
         types::{CtxDecl, SelfDecl},
     },
 };
-use fe_mir::ir::{self, FunctionId, TypeId};
+use fe_mir::ir::{self, inst::InstKind, FunctionId, TypeId};
+use fxhash::FxHashSet;
+use indexmap::IndexSet;
+use smol_str::SmolStr;
 
 use crate::db::CodegenDb;
 
@@ -32,11 +38,158 @@ pub fn abi_contract(db: &dyn CodegenDb, contract: ContractId) -> AbiContract {
         }
     }
 
-    let events = abi_module_events(db, contract.module(db.upcast()));
+    let events = db
+        .codegen_contract_event_types(contract)
+        .iter()
+        .map(|ty| db.codegen_abi_event(*ty))
+        .collect();
 
     AbiContract::new(funcs, events)
 }
 
+/// Returns the ABI of the errors that can be reverted with by the contract.
+pub fn abi_contract_errors(db: &dyn CodegenDb, contract: ContractId) -> Vec<AbiFunction> {
+    db.codegen_contract_error_types(contract)
+        .iter()
+        .map(|ty| db.codegen_abi_error(*ty))
+        .collect()
+}
+
+/// Returns the userdoc and devdoc of the contract, from the `///` comments on
+/// the contract, its public functions and its events and errors.
+pub fn abi_contract_natspec(db: &dyn CodegenDb, contract: ContractId) -> NatspecDocs {
+    let parse =
+        |doc: Option<SmolStr>| doc.map_or_else(Natspec::default, |doc| Natspec::parse(&doc));
+    let struct_doc = |ty: TypeId| {
+        parse(
+            ty.analyzer_ty(db.upcast())
+                .and_then(|ty| ty.as_struct(db.upcast()))
+                .and_then(|s| s.doc_comment(db.upcast())),
+        )
+    };
+
+    let mut docs = NatspecDocs::new(&parse(contract.doc_comment(db.upcast())));
+    if let Some(init) = contract.init_function(db.upcast()) {
+        docs.add_method("constructor", &parse(init.doc_comment(db.upcast())));
+    }
+
+    for &func in contract.all_functions(db.upcast()).as_ref() {
+        let mir_func = db.mir_lowered_func_signature(func);
+        if mir_func.linkage(db.upcast()).is_exported() {
+            let abi = db.codegen_abi_function(mir_func);
+            docs.add_method(
+                abi.selector().selector_signature(),
+                &parse(func.doc_comment(db.upcast())),
+            );
+        }
+    }
+
+    for &ty in db.codegen_contract_event_types(contract).iter() {
+        let abi = db.codegen_abi_event(ty);
+        docs.add_event(abi.signature().signature(), &struct_doc(ty));
+    }
+
+    for &ty in db.codegen_contract_error_types(contract).iter() {
+        let abi = db.codegen_abi_error(ty);
+        docs.add_error(abi.selector().selector_signature(), &struct_doc(ty));
+    }
+
+    docs
+}
+
+/// Returns the events of the contract: the events that are defined in the
+/// module of the contract, followed by the events defined in other modules that
+/// are emitted in any function reachable from the contract's functions.
+pub fn contract_event_types(db: &dyn CodegenDb, contract: ContractId) -> Rc<Vec<TypeId>> {
+    let module = contract.module(db.upcast());
+    let mut event_types: IndexSet<_> = db
+        .module_structs(module)
+        .iter()
+        .map(|s| s.as_type(db.upcast()))
+        .filter(|ty| ty.is_emittable(db.upcast()))
+        .map(|ty| db.mir_lowered_type(ty))
+        .collect();
+    event_types.extend(contract_reachable_types(db, contract).0);
+
+    Rc::new(event_types.into_iter().collect())
+}
+
+/// Returns the struct types that are passed to `revert` in any function
+/// reachable from the contract's functions.
+pub fn contract_error_types(db: &dyn CodegenDb, contract: ContractId) -> Rc<Vec<TypeId>> {
+    let (_, error_types) = contract_reachable_types(db, contract);
+    Rc::new(error_types.into_iter().collect())
+}
+
+/// Returns the types that are emitted, and the struct types that are reverted
+/// with, in any function reachable from the contract's functions.
+fn contract_reachable_types(
+    db: &dyn CodegenDb,
+    contract: ContractId,
+) -> (IndexSet<TypeId>, IndexSet<TypeId>) {
+    let mut worklist: Vec<_> = contract
+        .all_functions(db.upcast())
+        .iter()
+        .chain(contract.init_function(db.upcast()).iter())
+        .map(|func| db.mir_lowered_func_signature(*func))
+        .collect();
+    let mut visited = FxHashSet::default();
+    let mut event_types = IndexSet::new();
+    let mut error_types = IndexSet::new();
+
+    while let Some(func) = worklist.pop() {
+        if !visited.insert(func) {
+            continue;
+        }
+
+        let body = db.mir_lowered_func_body(func);
+        for block in body.order.iter_block() {
+            for inst in body.order.iter_inst(block) {
+                match &body.store.inst_data(inst).kind {
+                    InstKind::Call { func, .. } => worklist.push(*func),
+                    InstKind::Emit { arg } => {
+                        event_types.insert(body.store.value_ty(*arg).deref(db.upcast()));
+                    }
+                    InstKind::Revert { arg: Some(arg) } => {
+                        let ty = body.store.value_ty(*arg).deref(db.upcast());
+                        if ty.is_struct(db.upcast()) {
+                            error_types.insert(ty);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    (event_types, error_types)
+}
+
+pub fn abi_error(db: &dyn CodegenDb, ty: TypeId) -> AbiFunction {
+    let def = match &ty.deref(db.upcast()).data(db.upcast()).kind {
+        ir::TypeKind::Struct(def) => def.clone(),
+        _ => unreachable!(),
+    };
+    let name = ty
+        .analyzer_ty(db.upcast())
+        .and_then(|ty| ty.as_struct(db.upcast()))
+        .map_or_else(|| def.name.clone(), |s| s.abi_name(db.upcast()));
+    let args = def
+        .fields
+        .iter()
+        .map(|(name, ty)| (name.to_string(), db.codegen_abi_type(*ty)))
+        .collect();
+
+    // Errors are encoded like a call, so the state mutability is irrelevant.
+    AbiFunction::new(
+        AbiFunctionType::Function,
+        name.to_string(),
+        args,
+        None,
+        StateMutability::Pure,
+    )
+}
+
 pub fn abi_module_events(db: &dyn CodegenDb, module: ModuleId) -> Vec<AbiEvent> {
     let mut events = vec![];
     for &s in db.module_structs(module).as_ref() {
@@ -96,7 +249,7 @@ pub fn abi_function(db: &dyn CodegenDb, function: FunctionId) -> AbiFunction {
 pub fn abi_function_argument_maximum_size(db: &dyn CodegenDb, function: FunctionId) -> usize {
     let sig = db.codegen_legalized_signature(function);
     sig.params.iter().fold(0, |acc, param| {
-        acc + db.codegen_abi_type_maximum_size(param.ty)
+        acc.saturating_add(db.codegen_abi_type_maximum_size(param.ty))
     })
 }
 
@@ -123,6 +276,8 @@ pub fn abi_type_maximum_size(db: &dyn CodegenDb, ty: TypeId) -> usize {
             }
 
             ir::TypeKind::String(len) => abi_type.header_size() + 32 + ceil_32(*len),
+            // The length of calldata bytes is only bounded by the calldata size.
+            _ if ty.is_calldata_bytes(db.upcast()) => usize::MAX,
             _ if ty.is_aggregate(db.upcast()) => {
                 let mut maximum = 0;
                 for i in 0..ty.aggregate_field_num(db.upcast()) {
@@ -154,6 +309,8 @@ pub fn abi_type_minimum_size(db: &dyn CodegenDb, ty: TypeId) -> usize {
 
             ir::TypeKind::String(_) => abi_type.header_size() + 32,
 
+            _ if ty.is_calldata_bytes(db.upcast()) => 64,
+
             _ if ty.is_aggregate(db.upcast()) => {
                 let mut minimum = 0;
                 for i in 0..ty.aggregate_field_num(db.upcast()) {
@@ -221,6 +378,7 @@ pub fn abi_type(db: &dyn CodegenDb, ty: TypeId) -> AbiType {
 
             AbiType::Tuple(fields)
         }
+        ir::TypeKind::Struct(_) if legalized_ty.is_calldata_bytes(db.upcast()) => AbiType::Bytes,
         ir::TypeKind::Struct(def) => {
             let fields = def
                 .fields
@@ -271,7 +429,8 @@ pub fn abi_event(db: &dyn CodegenDb, ty: TypeId) -> AbiEvent {
         })
         .collect();
 
-    AbiEvent::new(event_def.name.to_string(), fields, false)
+    let name = analyzer_struct.abi_name(db.upcast());
+    AbiEvent::new(name.to_string(), fields, false)
 }
 
 fn ceil_32(value: usize) -> usize {
@@ -1,8 +1,13 @@
 use std::rc::Rc;
 
-use fe_analyzer::namespace::items::ContractId;
+use fe_abi::storage::{StorageField, StorageLayout};
+use fe_analyzer::{
+    display::Displayable,
+    namespace::{items::ContractId, types::Type},
+};
+use fe_mir::ir::TypeKind;
 
-use crate::db::CodegenDb;
+use crate::{db::CodegenDb, yul::slot_size::SLOT_SIZE};
 
 pub fn symbol_name(db: &dyn CodegenDb, contract: ContractId) -> Rc<String> {
     let module = contract.module(db.upcast());
@@ -18,3 +23,32 @@ pub fn symbol_name(db: &dyn CodegenDb, contract: ContractId) -> Rc<String> {
 pub fn deployer_symbol_name(db: &dyn CodegenDb, contract: ContractId) -> Rc<String> {
     format!("deploy_{}", symbol_name(db, contract).as_ref()).into()
 }
+
+pub fn storage_layout(db: &dyn CodegenDb, contract: ContractId) -> StorageLayout {
+    let analyzer_ty = Type::SelfContract(contract).id(db.upcast());
+    let contract_ty = db.codegen_legalized_type(db.mir_lowered_type(analyzer_ty));
+    let field_types = contract.fields(db.upcast());
+
+    let fields = match &contract_ty.data(db.upcast()).kind {
+        TypeKind::Contract(def) => def
+            .fields
+            .iter()
+            .enumerate()
+            // Storage gaps have no analyzer field, and aren't part of the layout.
+            .filter_map(|(idx, (name, ty))| {
+                let analyzer_field_ty = field_types.get(name)?.typ(db.upcast()).unwrap();
+                let start = contract_ty.aggregate_elem_offset(db.upcast(), idx, SLOT_SIZE);
+                Some(StorageField {
+                    name: name.to_string(),
+                    ty: analyzer_field_ty.display(db.upcast()).to_string(),
+                    slot: start / SLOT_SIZE,
+                    offset: start % SLOT_SIZE,
+                    size: ty.size_of(db.upcast(), SLOT_SIZE),
+                })
+            })
+            .collect(),
+        _ => unreachable!(),
+    };
+
+    StorageLayout::new(fields)
+}
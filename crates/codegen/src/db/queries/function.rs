@@ -7,7 +7,9 @@ use fe_analyzer::{
         types::{Type, TypeId},
     },
 };
-use fe_mir::ir::{FunctionBody, FunctionId, FunctionSignature};
+use fe_mir::ir::{
+    inst::InstKind, FunctionBody, FunctionId, FunctionSignature, InstId, TypeKind, Value, ValueId,
+};
 use salsa::InternKey;
 use smol_str::SmolStr;
 
@@ -20,11 +22,77 @@ pub fn legalized_signature(db: &dyn CodegenDb, function: FunctionId) -> Rc<Funct
 }
 
 pub fn legalized_body(db: &dyn CodegenDb, function: FunctionId) -> Rc<FunctionBody> {
+    let _span =
+        tracing::debug_span!("legalized_body", item = %function.debug_name(db.upcast())).entered();
     let mut body = function.body(db.upcast()).as_ref().clone();
     legalize::legalize_func_body(db, &mut body);
     body.into()
 }
 
+pub fn storage_return_copies(db: &dyn CodegenDb, function: FunctionId) -> Rc<Vec<InstId>> {
+    let body = db.codegen_legalized_body(function);
+    let mut copies = vec![];
+    for block in body.order.iter_block() {
+        for inst in body.order.iter_inst(block) {
+            if let InstKind::Return { arg: Some(arg) } = body.store.inst_data(inst).kind {
+                match storage_return_copy(db, &body, inst, arg) {
+                    Some(copy) => copies.push(copy),
+                    None => return Rc::new(vec![]),
+                }
+            }
+        }
+    }
+
+    copies.into()
+}
+
+/// Returns the `MemCopy` that `ret` returns the result of, if it copies a
+/// string or bytes out of storage right before returning.
+fn storage_return_copy(
+    db: &dyn CodegenDb,
+    body: &FunctionBody,
+    ret: InstId,
+    arg: ValueId,
+) -> Option<InstId> {
+    let copy = match body.store.value_data(arg) {
+        Value::Temporary { inst, .. } => *inst,
+        _ => return None,
+    };
+    if body.order.prev_inst(ret) != Some(copy) {
+        return None;
+    }
+
+    let src = match body.store.inst_data(copy).kind {
+        InstKind::MemCopy { src } => src,
+        _ => return None,
+    };
+    if !matches!(
+        body.store.value_data(src),
+        Value::Local(_) | Value::Temporary { .. }
+    ) {
+        return None;
+    }
+
+    let src_ty = body.store.value_ty(src);
+    if !src_ty.is_sptr(db.upcast()) {
+        return None;
+    }
+    let is_string_or_bytes = match &src_ty.deref(db.upcast()).data(db.upcast()).kind {
+        TypeKind::String(_) => true,
+        TypeKind::Array(def) => matches!(def.elem_ty.data(db.upcast()).kind, TypeKind::U8),
+        _ => false,
+    };
+    is_string_or_bytes.then_some(copy)
+}
+
+pub fn storage_return_symbol_name(db: &dyn CodegenDb, function: FunctionId) -> Rc<String> {
+    format!(
+        "{}$storage_return",
+        db.codegen_function_symbol_name(function)
+    )
+    .into()
+}
+
 pub fn symbol_name(db: &dyn CodegenDb, function: FunctionId) -> Rc<String> {
     let module = function.signature(db.upcast()).module_id;
     let module_name = module.name(db.upcast());
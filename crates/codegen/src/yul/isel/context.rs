@@ -10,12 +10,14 @@ use crate::{
     yul::runtime::{DefaultRuntimeProvider, RuntimeProvider},
 };
 
-use super::{lower_contract_deployable, lower_function};
+use super::{function::lower_storage_return_function, lower_contract_deployable, lower_function};
 
 pub struct Context {
     pub runtime: Box<dyn RuntimeProvider>,
     pub(super) contract_dependency: IndexSet<ContractId>,
     pub(super) function_dependency: IndexSet<FunctionId>,
+    /// Functions whose storage return variant is called by the dispatcher.
+    pub(super) storage_return_dependency: IndexSet<FunctionId>,
     pub(super) string_constants: IndexSet<String>,
     pub(super) lowered_functions: FxHashSet<FunctionId>,
 }
@@ -29,6 +31,7 @@ impl Default for Context {
             runtime: Box::<DefaultRuntimeProvider>::default(),
             contract_dependency: IndexSet::default(),
             function_dependency: IndexSet::default(),
+            storage_return_dependency: IndexSet::default(),
             string_constants: IndexSet::default(),
             lowered_functions: FxHashSet::default(),
         }
@@ -41,6 +44,10 @@ impl Context {
         db: &dyn CodegenDb,
     ) -> Vec<yul::FunctionDefinition> {
         let mut funcs = vec![];
+        for dependency in std::mem::take(&mut self.storage_return_dependency) {
+            funcs.push(lower_storage_return_function(db, self, dependency))
+        }
+
         loop {
             let dependencies = std::mem::take(&mut self.function_dependency);
             if dependencies.is_empty() {
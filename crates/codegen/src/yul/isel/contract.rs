@@ -144,7 +144,6 @@ fn make_dispatcher(
 }
 
 fn dispatch_arm(db: &dyn CodegenDb, context: &mut Context, func: FunctionId) -> yul::Case {
-    context.function_dependency.insert(func);
     let func_sig = db.codegen_legalized_signature(func);
     let mut param_vars = Vec::with_capacity(func_sig.params.len());
     let mut param_tys = Vec::with_capacity(func_sig.params.len());
@@ -154,7 +153,19 @@ fn dispatch_arm(db: &dyn CodegenDb, context: &mut Context, func: FunctionId) ->
     });
 
     let decode_params = if func_sig.params.is_empty() {
-        statements! {}
+        // Decoding validates the size of non-empty calldata, so only functions
+        // without parameters need an explicit check here.
+        if db.codegen_calldata_validation() {
+            let param_size = expression! { sub((calldatasize()), 4) };
+            let validate = context
+                .runtime
+                .abi_validate_size(db, param_size, &param_tys);
+            statements! {
+                ([yul::Statement::Expression(validate)])
+            }
+        } else {
+            statements! {}
+        }
     } else {
         let ident_params: Vec<_> = param_vars.iter().map(YulVariable::ident).collect();
         let param_size = YulVariable::new("param_size");
@@ -165,11 +176,23 @@ fn dispatch_arm(db: &dyn CodegenDb, context: &mut Context, func: FunctionId) ->
     };
 
     let call_and_encode_return = {
-        let name = identifier! { (db.codegen_function_symbol_name(func)) };
+        // Strings and bytes that are returned from storage are encoded
+        // straight from storage, instead of from a copy in memory.
+        let returns_storage = !db.codegen_storage_return_copies(func).is_empty();
+        let name = if returns_storage {
+            context.storage_return_dependency.insert(func);
+            db.codegen_storage_return_symbol_name(func)
+        } else {
+            context.function_dependency.insert(func);
+            db.codegen_function_symbol_name(func)
+        };
+        let name = identifier! { (name) };
         // we pass in a `0` for the expected `Context` argument
         let call = expression! {[name]([(param_vars.iter().map(YulVariable::expr).collect::<Vec<_>>())...])};
         if let Some(mut return_type) = func_sig.return_type {
-            if return_type.is_aggregate(db.upcast()) {
+            if returns_storage {
+                return_type = return_type.deref(db.upcast()).make_sptr(db.upcast());
+            } else if return_type.is_aggregate(db.upcast()) {
                 return_type = return_type.make_mptr(db.upcast());
             }
 
@@ -1,8 +1,8 @@
 #![allow(unused)]
-use std::thread::Scope;
+use std::{rc::Rc, thread::Scope};
 
 use super::{context::Context, inst_order::InstSerializer};
-use fe_common::numeric::to_hex_str;
+use fe_common::{evm::EvmVersion, numeric::to_hex_str};
 
 use fe_abi::function::{AbiFunction, AbiFunctionType};
 use fe_common::db::Upcast;
@@ -10,7 +10,7 @@ use fe_mir::{
     ir::{
         self,
         constant::ConstantValue,
-        inst::{BinOp, CallType, CastKind, InstKind, UnOp},
+        inst::{BinOp, CallType, CastKind, InstKind, UnOp, YulIntrinsicOp},
         value::AssignableValue,
         Constant, FunctionBody, FunctionId, FunctionSignature, InstId, Type, TypeId, TypeKind,
         Value, ValueId,
@@ -46,6 +46,21 @@ pub fn lower_function(
     FuncLowerHelper::new(db, ctx, function, sig, body).lower_func()
 }
 
+/// Lowers the variant of `function` that returns the storage pointers of the
+/// strings or bytes that it would copy to memory before returning them. See
+/// `CodegenDb::codegen_storage_return_copies`.
+pub(super) fn lower_storage_return_function(
+    db: &dyn CodegenDb,
+    ctx: &mut Context,
+    function: FunctionId,
+) -> yul::FunctionDefinition {
+    let sig = &db.codegen_legalized_signature(function);
+    let body = &db.codegen_legalized_body(function);
+    let mut helper = FuncLowerHelper::new(db, ctx, function, sig, body);
+    helper.storage_return_copies = db.codegen_storage_return_copies(function);
+    helper.lower_func()
+}
+
 struct FuncLowerHelper<'db, 'a> {
     db: &'db dyn CodegenDb,
     ctx: &'a mut Context,
@@ -54,6 +69,8 @@ struct FuncLowerHelper<'db, 'a> {
     sig: &'a FunctionSignature,
     body: &'a FunctionBody,
     ret_value: Option<yul::Identifier>,
+    /// The copies that are elided by returning their sources instead.
+    storage_return_copies: Rc<Vec<InstId>>,
     sink: Vec<yul::Statement>,
 }
 
@@ -91,12 +108,18 @@ impl<'db, 'a> FuncLowerHelper<'db, 'a> {
             sig,
             body,
             ret_value,
+            storage_return_copies: Rc::default(),
             sink: Vec::new(),
         }
     }
 
     fn lower_func(mut self) -> yul::FunctionDefinition {
-        let name = identifier! { (self.db.codegen_function_symbol_name(self.func)) };
+        let name = if self.storage_return_copies.is_empty() {
+            self.db.codegen_function_symbol_name(self.func)
+        } else {
+            self.db.codegen_storage_return_symbol_name(self.func)
+        };
+        let name = identifier! { (name) };
 
         let parameters = self
             .sig
@@ -158,6 +181,18 @@ impl<'db, 'a> FuncLowerHelper<'db, 'a> {
     }
 
     fn lower_inst(&mut self, inst: InstId) {
+        if self.storage_return_copies.contains(&inst) {
+            // The storage pointer is returned in place of the copy.
+            if let (InstKind::MemCopy { src }, Some(AssignableValue::Value(result))) = (
+                &self.body.store.inst_data(inst).kind,
+                self.body.store.inst_result(inst),
+            ) {
+                let src = self.value_ident(*src);
+                self.value_map.insert(*result, src);
+            }
+            return;
+        }
+
         if let Some(lhs) = self.body.store.inst_result(inst) {
             self.declare_assignable_value(lhs)
         }
@@ -430,8 +465,24 @@ impl<'db, 'a> FuncLowerHelper<'db, 'a> {
             }
 
             InstKind::YulIntrinsic { op, args } => {
-                let args: Vec<_> = args.iter().map(|arg| self.value_expr(*arg)).collect();
-                let op_name = identifier! { (format!("{op}").strip_prefix("__").unwrap()) };
+                let mut args: Vec<_> = args.iter().map(|arg| self.value_expr(*arg)).collect();
+                let has_transient_storage = self
+                    .db
+                    .codegen_evm_version()
+                    .is_some_and(|version| version >= EvmVersion::Cancun);
+                let op = match op {
+                    YulIntrinsicOp::Tload if !has_transient_storage => YulIntrinsicOp::Sload,
+                    YulIntrinsicOp::Tstore if !has_transient_storage => YulIntrinsicOp::Sstore,
+                    _ => *op,
+                };
+                let op_name = match op.verbatim_opcode() {
+                    Some((opcode, outputs)) => {
+                        let name = identifier! { (format!("verbatim_{}i_{outputs}o", args.len())) };
+                        args.insert(0, literal_expression! { (format!("hex\"{opcode:02x}\"")) });
+                        name
+                    }
+                    None => identifier! { (format!("{op}").strip_prefix("__").unwrap()) },
+                };
                 let result = expression! { [op_name]([args...]) };
                 // Intrinsic operation never returns ptr type, so we can use u256_ty as a dummy
                 // type for the result.
@@ -591,6 +642,16 @@ impl<'db, 'a> FuncLowerHelper<'db, 'a> {
                 }
             }
             UnOp::Inv => expression! { not([value_expr])},
+            UnOp::LeadingZeros => {
+                let value_ty = self.body.store.value_ty(value);
+                self.ctx
+                    .runtime
+                    .leading_zeros(self.db, value_expr, value_ty)
+            }
+            UnOp::CountOnes => {
+                let value_ty = self.body.store.value_ty(value);
+                self.ctx.runtime.count_ones(self.db, value_expr, value_ty)
+            }
         }
     }
 
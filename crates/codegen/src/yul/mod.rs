@@ -4,7 +4,7 @@ pub mod isel;
 pub mod legalize;
 pub mod runtime;
 
-mod slot_size;
+pub(crate) mod slot_size;
 
 use yultsur::*;
 
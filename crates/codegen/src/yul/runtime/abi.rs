@@ -238,6 +238,7 @@ pub(super) fn make_abi_encode_string_type(
     provider: &mut DefaultRuntimeProvider,
     db: &dyn CodegenDb,
     func_name: &str,
+    is_src_storage: bool,
     is_dst_storage: bool,
 ) -> RuntimeFunction {
     let func_name = YulVariable::new(func_name);
@@ -245,15 +246,16 @@ pub(super) fn make_abi_encode_string_type(
     let dst = YulVariable::new("dst");
     let string_len = YulVariable::new("string_len");
     let enc_size = YulVariable::new("enc_size");
+    let src_len_ty = make_ptr(db, yul_primitive_type(db), is_src_storage);
 
     let func_def = function_definition! {
         function [func_name.ident()]([src.ident()], [dst.ident()]) -> [enc_size.ident()] {
-            (let [string_len.ident()] := mload([src.expr()]))
+            (let [string_len.ident()] := [provider.ptr_load(db, src.expr(), src_len_ty)])
             (let data_size := add(32, [string_len.expr()]))
             ([enc_size.ident()] := mul((div((add(data_size, 31)), 32)), 32))
             (let padding_word_ptr := add([dst.expr()], (sub([enc_size.expr()], 32))))
             (mstore(padding_word_ptr, 0))
-            ([yul::Statement::Expression(provider.ptr_copy(db, src.expr(), dst.expr(), literal_expression!{data_size}, false, is_dst_storage))])
+            ([yul::Statement::Expression(provider.ptr_copy(db, src.expr(), dst.expr(), literal_expression!{data_size}, is_src_storage, is_dst_storage))])
         }
     };
     RuntimeFunction::from_statement(func_def)
@@ -264,6 +266,7 @@ pub(super) fn make_abi_encode_bytes_type(
     db: &dyn CodegenDb,
     func_name: &str,
     len: usize,
+    is_src_storage: bool,
     is_dst_storage: bool,
 ) -> RuntimeFunction {
     let func_name = YulVariable::new(func_name);
@@ -281,7 +284,7 @@ pub(super) fn make_abi_encode_bytes_type(
             })
             ([yul::Statement::Expression(provider.ptr_store(db, dst.expr(), literal_expression!{ (len) }, dst_len_ty))])
             ([dst.ident()] := add(32, [dst.expr()]))
-            ([yul::Statement::Expression(provider.ptr_copy(db, src.expr(), dst.expr(), literal_expression!{(len)}, false, is_dst_storage))])
+            ([yul::Statement::Expression(provider.ptr_copy(db, src.expr(), dst.expr(), literal_expression!{(len)}, is_src_storage, is_dst_storage))])
         }
     };
     RuntimeFunction::from_statement(func_def)
@@ -373,21 +376,7 @@ pub(super) fn make_abi_decode(
         .collect();
 
     let abi_enc_size = abi_enc_size(db, types);
-    let size_check = match abi_enc_size {
-        AbiEncodingSize::Static(size) => statements! {
-                (if (iszero((eq([enc_size.expr()], [literal_expression!{(size)}]))))
-        {             [revert_with_invalid_abi_data(provider, db)]
-                })
-            },
-        AbiEncodingSize::Bounded { min, max } => statements! {
-            (if (or(
-                (lt([enc_size.expr()], [literal_expression!{(min)}])),
-                (gt([enc_size.expr()], [literal_expression!{(max)}]))
-            )) {
-                [revert_with_invalid_abi_data(provider, db)]
-            })
-        },
-    };
+    let size_check = make_size_check(provider, db, enc_size.expr(), abi_enc_size);
 
     let mut body = statements! {
         (let [header_ptr.ident()] := [src.expr()])
@@ -441,6 +430,47 @@ pub(super) fn make_abi_decode(
     RuntimeFunction::from_statement(func_def)
 }
 
+pub(super) fn make_abi_validate_size(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    func_name: &str,
+    types: &[TypeId],
+) -> RuntimeFunction {
+    let func_name = YulVariable::new(func_name);
+    let enc_size = YulVariable::new("$enc_size");
+    let size_check = make_size_check(provider, db, enc_size.expr(), abi_enc_size(db, types));
+
+    let func_def = function_definition! {
+        function [func_name.ident()]([enc_size.ident()]) {
+            [size_check...]
+        }
+    };
+    RuntimeFunction::from_statement(func_def)
+}
+
+fn make_size_check(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    enc_size: yul::Expression,
+    abi_enc_size: AbiEncodingSize,
+) -> Vec<yul::Statement> {
+    match abi_enc_size {
+        AbiEncodingSize::Static(size) => statements! {
+                (if (iszero((eq([enc_size], [literal_expression!{(size)}]))))
+        {             [revert_with_invalid_abi_data(provider, db)]
+                })
+            },
+        AbiEncodingSize::Bounded { min, max } => statements! {
+            (if (or(
+                (lt([enc_size.clone()], [literal_expression!{(min)}])),
+                (gt([enc_size], [literal_expression!{(max)}]))
+            )) {
+                [revert_with_invalid_abi_data(provider, db)]
+            })
+        },
+    }
+}
+
 impl DefaultRuntimeProvider {
     fn abi_decode_static(
         &mut self,
@@ -508,6 +538,14 @@ impl DefaultRuntimeProvider {
                 })
             }
 
+            AbiType::Bytes if ty.is_calldata_bytes(db.upcast()) => {
+                debug_assert!(matches!(abi_loc, AbiSrcLocation::CallData));
+                let name = "$abi_decode_calldata_bytes";
+                self.create_then_call(name, args, |provider| {
+                    make_abi_decode_calldata_bytes_type(provider, db, name)
+                })
+            }
+
             AbiType::Bytes => {
                 let len = match &ty.data(db.upcast()).kind {
                     TypeKind::Array(ArrayDef { len, .. }) => *len,
@@ -734,6 +772,42 @@ fn make_abi_decode_bytes_type(
     RuntimeFunction::from_statement(func)
 }
 
+/// Decodes `bytes` in calldata to a `CalldataBytes`, which records where the
+/// bytes are instead of copying them to memory.
+fn make_abi_decode_calldata_bytes_type(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    func_name: &str,
+) -> RuntimeFunction {
+    let func_name = YulVariable::new(func_name);
+    let src = YulVariable::new("src");
+    let decoded_data = YulVariable::new("decoded_data");
+    let decoded_size = YulVariable::new("decoded_size");
+    let bytes_size = YulVariable::new("bytes_size");
+    let end_word = YulVariable::new("end_word");
+    let end_word_ptr = YulVariable::new("end_word_ptr");
+    let padding_size_bits = YulVariable::new("padding_size_bits");
+
+    let func = function_definition! {
+        function [func_name.ident()]([src.ident()]) -> [(vec![decoded_data.ident(),decoded_size.ident()])...] {
+            (let [bytes_size.ident()] := calldataload([src.expr()]))
+            (if (gt([bytes_size.expr()], (calldatasize()))) { [revert_with_invalid_abi_data(provider, db)] } )
+            ([src.ident()] := add([src.expr()], 32))
+            (let padded_data_size := mul((div((add([bytes_size.expr()], 31)), 32)), 32))
+            ([decoded_size.ident()] := add(padded_data_size, 32))
+            (let [end_word_ptr.ident()] := sub((add([src.expr()], padded_data_size)), 32))
+            (let [end_word.ident()] := calldataload([end_word_ptr.expr()]))
+            (let [padding_size_bits.ident()] := mul((sub(padded_data_size, [bytes_size.expr()])), 8))
+            [(check_right_padding(provider, db, end_word.expr(), padding_size_bits.expr()))...]
+            ([decoded_data.ident()] := [provider.alloc(db, literal_expression!{ 64 })])
+            (mstore([decoded_data.expr()], [src.expr()]))
+            (mstore((add([decoded_data.expr()], 32)), [bytes_size.expr()]))
+        }
+    };
+
+    RuntimeFunction::from_statement(func)
+}
+
 fn make_abi_decode_dynamic_elem_array_type(
     provider: &mut DefaultRuntimeProvider,
     db: &dyn CodegenDb,
@@ -882,6 +956,7 @@ fn make_abi_decode_dynamic_aggregate_type(
     RuntimeFunction(func_def)
 }
 
+#[derive(Clone, Copy)]
 enum AbiEncodingSize {
     Static(usize),
     Bounded { min: usize, max: usize },
@@ -889,11 +964,11 @@ enum AbiEncodingSize {
 
 fn abi_enc_size(db: &dyn CodegenDb, types: &[TypeId]) -> AbiEncodingSize {
     let mut min = 0;
-    let mut max = 0;
+    let mut max: usize = 0;
     for &ty in types {
         let legalized_ty = db.codegen_legalized_type(ty);
         min += db.codegen_abi_type_minimum_size(legalized_ty);
-        max += db.codegen_abi_type_maximum_size(legalized_ty);
+        max = max.saturating_add(db.codegen_abi_type_maximum_size(legalized_ty));
     }
 
     if min == max {
@@ -0,0 +1,103 @@
+use crate::{
+    db::CodegenDb,
+    yul::{slot_size::SLOT_SIZE, YulVariable},
+};
+
+use super::{BitMask, DefaultRuntimeProvider, RuntimeFunction};
+
+use fe_mir::ir::TypeId;
+
+use yultsur::*;
+
+pub(super) fn dispatch_leading_zeros(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    value: yul::Expression,
+    ty: TypeId,
+) -> yul::Expression {
+    debug_assert!(ty.is_integral(db.upcast()));
+    let (value, bits) = truncate(db, value, ty);
+
+    let name = "$leading_zeros";
+    provider.create_then_call(name, vec![value, bits], |_| make_leading_zeros(name))
+}
+
+pub(super) fn dispatch_count_ones(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    value: yul::Expression,
+    ty: TypeId,
+) -> yul::Expression {
+    debug_assert!(ty.is_integral(db.upcast()));
+    let (value, _) = truncate(db, value, ty);
+
+    let name = "$count_ones";
+    provider.create_then_call(name, vec![value], |_| make_count_ones(name))
+}
+
+/// Returns the value truncated to the bit width of `ty`, and the bit width.
+/// Signed values are sign extended to 256 bits, so the extension has to be
+/// masked away before the bits are counted.
+fn truncate(
+    db: &dyn CodegenDb,
+    value: yul::Expression,
+    ty: TypeId,
+) -> (yul::Expression, yul::Expression) {
+    let size = ty.size_of(db.upcast(), SLOT_SIZE);
+    let bits = literal_expression! {(size * 8)};
+    if ty.is_signed(db.upcast()) && size < SLOT_SIZE {
+        let mask = BitMask::new(size);
+        (expression! { and([value], [mask.as_expr()]) }, bits)
+    } else {
+        (value, bits)
+    }
+}
+
+/// Counts the leading zeros of a `$bits` wide value without branching, by
+/// binary searching the index of the most significant set bit.
+fn make_leading_zeros(func_name: &str) -> RuntimeFunction {
+    let func_name = YulVariable::new(func_name);
+    let value = YulVariable::new("$value");
+    let bits = YulVariable::new("$bits");
+    let msb = YulVariable::new("$msb");
+    let ret = YulVariable::new("$ret");
+
+    let func = function_definition! {
+        function [func_name.ident()]([value.ident()], [bits.ident()]) -> [ret.ident()] {
+            (let [msb.ident()] := shl(7, (lt(0xffffffffffffffffffffffffffffffff, [value.expr()]))))
+            ([msb.ident()] := or([msb.expr()], (shl(6, (lt(0xffffffffffffffff, (shr([msb.expr()], [value.expr()]))))))))
+            ([msb.ident()] := or([msb.expr()], (shl(5, (lt(0xffffffff, (shr([msb.expr()], [value.expr()]))))))))
+            ([msb.ident()] := or([msb.expr()], (shl(4, (lt(0xffff, (shr([msb.expr()], [value.expr()]))))))))
+            ([msb.ident()] := or([msb.expr()], (shl(3, (lt(0xff, (shr([msb.expr()], [value.expr()]))))))))
+            ([msb.ident()] := or([msb.expr()], (shl(2, (lt(0xf, (shr([msb.expr()], [value.expr()]))))))))
+            ([msb.ident()] := or([msb.expr()], (shl(1, (lt(0x3, (shr([msb.expr()], [value.expr()]))))))))
+            ([msb.ident()] := or([msb.expr()], (lt(0x1, (shr([msb.expr()], [value.expr()]))))))
+            ([ret.ident()] := add((sub((sub([bits.expr()], 1)), [msb.expr()])), (iszero([value.expr()]))))
+        }
+    };
+    RuntimeFunction::from_statement(func)
+}
+
+/// Counts the set bits of a value with the usual SWAR reduction: the bits
+/// are summed in parallel within each byte, and the byte sums are then added
+/// up by a single multiplication. The sum of 32 bytes of ones overflows a
+/// byte, so that single case is handled separately.
+fn make_count_ones(func_name: &str) -> RuntimeFunction {
+    let func_name = YulVariable::new(func_name);
+    let value = YulVariable::new("$value");
+    let max = YulVariable::new("$max");
+    let is_max = YulVariable::new("$is_max");
+    let ret = YulVariable::new("$ret");
+
+    let func = function_definition! {
+        function [func_name.ident()]([value.ident()]) -> [ret.ident()] {
+            (let [max.ident()] := not(0))
+            (let [is_max.ident()] := eq([value.expr()], [max.expr()]))
+            ([value.ident()] := sub([value.expr()], (and((shr(1, [value.expr()])), (div([max.expr()], 3))))))
+            ([value.ident()] := add((and([value.expr()], (div([max.expr()], 5)))), (and((shr(2, [value.expr()])), (div([max.expr()], 5))))))
+            ([value.ident()] := and((add([value.expr()], (shr(4, [value.expr()])))), (div([max.expr()], 17))))
+            ([ret.ident()] := or((shl(8, [is_max.expr()])), (shr(248, (mul([value.expr()], (div([max.expr()], 255))))))))
+        }
+    };
+    RuntimeFunction::from_statement(func)
+}
@@ -1,4 +1,5 @@
 mod abi;
+mod bits;
 mod contract;
 mod data;
 mod emit;
@@ -22,8 +23,11 @@ use super::slot_size::yul_primitive_type;
 pub trait RuntimeProvider {
     fn collect_definitions(&self) -> Vec<yul::FunctionDefinition>;
 
+    /// Allocates `size` bytes of memory and returns a pointer to them.
     fn alloc(&mut self, db: &dyn CodegenDb, size: yul::Expression) -> yul::Expression;
 
+    /// Returns a pointer to the lowest available memory, without allocating
+    /// it. The memory may be used as scratch space until the next allocation.
     fn avail(&mut self, db: &dyn CodegenDb) -> yul::Expression;
 
     fn create(
@@ -149,6 +153,15 @@ pub trait RuntimeProvider {
         abi_loc: AbiSrcLocation,
     ) -> yul::Expression;
 
+    /// Reverts with an invalid ABI data error unless `size` is a valid
+    /// encoding size of `types`.
+    fn abi_validate_size(
+        &mut self,
+        db: &dyn CodegenDb,
+        size: yul::Expression,
+        types: &[TypeId],
+    ) -> yul::Expression;
+
     fn primitive_cast(
         &mut self,
         db: &dyn CodegenDb,
@@ -215,6 +228,20 @@ pub trait RuntimeProvider {
         rhs: yul::Expression,
         ty: TypeId,
     ) -> yul::Expression;
+
+    fn leading_zeros(
+        &mut self,
+        db: &dyn CodegenDb,
+        value: yul::Expression,
+        ty: TypeId,
+    ) -> yul::Expression;
+
+    fn count_ones(
+        &mut self,
+        db: &dyn CodegenDb,
+        value: yul::Expression,
+        ty: TypeId,
+    ) -> yul::Expression;
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -223,6 +250,10 @@ pub enum AbiSrcLocation {
     Memory,
 }
 
+/// The default runtime, which allocates memory with a bump allocator: the
+/// free memory pointer only ever grows. `std::buf::MemoryRegion` relies on
+/// this to reset the pointer, so the memory layout in `data.rs` must be kept
+/// in sync with `std::buf`.
 #[derive(Debug, Default)]
 pub struct DefaultRuntimeProvider {
     functions: IndexMap<String, RuntimeFunction>,
@@ -612,15 +643,33 @@ impl RuntimeProvider for DefaultRuntimeProvider {
                     TypeKind::Array(ArrayDef { len, .. }) => *len,
                     _ => unreachable!(),
                 };
-                let name = format! {"$abi_encode_bytes{len}_type_to_{func_name_postfix}"};
+                let is_src_storage = legalized_ty.is_sptr(db.upcast());
+                let src_postfix = if is_src_storage { "_from_storage" } else { "" };
+                let name =
+                    format! {"$abi_encode_bytes{len}_type{src_postfix}_to_{func_name_postfix}"};
                 self.create_then_call(&name, args, |provider| {
-                    abi::make_abi_encode_bytes_type(provider, db, &name, len, is_dst_storage)
+                    abi::make_abi_encode_bytes_type(
+                        provider,
+                        db,
+                        &name,
+                        len,
+                        is_src_storage,
+                        is_dst_storage,
+                    )
                 })
             }
             AbiType::String => {
-                let name = format! {"$abi_encode_string_type_to_{func_name_postfix}"};
+                let is_src_storage = legalized_ty.is_sptr(db.upcast());
+                let src_postfix = if is_src_storage { "_from_storage" } else { "" };
+                let name = format! {"$abi_encode_string_type{src_postfix}_to_{func_name_postfix}"};
                 self.create_then_call(&name, args, |provider| {
-                    abi::make_abi_encode_string_type(provider, db, &name, is_dst_storage)
+                    abi::make_abi_encode_string_type(
+                        provider,
+                        db,
+                        &name,
+                        is_src_storage,
+                        is_dst_storage,
+                    )
                 })
             }
             AbiType::Function => unreachable!(),
@@ -670,6 +719,22 @@ impl RuntimeProvider for DefaultRuntimeProvider {
         })
     }
 
+    fn abi_validate_size(
+        &mut self,
+        db: &dyn CodegenDb,
+        size: yul::Expression,
+        types: &[TypeId],
+    ) -> yul::Expression {
+        let mut name = "$abi_validate_size".to_string();
+        for ty in types {
+            write!(name, "_{}", ty.0).unwrap();
+        }
+
+        self.create_then_call(&name, vec![size], |provider| {
+            abi::make_abi_validate_size(provider, db, &name, types)
+        })
+    }
+
     fn safe_add(
         &mut self,
         db: &dyn CodegenDb,
@@ -735,6 +800,26 @@ impl RuntimeProvider for DefaultRuntimeProvider {
         debug_assert!(ty.is_integral(db.upcast()));
         safe_math::dispatch_safe_pow(self, db, lhs, rhs, ty)
     }
+
+    fn leading_zeros(
+        &mut self,
+        db: &dyn CodegenDb,
+        value: yul::Expression,
+        ty: TypeId,
+    ) -> yul::Expression {
+        debug_assert!(ty.is_integral(db.upcast()));
+        bits::dispatch_leading_zeros(self, db, value, ty)
+    }
+
+    fn count_ones(
+        &mut self,
+        db: &dyn CodegenDb,
+        value: yul::Expression,
+        ty: TypeId,
+    ) -> yul::Expression {
+        debug_assert!(ty.is_integral(db.upcast()));
+        bits::dispatch_count_ones(self, db, value, ty)
+    }
 }
 
 #[derive(Debug)]
@@ -6,7 +6,7 @@ use crate::{
 use super::{DefaultRuntimeProvider, RuntimeFunction, RuntimeProvider};
 
 use fe_abi::function::{AbiFunction, AbiFunctionType, StateMutability};
-use fe_mir::ir::{self, TypeId};
+use fe_mir::ir::TypeId;
 use yultsur::*;
 
 pub(super) fn make_revert(
@@ -63,28 +63,20 @@ pub(super) fn make_revert(
 /// Returns signature hash of the type.
 fn type_signature_for_revert(db: &dyn CodegenDb, name: &str, ty: TypeId) -> yul::Expression {
     let deref_ty = ty.deref(db.upcast());
-    let ty_data = deref_ty.data(db.upcast());
-    let args = match &ty_data.kind {
-        ir::TypeKind::Struct(def) => def
-            .fields
-            .iter()
-            .map(|(_, ty)| ("".to_string(), db.codegen_abi_type(*ty)))
-            .collect(),
-        _ => {
-            let abi_ty = db.codegen_abi_type(deref_ty);
-            vec![("_".to_string(), abi_ty)]
-        }
+    let abi = if deref_ty.is_struct(db.upcast()) {
+        db.codegen_abi_error(deref_ty)
+    } else {
+        // selector and state mutability is independent we can set has_self and has_ctx any value.
+        let abi_ty = db.codegen_abi_type(deref_ty);
+        AbiFunction::new(
+            AbiFunctionType::Function,
+            name.to_string(),
+            vec![("_".to_string(), abi_ty)],
+            None,
+            StateMutability::Pure,
+        )
     };
 
-    // selector and state mutability is independent we can set has_self and has_ctx any value.
-    let selector = AbiFunction::new(
-        AbiFunctionType::Function,
-        name.to_string(),
-        args,
-        None,
-        StateMutability::Pure,
-    )
-    .selector();
-    let type_sig = selector.hex();
+    let type_sig = abi.selector().hex();
     literal_expression! {(format!{"0x{type_sig}" })}
 }
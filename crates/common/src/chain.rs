@@ -0,0 +1,75 @@
+//! Profiles of the chains that code can be deployed to.
+use std::collections::BTreeMap;
+
+use smol_str::SmolStr;
+
+use crate::evm::{EvmVersion, Precompile};
+
+/// The features of a chain that the code of an ingot is compiled for.
+///
+/// Profiles of well-known chains are built in, and manifests can declare
+/// their own or override the built-in ones:
+///
+/// ```toml
+/// chain = "optimism"
+///
+/// [chains.optimism]
+/// evm-version = "shanghai"
+/// precompiles = ["ec_recover", "sha2_256"]
+/// gas-hints = { sstore = 5000 }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChainProfile {
+    pub name: SmolStr,
+    pub evm_version: EvmVersion,
+    /// The precompiles that exist on the chain.
+    pub precompiles: Vec<Precompile>,
+    /// Gas costs of operations that differ from mainnet, e.g. `sstore`. They
+    /// are recorded in the build metadata for tools that estimate costs, and
+    /// don't affect code generation.
+    pub gas_hints: BTreeMap<SmolStr, u64>,
+}
+
+impl ChainProfile {
+    pub const BUILTIN: [&'static str; 3] = ["mainnet", "optimism", "arbitrum"];
+
+    /// Returns the built-in profile of the chain called `name`.
+    pub fn builtin(name: &str) -> Option<Self> {
+        if !Self::BUILTIN.contains(&name) {
+            return None;
+        }
+
+        let precompiles = Precompile::ALL
+            .into_iter()
+            .filter(|precompile| name != "mainnet" || precompile.introduced().is_some())
+            .collect();
+        Some(Self {
+            name: name.into(),
+            evm_version: EvmVersion::Cancun,
+            precompiles,
+            gas_hints: BTreeMap::new(),
+        })
+    }
+
+    pub fn has_precompile(&self, precompile: Precompile) -> bool {
+        self.precompiles.contains(&precompile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_profiles() {
+        for name in ChainProfile::BUILTIN {
+            let profile = ChainProfile::builtin(name).unwrap();
+            assert_eq!(profile.name, name);
+            assert!(profile.has_precompile(Precompile::EcRecover));
+            assert!(profile.has_precompile(Precompile::PointEvaluation));
+        }
+        let mainnet = ChainProfile::builtin("mainnet").unwrap();
+        assert!(!mainnet.has_precompile(Precompile::P256Verify));
+        assert_eq!(ChainProfile::builtin("goerli"), None);
+    }
+}
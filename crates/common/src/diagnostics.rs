@@ -5,9 +5,14 @@ pub use codespan_reporting::diagnostic as cs;
 use codespan_reporting::files::Error as CsError;
 use codespan_reporting::term;
 pub use cs::Severity;
+use once_cell::sync::OnceCell;
+use std::fmt;
 use std::ops::Range;
 use std::rc::Rc;
-use term::termcolor::{BufferWriter, ColorChoice};
+use std::str::FromStr;
+use term::termcolor::{BufferWriter, Color, ColorChoice, ColorSpec};
+
+static RENDER_OPTIONS: OnceCell<RenderOptions> = OnceCell::new();
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct Diagnostic {
@@ -15,15 +20,20 @@ pub struct Diagnostic {
     pub message: String,
     pub labels: Vec<Label>,
     pub notes: Vec<String>,
+    pub suggestions: Vec<Suggestion>,
 }
 impl Diagnostic {
     pub fn into_cs(self) -> cs::Diagnostic<SourceFileId> {
+        let suggestions = self
+            .suggestions
+            .into_iter()
+            .map(|suggestion| format!("Suggestion: {}", suggestion.message));
         cs::Diagnostic {
             severity: self.severity,
             code: None,
             message: self.message,
             labels: self.labels.into_iter().map(Label::into_cs_label).collect(),
-            notes: self.notes,
+            notes: self.notes.into_iter().chain(suggestions).collect(),
         }
     }
     pub fn error(message: String) -> Self {
@@ -32,10 +42,73 @@ impl Diagnostic {
             message,
             labels: vec![],
             notes: vec![],
+            suggestions: vec![],
         }
     }
 }
 
+/// A fix for a diagnostic: replacing the source code at `span` with
+/// `replacement` resolves the problem. The `message` is shown to the user as a
+/// note.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Suggestion {
+    pub message: String,
+    pub span: Span,
+    pub replacement: String,
+    pub applicability: Applicability,
+}
+
+/// Whether a suggestion can be applied without the user looking at it.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Applicability {
+    /// The suggestion is known to fix the problem, and `fe fix` applies it.
+    MachineApplicable,
+    /// The suggestion is one of several possible fixes, or may need more
+    /// changes to compile, so it's only shown to the user.
+    MaybeIncorrect,
+}
+
+/// Returns the suggestions that `fe fix` applies, i.e. the machine-applicable
+/// ones.
+pub fn machine_applicable_suggestions(diagnostics: &[Diagnostic]) -> Vec<Suggestion> {
+    diagnostics
+        .iter()
+        .flat_map(|diag| &diag.suggestions)
+        .filter(|suggestion| suggestion.applicability == Applicability::MachineApplicable)
+        .cloned()
+        .collect()
+}
+
+/// Applies the given suggestions for a file to its source `src`, and returns
+/// the new source along with the suggestions that were applied.
+///
+/// Identical suggestions are applied once, and a suggestion that overlaps one
+/// before it is skipped; it can be applied by checking the file again.
+pub fn apply_suggestions(src: &str, suggestions: &[Suggestion]) -> (String, Vec<Suggestion>) {
+    let mut sorted: Vec<_> = suggestions.iter().collect();
+    sorted.sort_by_key(|suggestion| (suggestion.span.start, suggestion.span.end));
+    sorted.dedup_by(|a, b| a.span == b.span && a.replacement == b.replacement);
+
+    let mut applied: Vec<Suggestion> = vec![];
+    for suggestion in sorted {
+        let overlaps = applied
+            .last()
+            .is_some_and(|prev| suggestion.span.start < prev.span.end);
+        if !overlaps {
+            applied.push(suggestion.clone());
+        }
+    }
+
+    let mut result = src.to_string();
+    for suggestion in applied.iter().rev() {
+        result.replace_range(
+            suggestion.span.start..suggestion.span.end,
+            &suggestion.replacement,
+        );
+    }
+    (result, applied)
+}
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum LabelStyle {
     Primary,
@@ -88,11 +161,243 @@ impl Label {
     }
 }
 
-/// Print the given diagnostics to stderr.
+/// Sorts diagnostics by the location of their primary label, so that the
+/// order doesn't depend on the order in which items were analyzed.
+///
+/// Diagnostics are ordered by file path, then by span, then by message.
+/// Diagnostics without labels come first.
+pub fn sort_diagnostics(db: &dyn SourceDb, diagnostics: &mut [Diagnostic]) {
+    diagnostics.sort_by_cached_key(|diag| {
+        let span = diag
+            .labels
+            .iter()
+            .find(|label| label.style == LabelStyle::Primary)
+            .or_else(|| diag.labels.first())
+            .map(|label| label.span);
+        let location = span.map(|span| (span.file_id.path(db), span.start, span.end));
+        (location, diag.message.clone())
+    });
+}
+
+/// How diagnostics are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorFormat {
+    /// The message, the labeled source code and the notes.
+    Full,
+    /// A single `path:line:column: severity: message` line, e.g. for CI logs.
+    Short,
+}
+
+impl ErrorFormat {
+    pub const ALL: [ErrorFormat; 2] = [ErrorFormat::Full, ErrorFormat::Short];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorFormat::Full => "full",
+            ErrorFormat::Short => "short",
+        }
+    }
+
+    fn config(self) -> term::Config {
+        let display_style = match self {
+            ErrorFormat::Full => term::DisplayStyle::Rich,
+            ErrorFormat::Short => term::DisplayStyle::Short,
+        };
+        // Tabs are expanded and wide characters are measured by their display
+        // width, so the underlines line up with the source code.
+        term::Config {
+            display_style,
+            tab_width: 4,
+            ..term::Config::default()
+        }
+    }
+}
+
+impl fmt::Display for ErrorFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ErrorFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ErrorFormat::ALL
+            .into_iter()
+            .find(|format| format.as_str() == s)
+            .ok_or_else(|| format!("unknown error format `{s}`; expected `full` or `short`"))
+    }
+}
+
+/// When diagnostics are printed in color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Color the output if the terminal supports it, unless the environment
+    /// disables it.
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorMode {
+    pub const ALL: [ColorMode; 3] = [ColorMode::Auto, ColorMode::Always, ColorMode::Never];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ColorMode::Auto => "auto",
+            ColorMode::Always => "always",
+            ColorMode::Never => "never",
+        }
+    }
+
+    fn choice(self) -> ColorChoice {
+        self.choice_with_env(|name| std::env::var(name).ok())
+    }
+
+    /// In `Auto` mode, a non-empty `NO_COLOR` disables colors, a
+    /// `CLICOLOR_FORCE` other than `0` enables them, and `CLICOLOR=0`
+    /// disables them. See <https://no-color.org> and
+    /// <https://bixense.com/clicolors>.
+    fn choice_with_env(self, var: impl Fn(&str) -> Option<String>) -> ColorChoice {
+        match self {
+            ColorMode::Always => ColorChoice::Always,
+            ColorMode::Never => ColorChoice::Never,
+            ColorMode::Auto => {
+                let var = |name| var(name).filter(|value| !value.is_empty());
+                if var("NO_COLOR").is_some() {
+                    ColorChoice::Never
+                } else if var("CLICOLOR_FORCE").is_some_and(|value| value != "0") {
+                    ColorChoice::Always
+                } else if var("CLICOLOR").is_some_and(|value| value == "0") {
+                    ColorChoice::Never
+                } else {
+                    ColorChoice::Auto
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for ColorMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ColorMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ColorMode::ALL
+            .into_iter()
+            .find(|mode| mode.as_str() == s)
+            .ok_or_else(|| {
+                format!("unknown color mode `{s}`; expected `auto`, `always` or `never`")
+            })
+    }
+}
+
+/// The colors of diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    Default,
+    /// Bold, bright colors that stay legible on dark and light backgrounds.
+    HighContrast,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 2] = [Theme::Default, Theme::HighContrast];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Theme::Default => "default",
+            Theme::HighContrast => "high-contrast",
+        }
+    }
+
+    fn styles(self) -> term::Styles {
+        let mut styles = term::Styles::default();
+        if self == Theme::HighContrast {
+            let bold = |color| {
+                let mut spec = ColorSpec::new();
+                spec.set_fg(Some(color)).set_intense(true).set_bold(true);
+                spec
+            };
+            styles.header_bug = bold(Color::Red);
+            styles.header_error = bold(Color::Red);
+            styles.header_warning = bold(Color::Yellow);
+            styles.header_note = bold(Color::Green);
+            styles.header_help = bold(Color::Cyan);
+            styles.header_message = bold(Color::White);
+            styles.primary_label_bug = bold(Color::Red);
+            styles.primary_label_error = bold(Color::Red);
+            styles.primary_label_warning = bold(Color::Yellow);
+            styles.primary_label_note = bold(Color::Green);
+            styles.primary_label_help = bold(Color::Cyan);
+            styles.secondary_label = bold(Color::Cyan);
+            styles.line_number = bold(Color::White);
+            styles.source_border = bold(Color::White);
+            styles.note_bullet = bold(Color::White);
+        }
+        styles
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Theme::ALL
+            .into_iter()
+            .find(|theme| theme.as_str() == s)
+            .ok_or_else(|| format!("unknown theme `{s}`; expected `default` or `high-contrast`"))
+    }
+}
+
+/// How `print_diagnostics` prints diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderOptions {
+    pub format: ErrorFormat,
+    pub color: ColorMode,
+    pub theme: Theme,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            format: ErrorFormat::Full,
+            color: ColorMode::Auto,
+            theme: Theme::Default,
+        }
+    }
+}
+
+/// Sets how `print_diagnostics` prints diagnostics. Only the first call has
+/// an effect.
+pub fn set_render_options(options: RenderOptions) {
+    let _ = RENDER_OPTIONS.set(options);
+}
+
+fn render_options() -> RenderOptions {
+    RENDER_OPTIONS.get().copied().unwrap_or_default()
+}
+
+/// Print the given diagnostics to stderr, as set with `set_render_options`.
 pub fn print_diagnostics(db: &dyn SourceDb, diagnostics: &[Diagnostic]) {
-    let writer = BufferWriter::stderr(ColorChoice::Auto);
+    let options = render_options();
+    let writer = BufferWriter::stderr(options.color.choice());
     let mut buffer = writer.buffer();
-    let config = term::Config::default();
+    let config = term::Config {
+        styles: options.theme.styles(),
+        ..options.format.config()
+    };
     let files = SourceDbWrapper(db);
 
     for diag in diagnostics {
@@ -104,9 +409,18 @@ pub fn print_diagnostics(db: &dyn SourceDb, diagnostics: &[Diagnostic]) {
 
 /// Format the given diagnostics as a string.
 pub fn diagnostics_string(db: &dyn SourceDb, diagnostics: &[Diagnostic]) -> String {
+    format_diagnostics(db, diagnostics, ErrorFormat::Full)
+}
+
+/// Format the given diagnostics as a string in the given format.
+pub fn format_diagnostics(
+    db: &dyn SourceDb,
+    diagnostics: &[Diagnostic],
+    format: ErrorFormat,
+) -> String {
     let writer = BufferWriter::stderr(ColorChoice::Never);
     let mut buffer = writer.buffer();
-    let config = term::Config::default();
+    let config = format.config();
     let files = SourceDbWrapper(db);
 
     for diag in diagnostics {
@@ -143,3 +457,124 @@ impl<'a> codespan_reporting::files::Files<'_> for SourceDbWrapper<'a> {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::TestDb;
+
+    fn suggestion(start: usize, end: usize, replacement: &str) -> Suggestion {
+        Suggestion {
+            message: String::new(),
+            span: Span::new(SourceFileId::dummy_file(), start, end),
+            replacement: replacement.into(),
+            applicability: Applicability::MachineApplicable,
+        }
+    }
+
+    #[test]
+    fn apply_overlapping_suggestions() {
+        let src = "Foo.bar(x)";
+        let suggestions = [
+            suggestion(3, 4, "::"),
+            suggestion(0, 0, "use a::Foo\n"),
+            suggestion(3, 4, "::"),
+            suggestion(3, 7, "::baz("),
+            suggestion(0, 0, "use b::x\n"),
+        ];
+        let (fixed, applied) = apply_suggestions(src, &suggestions);
+        assert_eq!(fixed, "use a::Foo\nuse b::x\nFoo::bar(x)");
+        assert_eq!(applied.len(), 3);
+    }
+
+    #[test]
+    fn only_machine_applicable_suggestions() {
+        let maybe_incorrect = Suggestion {
+            applicability: Applicability::MaybeIncorrect,
+            ..suggestion(0, 0, "use b::Foo\n")
+        };
+        let diags = [
+            Diagnostic {
+                suggestions: vec![suggestion(0, 0, "use a::Foo\n"), maybe_incorrect.clone()],
+                ..Diagnostic::error("error".into())
+            },
+            Diagnostic {
+                suggestions: vec![maybe_incorrect],
+                ..Diagnostic::error("error".into())
+            },
+        ];
+        assert_eq!(
+            machine_applicable_suggestions(&diags),
+            vec![suggestion(0, 0, "use a::Foo\n")]
+        );
+    }
+
+    #[test]
+    fn short_error_format() {
+        let mut db = TestDb::default();
+        let file = SourceFileId::new_local(&mut db, "test.fe", "fn f():\n  x\n".into());
+        let diag = Diagnostic {
+            labels: vec![Label::primary(Span::new(file, 10, 11), "undefined")],
+            notes: vec!["Note: a note".into()],
+            ..Diagnostic::error("cannot find value `x` in this scope".into())
+        };
+
+        let output = format_diagnostics(&db, &[diag.clone(), diag], ErrorFormat::Short);
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(
+            lines,
+            vec!["test.fe:2:3: error: cannot find value `x` in this scope"; 2]
+        );
+    }
+
+    #[test]
+    fn underline_after_tabs_and_wide_chars() {
+        let mut db = TestDb::default();
+        let content = "let \u{e4}\t= \"\u{1f600}\"\tx\n";
+        let file = SourceFileId::new_local(&mut db, "test.fe", content.into());
+        let start = content.find('x').unwrap();
+        let diag = Diagnostic {
+            labels: vec![Label::primary(Span::new(file, start, start + 1), "here")],
+            ..Diagnostic::error("error".into())
+        };
+
+        let output = format_diagnostics(&db, &[diag], ErrorFormat::Full);
+        let source_line = output.lines().find(|line| line.contains("let")).unwrap();
+        let underline = output.lines().find(|line| line.contains('^')).unwrap();
+        // The emoji is two columns wide.
+        let column = |line: &str, c: char| {
+            line.chars()
+                .take_while(|&ch| ch != c)
+                .map(|ch| if ch == '\u{1f600}' { 2 } else { 1 })
+                .sum::<usize>()
+        };
+        assert!(!source_line.contains('\t'));
+        assert_eq!(column(source_line, 'x'), column(underline, '^'));
+    }
+
+    #[test]
+    fn color_mode_env() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        let auto = |vars| ColorMode::Auto.choice_with_env(env(vars));
+
+        assert_eq!(auto(&[]), ColorChoice::Auto);
+        assert_eq!(auto(&[("NO_COLOR", "1")]), ColorChoice::Never);
+        assert_eq!(auto(&[("NO_COLOR", "")]), ColorChoice::Auto);
+        assert_eq!(auto(&[("CLICOLOR", "0")]), ColorChoice::Never);
+        assert_eq!(auto(&[("CLICOLOR_FORCE", "1")]), ColorChoice::Always);
+        assert_eq!(
+            auto(&[("CLICOLOR_FORCE", "1"), ("NO_COLOR", "1")]),
+            ColorChoice::Never
+        );
+        assert_eq!(
+            ColorMode::Always.choice_with_env(env(&[("NO_COLOR", "1")])),
+            ColorChoice::Always
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! Editions of the language, and the features that each of them enables.
+//!
+//! An ingot declares its edition with the `edition` key of its manifest, so
+//! that ingots written for different editions can depend on each other.
+use std::fmt;
+use std::str::FromStr;
+
+use crate::diagnostics::{Diagnostic, Label, Severity};
+use crate::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Edition {
+    /// The language of Fe v0.26.
+    E2023,
+    E2024,
+}
+
+impl Edition {
+    pub const ALL: [Edition; 2] = [Edition::E2023, Edition::E2024];
+
+    /// The edition of standalone files, of the standard library and of
+    /// ingots that don't declare one.
+    pub const LATEST: Edition = Edition::E2024;
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Edition::E2023 => "2023",
+            Edition::E2024 => "2024",
+        }
+    }
+
+    pub fn supports(self, feature: Feature) -> bool {
+        self >= feature.edition()
+    }
+}
+
+impl fmt::Display for Edition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Edition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Edition::ALL
+            .into_iter()
+            .find(|edition| edition.as_str() == s)
+            .ok_or_else(|| {
+                let editions: Vec<_> = Edition::ALL.iter().map(|e| e.as_str()).collect();
+                format!(
+                    "unknown edition `{s}`; the supported editions are {}",
+                    editions.join(", ")
+                )
+            })
+    }
+}
+
+/// A language feature that isn't available in every edition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Feature {
+    /// `&storage T` parameters.
+    StorageReferences,
+    /// Generic parameters with several bounds, e.g. `T: Foo + Bar`.
+    MultipleBounds,
+    /// `impl Trait for Self` blocks inside of contracts.
+    ContractTraitImpls,
+    /// The `#only`, `#nonreentrant` and `#when_not_paused` attributes of
+    /// contract functions.
+    FunctionGuards,
+}
+
+impl Feature {
+    /// The first edition that supports the feature.
+    pub fn edition(self) -> Edition {
+        match self {
+            Feature::StorageReferences
+            | Feature::MultipleBounds
+            | Feature::ContractTraitImpls
+            | Feature::FunctionGuards => Edition::E2024,
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Feature::StorageReferences => "`&storage` parameters",
+            Feature::MultipleBounds => "multiple bounds on a generic parameter",
+            Feature::ContractTraitImpls => "`impl` blocks inside of contracts",
+            Feature::FunctionGuards => "function guard attributes",
+        }
+    }
+
+    /// Returns the error for a use of the feature at `span` in code of the
+    /// given edition, or `None` if the edition supports it.
+    pub fn gate(self, edition: Edition, span: Span) -> Option<Diagnostic> {
+        if edition.supports(self) {
+            return None;
+        }
+        let required = self.edition();
+        Some(Diagnostic {
+            severity: Severity::Error,
+            message: format!("{} require edition {required}", self.description()),
+            labels: vec![Label::primary(
+                span,
+                format!("not available in edition {edition}"),
+            )],
+            notes: vec![format!(
+                "Hint: set `edition = \"{required}\"` in the `fe.toml` of the ingot"
+            )],
+            suggestions: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        for edition in Edition::ALL {
+            assert_eq!(edition.as_str().parse::<Edition>(), Ok(edition));
+        }
+        assert!("2021".parse::<Edition>().is_err());
+    }
+
+    #[test]
+    fn feature_gates() {
+        let feature = Feature::StorageReferences;
+        assert!(feature.gate(Edition::E2024, Span::dummy()).is_none());
+
+        let diag = feature.gate(Edition::E2023, Span::dummy()).unwrap();
+        assert_eq!(diag.message, "`&storage` parameters require edition 2024");
+        assert_eq!(
+            diag.notes,
+            vec!["Hint: set `edition = \"2024\"` in the `fe.toml` of the ingot"]
+        );
+    }
+}
@@ -0,0 +1,242 @@
+//! The EVM versions that code can be compiled for, and the instructions that
+//! are available on each of them.
+use std::fmt;
+use std::str::FromStr;
+
+/// An EVM version, named after the hard fork that introduced it.
+///
+/// Versions before Istanbul aren't supported, because the code generator
+/// relies on instructions that were introduced in Constantinople.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EvmVersion {
+    Istanbul,
+    Berlin,
+    London,
+    Paris,
+    Shanghai,
+    Cancun,
+}
+
+impl EvmVersion {
+    pub const ALL: [EvmVersion; 6] = [
+        EvmVersion::Istanbul,
+        EvmVersion::Berlin,
+        EvmVersion::London,
+        EvmVersion::Paris,
+        EvmVersion::Shanghai,
+        EvmVersion::Cancun,
+    ];
+
+    /// The name of the version, as used by solc.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EvmVersion::Istanbul => "istanbul",
+            EvmVersion::Berlin => "berlin",
+            EvmVersion::London => "london",
+            EvmVersion::Paris => "paris",
+            EvmVersion::Shanghai => "shanghai",
+            EvmVersion::Cancun => "cancun",
+        }
+    }
+
+    /// Returns why `instruction` (e.g. `"selfdestruct"`) must not be used on
+    /// this version, or `None` if it can be used.
+    pub fn instruction_error(self, instruction: &str) -> Option<String> {
+        let introduced = match instruction {
+            "basefee" => EvmVersion::London,
+            "prevrandao" => EvmVersion::Paris,
+            "blobhash" | "blobbasefee" => EvmVersion::Cancun,
+            _ => EvmVersion::Istanbul,
+        };
+        if self < introduced {
+            return Some(format!(
+                "`{instruction}` is not available on EVM version `{self}`; it was introduced in `{introduced}`"
+            ));
+        }
+
+        match instruction {
+            // EIP-6780
+            "selfdestruct" if self >= EvmVersion::Cancun => Some(format!(
+                "`selfdestruct` is deprecated on EVM version `{self}`, and only deletes contracts that were created in the same transaction"
+            )),
+            // EIP-6049
+            "selfdestruct" if self >= EvmVersion::Shanghai => Some(format!(
+                "`selfdestruct` is deprecated on EVM version `{self}`"
+            )),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for EvmVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for EvmVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        EvmVersion::ALL
+            .into_iter()
+            .find(|version| version.as_str() == s)
+            .ok_or_else(|| {
+                let versions = EvmVersion::ALL.map(EvmVersion::as_str).join(", ");
+                format!("unsupported EVM version `{s}`. Supported versions: {versions}")
+            })
+    }
+}
+
+/// The instructions that ERC-4337 bans in the validation of a user operation
+/// by an account (rule OP-011 of ERC-7562), because their results may differ
+/// between validation and execution. `gas` is allowed, as it is only used for
+/// the gas argument of calls, and `balance` and `selfbalance` are banned as
+/// for an unstaked account.
+pub const USER_OP_VALIDATION_BANNED: [&str; 17] = [
+    "gasprice",
+    "gaslimit",
+    "prevrandao",
+    "timestamp",
+    "basefee",
+    "blockhash",
+    "number",
+    "coinbase",
+    "origin",
+    "balance",
+    "selfbalance",
+    "create",
+    "create2",
+    "selfdestruct",
+    "invalid",
+    "blobhash",
+    "blobbasefee",
+];
+
+/// A precompiled contract that `std::precompiles` has a binding for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Precompile {
+    EcRecover,
+    Sha2256,
+    Ripemd160,
+    Identity,
+    ModExp,
+    EcAdd,
+    EcMul,
+    EcPairing,
+    Blake2f,
+    /// EIP-4844
+    PointEvaluation,
+    /// RIP-7212, which isn't part of mainnet.
+    P256Verify,
+}
+
+impl Precompile {
+    pub const ALL: [Precompile; 11] = [
+        Precompile::EcRecover,
+        Precompile::Sha2256,
+        Precompile::Ripemd160,
+        Precompile::Identity,
+        Precompile::ModExp,
+        Precompile::EcAdd,
+        Precompile::EcMul,
+        Precompile::EcPairing,
+        Precompile::Blake2f,
+        Precompile::PointEvaluation,
+        Precompile::P256Verify,
+    ];
+
+    /// The name of the binding in `std::precompiles`, e.g. `"ec_recover"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Precompile::EcRecover => "ec_recover",
+            Precompile::Sha2256 => "sha2_256",
+            Precompile::Ripemd160 => "ripemd_160",
+            Precompile::Identity => "identity",
+            Precompile::ModExp => "mod_exp",
+            Precompile::EcAdd => "ec_add",
+            Precompile::EcMul => "ec_mul",
+            Precompile::EcPairing => "ec_pairing",
+            Precompile::Blake2f => "blake_2f",
+            Precompile::PointEvaluation => "point_evaluation",
+            Precompile::P256Verify => "p256_verify",
+        }
+    }
+
+    pub fn address(self) -> u64 {
+        match self {
+            Precompile::EcRecover => 0x01,
+            Precompile::Sha2256 => 0x02,
+            Precompile::Ripemd160 => 0x03,
+            Precompile::Identity => 0x04,
+            Precompile::ModExp => 0x05,
+            Precompile::EcAdd => 0x06,
+            Precompile::EcMul => 0x07,
+            Precompile::EcPairing => 0x08,
+            Precompile::Blake2f => 0x09,
+            Precompile::PointEvaluation => 0x0a,
+            Precompile::P256Verify => 0x100,
+        }
+    }
+
+    /// The first EVM version that has the precompile, if it's part of
+    /// mainnet.
+    pub fn introduced(self) -> Option<EvmVersion> {
+        match self {
+            Precompile::PointEvaluation => Some(EvmVersion::Cancun),
+            Precompile::P256Verify => None,
+            _ => Some(EvmVersion::Istanbul),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Precompile::ALL
+            .into_iter()
+            .find(|precompile| precompile.name() == name)
+    }
+}
+
+impl fmt::Display for Precompile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        for version in EvmVersion::ALL {
+            assert_eq!(version.as_str().parse::<EvmVersion>(), Ok(version));
+        }
+        assert!("byzantium".parse::<EvmVersion>().is_err());
+    }
+
+    #[test]
+    fn instruction_errors() {
+        assert_eq!(EvmVersion::London.instruction_error("basefee"), None);
+        assert!(EvmVersion::Berlin.instruction_error("basefee").is_some());
+        assert_eq!(EvmVersion::Paris.instruction_error("selfdestruct"), None);
+        assert!(EvmVersion::Shanghai
+            .instruction_error("selfdestruct")
+            .is_some());
+        assert!(EvmVersion::Cancun
+            .instruction_error("selfdestruct")
+            .is_some());
+        assert_eq!(EvmVersion::Cancun.instruction_error("sstore"), None);
+    }
+
+    #[test]
+    fn precompile_addresses() {
+        assert_eq!(Precompile::EcRecover.address(), 1);
+        assert_eq!(Precompile::Blake2f.address(), 9);
+        assert_eq!(Precompile::P256Verify.address(), 0x100);
+        assert_eq!(
+            Precompile::from_name("ec_pairing"),
+            Some(Precompile::EcPairing)
+        );
+        assert_eq!(Precompile::from_name("sha256"), None);
+    }
+}
@@ -1,12 +1,17 @@
+pub mod chain;
 pub mod db;
 pub mod diagnostics;
+pub mod edition;
+pub mod evm;
 pub mod files;
 pub mod numeric;
 pub mod panic;
+pub mod source_map;
 mod span;
 pub mod utils;
 
 pub use files::{File, FileKind, SourceFileId};
+pub use source_map::{LineCol, SourceLocation};
 pub use span::{Span, Spanned};
 
 #[macro_export]
@@ -1,5 +1,6 @@
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
 use std::panic;
+use tracing_error::{SpanTrace, SpanTraceStatus};
 
 const BUG_REPORT_URL: &str = "https://github.com/ethereum/fe/issues/new";
 type PanicCallback = dyn Fn(&panic::PanicInfo<'_>) + Sync + Send + 'static;
@@ -8,17 +9,53 @@ static DEFAULT_PANIC_HOOK: Lazy<Box<PanicCallback>> = Lazy::new(|| {
     panic::set_hook(Box::new(report_ice));
     hook
 });
+static ICE_CALLBACK: OnceCell<Box<PanicCallback>> = OnceCell::new();
 
 pub fn install_panic_hook() {
     Lazy::force(&DEFAULT_PANIC_HOOK);
 }
+
+/// Registers a callback that runs after an internal compiler error has been
+/// reported, e.g. to save the inputs that caused it.
+///
+/// Only the first registered callback is kept.
+pub fn set_ice_callback(callback: Box<PanicCallback>) {
+    let _ = ICE_CALLBACK.set(callback);
+}
+
+/// Returns the compiler phases and queries that were active when this was
+/// called, innermost first.
+///
+/// The stack is only available if the `tracing` subscriber includes a
+/// `tracing_error::ErrorLayer`.
+pub fn query_stack() -> Option<String> {
+    let trace = SpanTrace::capture();
+    if trace.status() == SpanTraceStatus::CAPTURED {
+        Some(trace.to_string())
+    } else {
+        None
+    }
+}
+
 fn report_ice(info: &panic::PanicInfo) {
     (*DEFAULT_PANIC_HOOK)(info);
 
+    eprintln!();
+    eprintln!("error: internal compiler error");
+    if let Some(stack) = query_stack() {
+        eprintln!();
+        eprintln!("query stack:");
+        eprintln!("{stack}");
+    }
+
     eprintln!();
     eprintln!("You've hit an internal compiler error. This is a bug in the Fe compiler.");
     eprintln!("Fe is still under heavy development, and isn't yet ready for production use.");
     eprintln!();
     eprintln!("If you would, please report this bug at the following URL:");
     eprintln!("  {BUG_REPORT_URL}");
+
+    if let Some(callback) = ICE_CALLBACK.get() {
+        callback(info);
+    }
 }
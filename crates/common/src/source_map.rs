@@ -0,0 +1,133 @@
+//! Conversion between byte offsets and line/column positions.
+//!
+//! Spans store byte offsets into a file. Anything that shows a position to a
+//! user (diagnostics, editors, debug info) should convert it with the
+//! functions here, so that every consumer agrees on what a position means.
+use std::fmt;
+use std::rc::Rc;
+
+use crate::db::SourceDb;
+use crate::files::{SourceFileId, Utf8PathBuf};
+use crate::Span;
+
+/// A zero-based line and column in a source file. The column counts
+/// characters, not bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineCol {
+    pub line: usize,
+    pub col: usize,
+}
+
+/// The resolved location of a [`Span`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceLocation {
+    pub file: SourceFileId,
+    pub path: Rc<Utf8PathBuf>,
+    pub start: LineCol,
+    pub end: LineCol,
+}
+
+impl fmt::Display for SourceLocation {
+    /// Formats the start of the location as `path:line:col`, with one-based
+    /// line and column numbers.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}",
+            self.path,
+            self.start.line + 1,
+            self.start.col + 1
+        )
+    }
+}
+
+impl SourceFileId {
+    /// Returns the line and column of `byte_index`. Offsets past the end of
+    /// the file are clamped to the end.
+    pub fn line_col(&self, db: &dyn SourceDb, byte_index: usize) -> LineCol {
+        let content = self.content(db);
+        let byte_index = floor_char_boundary(&content, byte_index);
+        let line = self.line_index(db, byte_index);
+        let line_start = db.file_line_starts(*self)[line];
+        LineCol {
+            line,
+            col: content[line_start..byte_index].chars().count(),
+        }
+    }
+
+    /// Returns the byte offset of `pos`, or `None` if the file has no such
+    /// position. A column at the end of a line is valid.
+    pub fn byte_index(&self, db: &dyn SourceDb, pos: LineCol) -> Option<usize> {
+        let range = self.line_range(db, pos.line)?;
+        let content = self.content(db);
+        let line = &content[range.clone()];
+        if pos.col == line.chars().count() {
+            return Some(range.end);
+        }
+        line.char_indices()
+            .nth(pos.col)
+            .map(|(offset, _)| range.start + offset)
+    }
+}
+
+impl Span {
+    /// Resolves the span to lines and columns. Returns `None` for spans that
+    /// don't point into a real file, e.g. those of compiler generated code.
+    pub fn location(&self, db: &dyn SourceDb) -> Option<SourceLocation> {
+        if self.is_dummy() || self.file_id.is_dummy() {
+            return None;
+        }
+
+        Some(SourceLocation {
+            file: self.file_id,
+            path: self.file_id.path(db),
+            start: self.file_id.line_col(db, self.start),
+            end: self.file_id.line_col(db, self.end),
+        })
+    }
+}
+
+fn floor_char_boundary(s: &str, index: usize) -> usize {
+    let mut index = index.min(s.len());
+    while !s.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::TestDb;
+
+    fn file(db: &mut TestDb, content: &str) -> SourceFileId {
+        SourceFileId::new_local(db, "test.fe", content.into())
+    }
+
+    #[test]
+    fn line_col_round_trip() {
+        let mut db = TestDb::default();
+        let content = "fn foo():\n  let ä = \"ö\"\n";
+        let file = file(&mut db, content);
+
+        for (index, _) in content.char_indices() {
+            let pos = file.line_col(&db, index);
+            assert_eq!(file.byte_index(&db, pos), Some(index));
+        }
+        assert_eq!(file.line_col(&db, 0), LineCol { line: 0, col: 0 });
+        assert_eq!(file.line_col(&db, 18), LineCol { line: 1, col: 7 });
+    }
+
+    #[test]
+    fn span_location() {
+        let mut db = TestDb::default();
+        let file = file(&mut db, "contract Foo:\n  x: u256\n");
+
+        let location = Span::new(file, 16, 17).location(&db).unwrap();
+        assert_eq!(location.start, LineCol { line: 1, col: 2 });
+        assert_eq!(location.end, LineCol { line: 1, col: 3 });
+        assert_eq!(location.to_string(), "test.fe:2:3");
+
+        assert_eq!(Span::dummy().location(&db), None);
+    }
+}
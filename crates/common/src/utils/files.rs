@@ -1,4 +1,6 @@
 use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
 use std::{fs, path::Path};
 use toml::Table;
 
@@ -7,6 +9,10 @@ use path_clean::PathClean;
 use smol_str::SmolStr;
 use walkdir::WalkDir;
 
+use crate::chain::ChainProfile;
+use crate::edition::Edition;
+use crate::evm::{EvmVersion, Precompile};
+
 const FE_TOML: &str = "fe.toml";
 
 enum FileLoader {
@@ -89,6 +95,30 @@ impl BuildFiles {
         self.project_files[&self.root_project_path].mode
     }
 
+    /// The EVM version that the root project declares in its manifest.
+    pub fn root_project_evm_version(&self) -> Option<EvmVersion> {
+        self.project_files[&self.root_project_path].evm_version
+    }
+
+    /// Returns the profile of the chain called `name`, or of the chain that
+    /// the root project selects in its manifest if `name` is `None`.
+    /// Profiles declared in the manifest take precedence over built-in ones.
+    pub fn root_project_chain(&self, name: Option<&str>) -> Result<Option<ChainProfile>, String> {
+        let project = &self.project_files[&self.root_project_path];
+        let name = match name.or(project.chain.as_deref()) {
+            Some(name) => name,
+            None => return Ok(None),
+        };
+
+        project
+            .chains
+            .get(name)
+            .cloned()
+            .or_else(|| ChainProfile::builtin(name))
+            .map(Some)
+            .ok_or_else(|| unknown_chain_error(name, project.chains.keys()))
+    }
+
     /// Build files are loaded from the file system.
     pub fn load_fs(root_path: &str) -> Result<Self, String> {
         Self::load(&FileLoader::Fs, root_path)
@@ -138,6 +168,13 @@ pub struct ProjectFiles {
     pub mode: ProjectMode,
     pub dependencies: Vec<Dependency>,
     pub src: Vec<(String, String)>,
+    /// The edition of the language that the source files are written in.
+    pub edition: Edition,
+    pub evm_version: Option<EvmVersion>,
+    /// The chain selected by the `chain` key of the manifest.
+    pub chain: Option<SmolStr>,
+    /// The chain profiles declared in the manifest.
+    pub chains: BTreeMap<SmolStr, ChainProfile>,
 }
 
 impl ProjectFiles {
@@ -150,6 +187,35 @@ impl ProjectFiles {
         let manifest = Manifest::load(loader, &manifest_path)?;
         let name = manifest.name;
         let version = manifest.version;
+        let edition = manifest
+            .edition
+            .as_deref()
+            .map(Edition::from_str)
+            .transpose()
+            .map_err(|err| format!("Invalid `edition` in {manifest_path}.\n{err}"))?
+            .unwrap_or(Edition::LATEST);
+        let evm_version = manifest
+            .evm_version
+            .as_deref()
+            .map(EvmVersion::from_str)
+            .transpose()
+            .map_err(|err| format!("Invalid `evm-version` in {manifest_path}.\n{err}"))?;
+        let chains = manifest
+            .chains
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, chain)| Ok((name.clone(), chain.profile(name)?)))
+            .collect::<Result<BTreeMap<_, _>, String>>()
+            .map_err(|err| format!("Invalid chain profile in {manifest_path}.\n{err}"))?;
+        let chain = manifest.chain;
+        if let Some(name) = &chain {
+            if !chains.contains_key(name) && ChainProfile::builtin(name).is_none() {
+                return Err(format!(
+                    "Invalid `chain` in {manifest_path}.\n{}",
+                    unknown_chain_error(name, chains.keys())
+                ));
+            }
+        }
         let dependencies = if let Some(dependencies) = &manifest.dependencies {
             dependencies
                 .iter()
@@ -186,6 +252,10 @@ impl ProjectFiles {
             mode,
             dependencies,
             src,
+            edition,
+            evm_version,
+            chain,
+            chains,
         })
     }
 }
@@ -281,6 +351,11 @@ struct Manifest {
     pub name: SmolStr,
     pub version: SmolStr,
     dependencies: Option<Table>,
+    edition: Option<SmolStr>,
+    #[serde(rename = "evm-version")]
+    evm_version: Option<SmolStr>,
+    chain: Option<SmolStr>,
+    chains: Option<BTreeMap<SmolStr, ChainManifest>>,
 }
 
 impl Manifest {
@@ -295,6 +370,71 @@ impl Manifest {
     }
 }
 
+/// A `[chains.<name>]` table of a manifest. Omitted keys are taken from the
+/// built-in profile of the chain, if there is one.
+#[derive(Deserialize)]
+struct ChainManifest {
+    #[serde(rename = "evm-version")]
+    evm_version: Option<SmolStr>,
+    precompiles: Option<Vec<SmolStr>>,
+    #[serde(rename = "gas-hints")]
+    gas_hints: Option<BTreeMap<SmolStr, u64>>,
+}
+
+impl ChainManifest {
+    fn profile(self, name: SmolStr) -> Result<ChainProfile, String> {
+        let builtin = ChainProfile::builtin(&name);
+        let evm_version = match (self.evm_version, &builtin) {
+            (Some(version), _) => version.parse()?,
+            (None, Some(builtin)) => builtin.evm_version,
+            (None, None) => return Err(format!("chain `{name}` has no `evm-version`")),
+        };
+        let precompiles = match self.precompiles {
+            Some(precompiles) => precompiles
+                .iter()
+                .map(|precompile| {
+                    Precompile::from_name(precompile).ok_or_else(|| {
+                        format!("unknown precompile `{precompile}` in chain `{name}`")
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            None => builtin.as_ref().map_or_else(
+                || {
+                    Precompile::ALL
+                        .into_iter()
+                        .filter(|precompile| {
+                            precompile
+                                .introduced()
+                                .is_some_and(|introduced| introduced <= evm_version)
+                        })
+                        .collect()
+                },
+                |builtin| builtin.precompiles.clone(),
+            ),
+        };
+        let mut gas_hints = builtin.map(|builtin| builtin.gas_hints).unwrap_or_default();
+        gas_hints.extend(self.gas_hints.unwrap_or_default());
+
+        Ok(ChainProfile {
+            name,
+            evm_version,
+            precompiles,
+            gas_hints,
+        })
+    }
+}
+
+fn unknown_chain_error<'a>(name: &str, declared: impl Iterator<Item = &'a SmolStr>) -> String {
+    let chains = ChainProfile::BUILTIN
+        .into_iter()
+        .chain(declared.map(SmolStr::as_str))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("unknown chain `{name}`. Known chains: {chains}")
+}
+
 /// Returns the root path of the current Fe project
 pub fn get_project_root() -> Option<String> {
     let current_dir = std::env::current_dir().expect("Unable to get current directory");
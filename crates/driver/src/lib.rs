@@ -1,17 +1,29 @@
 #![allow(unused_imports, dead_code)]
 
+use fe_abi::conformance::{AbiEntry, AbiMismatch};
 use fe_abi::event::AbiEvent;
+use fe_abi::public_api::{ApiChange, PublicApi, PublicItem};
+use fe_abi::storage::{StorageLayout, StorageLayoutChange};
 use fe_abi::types::{AbiTupleField, AbiType};
 pub use fe_codegen::db::{CodegenDb, Db};
 
-use fe_analyzer::namespace::items::{ContractId, FunctionId, IngotId, IngotMode, ModuleId};
-use fe_common::diagnostics::Diagnostic;
+use fe_analyzer::builtins::Intrinsic;
+use fe_analyzer::context::CallType;
+use fe_analyzer::display::Displayable;
+use fe_analyzer::namespace::items::{
+    ContractId, EnumVariantKind, FunctionId, FunctionSigId, ImplId, IngotId, IngotMode, Item,
+    ModuleId, StructId, TypeDef,
+};
+use fe_analyzer::namespace::types::{Type, TypeId};
+use fe_common::chain::ChainProfile;
+use fe_common::diagnostics::{sort_diagnostics, Diagnostic, Label, Severity};
+use fe_common::evm::{EvmVersion, Precompile, USER_OP_VALIDATION_BANNED};
 use fe_common::files::FileKind;
-use fe_common::{db::Upcast, utils::files::BuildFiles};
+use fe_common::{db::Upcast, utils::files::BuildFiles, Span};
 use fe_parser::ast::SmolStr;
 use fe_test_runner::ethabi::{Event, EventParam, ParamType};
-use fe_test_runner::TestSink;
-use indexmap::{indexmap, IndexMap};
+use fe_test_runner::{TestEnv, TestSink};
+use indexmap::{indexmap, IndexMap, IndexSet};
 use serde_json::Value;
 use std::fmt::Display;
 
@@ -20,17 +32,32 @@ pub struct CompiledModule {
     pub src_ast: String,
     pub lowered_ast: String,
     pub contracts: IndexMap<String, CompiledContract>,
+    /// The compiler settings that affect the emitted bytecode, as JSON.
+    pub json_metadata: String,
+    /// The items of the ingot and the references between them, as JSON.
+    pub json_symbol_graph: String,
 }
 
 /// The artifacts of a compiled contract.
 pub struct CompiledContract {
     pub json_abi: String,
+    pub json_storage_layout: String,
+    pub json_selectors: String,
+    /// TypeScript bindings generated from the ABI.
+    pub ts_bindings: String,
+    /// NatSpec documentation for users, in the `userdoc` format of solc.
+    pub json_userdoc: String,
+    /// NatSpec documentation for developers, in the `devdoc` format of solc.
+    pub json_devdoc: String,
     pub yul: String,
     pub origin: ContractId,
     #[cfg(feature = "solc-backend")]
     pub bytecode: String,
     #[cfg(feature = "solc-backend")]
     pub runtime_bytecode: String,
+    /// The disassembled runtime bytecode, annotated with function names.
+    #[cfg(feature = "solc-backend")]
+    pub runtime_asm: String,
 }
 
 #[cfg(feature = "solc-backend")]
@@ -52,8 +79,12 @@ impl CompiledTest {
     }
 
     pub fn execute(&self, sink: &mut TestSink) -> bool {
+        self.execute_in(&TestEnv::default(), sink)
+    }
+
+    pub fn execute_in(&self, env: &TestEnv, sink: &mut TestSink) -> bool {
         let events = map_abi_events(&self.events);
-        fe_test_runner::execute(&self.name, &events, &self.bytecode, sink)
+        fe_test_runner::execute_in(&self.name, &events, &self.bytecode, env, sink)
     }
 }
 
@@ -105,8 +136,9 @@ fn map_abi_types(fields: &[AbiTupleField]) -> Vec<ParamType> {
 pub struct CompileError(pub Vec<Diagnostic>);
 
 pub fn check_single_file(db: &mut Db, path: &str, src: &str) -> Vec<Diagnostic> {
+    let _span = tracing::info_span!("check_single_file", file = path).entered();
     let module = ModuleId::new_standalone(db, path, src);
-    module.diagnostics(db)
+    analyze_module(db, module)
 }
 
 pub fn compile_single_file(
@@ -117,8 +149,9 @@ pub fn compile_single_file(
     with_runtime_bytecode: bool,
     optimize: bool,
 ) -> Result<CompiledModule, CompileError> {
+    let _span = tracing::info_span!("compile_single_file", file = path).entered();
     let module = ModuleId::new_standalone(db, path, src);
-    let diags = module.diagnostics(db);
+    let diags = analyze_module(db, module);
 
     if diags.is_empty() {
         compile_module(db, module, with_bytecode, with_runtime_bytecode, optimize)
@@ -134,8 +167,9 @@ pub fn compile_single_file_tests(
     src: &str,
     optimize: bool,
 ) -> Result<(SmolStr, Vec<CompiledTest>), CompileError> {
+    let _span = tracing::info_span!("compile_single_file_tests", file = path).entered();
     let module = ModuleId::new_standalone(db, path, src);
-    let diags = module.diagnostics(db);
+    let diags = analyze_module(db, module);
 
     if diags.is_empty() {
         Ok((module.name(db), compile_module_tests(db, module, optimize)))
@@ -147,11 +181,10 @@ pub fn compile_single_file_tests(
 // Run analysis with ingot
 // Return vector error,waring...
 pub fn check_ingot(db: &mut Db, build_files: &BuildFiles) -> Vec<Diagnostic> {
+    let _span =
+        tracing::info_span!("check_ingot", ingot = %build_files.root_project_path).entered();
     let ingot = IngotId::from_build_files(db, build_files);
-
-    let mut diags = ingot.diagnostics(db);
-    ingot.sink_external_ingot_diagnostics(db, &mut diags);
-    diags
+    analyze_ingot(db, ingot)
 }
 
 /// Compiles the main module of a project.
@@ -165,10 +198,11 @@ pub fn compile_ingot(
     with_runtime_bytecode: bool,
     optimize: bool,
 ) -> Result<CompiledModule, CompileError> {
+    let _span =
+        tracing::info_span!("compile_ingot", ingot = %build_files.root_project_path).entered();
     let ingot = IngotId::from_build_files(db, build_files);
 
-    let mut diags = ingot.diagnostics(db);
-    ingot.sink_external_ingot_diagnostics(db, &mut diags);
+    let diags = analyze_ingot(db, ingot);
     if !diags.is_empty() {
         return Err(CompileError(diags));
     }
@@ -190,10 +224,11 @@ pub fn compile_ingot_tests(
     build_files: &BuildFiles,
     optimize: bool,
 ) -> Result<Vec<(SmolStr, Vec<CompiledTest>)>, CompileError> {
+    let _span = tracing::info_span!("compile_ingot_tests", ingot = %build_files.root_project_path)
+        .entered();
     let ingot = IngotId::from_build_files(db, build_files);
 
-    let mut diags = ingot.diagnostics(db);
-    ingot.sink_external_ingot_diagnostics(db, &mut diags);
+    let diags = analyze_ingot(db, ingot);
     if !diags.is_empty() {
         return Err(CompileError(diags));
     }
@@ -211,6 +246,343 @@ pub fn compile_ingot_tests(
     }
 }
 
+fn analyze_module(db: &mut Db, module: ModuleId) -> Vec<Diagnostic> {
+    let _span = tracing::info_span!("analysis", module = %module.name(db)).entered();
+    let mut diags = module.diagnostics(db);
+    sort_diagnostics(db, &mut diags);
+    tracing::debug!(diagnostics = diags.len(), "analysis finished");
+    diags
+}
+
+fn analyze_ingot(db: &mut Db, ingot: IngotId) -> Vec<Diagnostic> {
+    let _span = tracing::info_span!("analysis", ingot = %ingot.name(db)).entered();
+    let mut diags = ingot.diagnostics(db);
+    ingot.sink_external_ingot_diagnostics(db, &mut diags);
+    sort_diagnostics(db, &mut diags);
+    tracing::debug!(diagnostics = diags.len(), "analysis finished");
+    diags
+}
+
+/// Compares two storage layouts emitted by `fe build --emit=storage-layout`
+/// and returns the changes that would break the storage of a contract that is
+/// upgraded from `old` to `new`.
+pub fn storage_layout_breaking_changes(
+    old: &str,
+    new: &str,
+) -> Result<Vec<StorageLayoutChange>, String> {
+    let parse = |json: &str| {
+        serde_json::from_str::<StorageLayout>(json)
+            .map_err(|err| format!("invalid storage layout: {err}"))
+    };
+    Ok(parse(old)?.breaking_changes(&parse(new)?))
+}
+
+/// Returns the public API of a single file module as JSON.
+pub fn public_api_single_file(db: &mut Db, path: &str, src: &str) -> Result<String, CompileError> {
+    let module = ModuleId::new_standalone(db, path, src);
+    let diags = analyze_module(db, module);
+    if !diags.is_empty() {
+        return Err(CompileError(diags));
+    }
+
+    let api = public_api(db, module.name(db).as_str(), &[module]);
+    Ok(serde_json::to_string_pretty(&api).unwrap())
+}
+
+/// Returns the public API of an ingot as JSON.
+pub fn public_api_ingot(db: &mut Db, build_files: &BuildFiles) -> Result<String, CompileError> {
+    let ingot = IngotId::from_build_files(db, build_files);
+    let diags = analyze_ingot(db, ingot);
+    if !diags.is_empty() {
+        return Err(CompileError(diags));
+    }
+
+    let api = public_api(db, ingot.name(db).as_str(), &ingot.all_modules(db));
+    Ok(serde_json::to_string_pretty(&api).unwrap())
+}
+
+/// Compares two public APIs emitted by `fe public-api` and returns the
+/// changes that may break code that depends on `old`.
+pub fn public_api_breaking_changes(old: &str, new: &str) -> Result<Vec<ApiChange>, String> {
+    let parse = |json: &str| {
+        serde_json::from_str::<PublicApi>(json).map_err(|err| format!("invalid public API: {err}"))
+    };
+    Ok(parse(old)?.breaking_changes(&parse(new)?))
+}
+
+/// Parses a JSON ABI, as emitted by `fe build` or solc, for use with
+/// [`abi_conformance_single_file`] and [`abi_conformance_ingot`].
+pub fn parse_json_abi(json: &str) -> Result<Vec<AbiEntry>, String> {
+    serde_json::from_str(json).map_err(|err| format!("invalid ABI: {err}"))
+}
+
+/// Returns the ways in which each contract of a single file module fails to
+/// implement the given ABI, keyed by contract name.
+pub fn abi_conformance_single_file(
+    db: &mut Db,
+    path: &str,
+    src: &str,
+    abi: &[AbiEntry],
+) -> Result<IndexMap<String, Vec<AbiMismatch>>, CompileError> {
+    let module = ModuleId::new_standalone(db, path, src);
+    let diags = analyze_module(db, module);
+    if !diags.is_empty() {
+        return Err(CompileError(diags));
+    }
+
+    Ok(abi_conformance(db, module, abi))
+}
+
+/// Returns the ways in which each contract of the root module of an ingot
+/// fails to implement the given ABI, keyed by contract name.
+pub fn abi_conformance_ingot(
+    db: &mut Db,
+    build_files: &BuildFiles,
+    abi: &[AbiEntry],
+) -> Result<IndexMap<String, Vec<AbiMismatch>>, CompileError> {
+    let ingot = IngotId::from_build_files(db, build_files);
+    let diags = analyze_ingot(db, ingot);
+    if !diags.is_empty() {
+        return Err(CompileError(diags));
+    }
+
+    let main_module = ingot
+        .root_module(db)
+        .expect("missing root module, with no diagnostic");
+    Ok(abi_conformance(db, main_module, abi))
+}
+
+fn abi_conformance(
+    db: &Db,
+    module: ModuleId,
+    abi: &[AbiEntry],
+) -> IndexMap<String, Vec<AbiMismatch>> {
+    module
+        .all_contracts(db.upcast())
+        .iter()
+        .map(|contract| {
+            let name = contract.data(db.upcast()).name.to_string();
+            let mismatches = db
+                .codegen_abi_contract(*contract)
+                .conformance_mismatches(&db.codegen_abi_contract_errors(*contract), abi);
+            (name, mismatches)
+        })
+        .collect()
+}
+
+fn public_api(db: &Db, ingot_name: &str, modules: &[ModuleId]) -> PublicApi {
+    let mut items = vec![];
+    for module in modules {
+        for item in module.all_items(db).iter() {
+            if item.is_public(db) {
+                public_items(db, *item, &mut items);
+            }
+        }
+
+        let module_path = item_path(db, Item::Module(*module));
+        for impl_ in module.all_impls(db).iter() {
+            if let Some(signature) = impl_signature(db, *impl_) {
+                let path = if module_path.is_empty() {
+                    signature.clone()
+                } else {
+                    format!("{module_path}::{signature}")
+                };
+                items.push(public_item(path, "impl", signature));
+            }
+        }
+    }
+
+    PublicApi::new(ingot_name.into(), items)
+}
+
+fn public_items(db: &Db, item: Item, items: &mut Vec<PublicItem>) {
+    let path = item_path(db, item);
+    match item {
+        Item::Function(function) if !function.is_test(db) => items.push(public_item(
+            path,
+            "fn",
+            function_signature(db, function.sig(db)),
+        )),
+        Item::Constant(constant) => {
+            let signature = format!(
+                "const {}: {}",
+                constant.name(db),
+                constant.typ(db).unwrap().display(db)
+            );
+            items.push(public_item(path, "const", signature))
+        }
+        Item::Trait(trait_) => {
+            let functions = trait_
+                .all_functions(db)
+                .iter()
+                .map(|sig| format!("{}; ", function_signature(db, *sig)))
+                .collect::<String>();
+            let signature = format!("trait {} {{ {functions}}}", trait_.name(db));
+            items.push(public_item(path, "trait", signature))
+        }
+        Item::Type(TypeDef::Alias(alias)) => {
+            let signature = format!(
+                "type {} = {}",
+                alias.name(db),
+                alias.type_id(db).unwrap().display(db)
+            );
+            items.push(public_item(path, "type", signature))
+        }
+        Item::Type(TypeDef::Struct(struct_)) => {
+            // Users can only construct a struct if all of its fields are public,
+            // in which case adding a field breaks them.
+            let signature = if struct_.has_private_field(db) {
+                format!("struct {} {{ .. }}", struct_.name(db))
+            } else {
+                let fields = struct_.fields(db).keys().cloned().collect::<Vec<_>>();
+                format!("struct {} {{ {} }}", struct_.name(db), fields.join(", "))
+            };
+            items.push(public_item(path.clone(), "struct", signature));
+
+            for (name, field) in struct_.fields(db).iter() {
+                if field.is_public(db) {
+                    let signature = format!("{name}: {}", field.typ(db).unwrap().display(db));
+                    items.push(public_item(format!("{path}::{name}"), "field", signature));
+                }
+            }
+            public_functions(db, &struct_.all_functions(db), items);
+        }
+        Item::Type(TypeDef::Enum(enum_)) => {
+            let variants = enum_
+                .variants(db)
+                .values()
+                .map(|variant| match variant.kind(db).unwrap() {
+                    EnumVariantKind::Unit => variant.name(db).to_string(),
+                    kind => format!("{}{}", variant.name(db), kind.display(db)),
+                })
+                .collect::<Vec<_>>();
+            let signature = format!("enum {} {{ {} }}", enum_.name(db), variants.join(", "));
+            items.push(public_item(path, "enum", signature));
+            public_functions(db, &enum_.all_functions(db), items);
+        }
+        Item::Type(TypeDef::Contract(contract)) => {
+            items.push(public_item(
+                path,
+                "contract",
+                format!("contract {}", contract.name(db)),
+            ));
+            if let Some(init) = contract.init_function(db) {
+                public_functions(db, &[init], items);
+            }
+            let functions = contract
+                .public_functions(db)
+                .values()
+                .copied()
+                .collect::<Vec<_>>();
+            public_functions(db, &functions, items);
+        }
+        _ => {}
+    }
+}
+
+fn public_functions(db: &Db, functions: &[FunctionId], items: &mut Vec<PublicItem>) {
+    for function in functions {
+        if function.is_public(db) || function.is_constructor(db) {
+            let signature = function_signature(db, function.sig(db));
+            items.push(public_item(
+                item_path(db, Item::Function(*function)),
+                "fn",
+                signature,
+            ));
+        }
+    }
+}
+
+fn public_item(path: String, kind: &str, signature: String) -> PublicItem {
+    PublicItem {
+        path,
+        kind: kind.into(),
+        signature,
+    }
+}
+
+/// Returns the path of the item relative to the root module of its ingot.
+fn item_path(db: &Db, item: Item) -> String {
+    item.path(db)[1..].join("::")
+}
+
+fn function_signature(db: &Db, sig: FunctionSigId) -> String {
+    let signature = sig.signature(db);
+    let mut params = vec![];
+    if let Some(self_decl) = signature.self_decl {
+        params.push(
+            if self_decl.is_mut() {
+                "mut self"
+            } else {
+                "self"
+            }
+            .to_string(),
+        );
+    }
+    for param in &signature.params {
+        let typ = param.typ.as_ref().unwrap().display(db);
+        params.push(match param.label() {
+            Some(label) if label == param.name => format!("{}: {typ}", param.name),
+            Some(label) => format!("{label} {}: {typ}", param.name),
+            None => format!("_ {}: {typ}", param.name),
+        });
+    }
+
+    let generic_params = sig.generic_params(db);
+    let generics = if generic_params.is_empty() {
+        String::new()
+    } else {
+        let params = generic_params
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        format!("<{}>", params.join(", "))
+    };
+
+    let return_type = signature.return_type.as_ref().unwrap();
+    let return_type = if return_type.typ(db).is_unit() {
+        String::new()
+    } else {
+        format!(" -> {}", return_type.display(db))
+    };
+
+    format!(
+        "{}fn {}{generics}({}){return_type}",
+        if sig.unsafe_span(db).is_some() {
+            "unsafe "
+        } else {
+            ""
+        },
+        sig.name(db),
+        params.join(", ")
+    )
+}
+
+/// Returns the signature of an impl of a public trait for a type that can be
+/// named outside of the ingot.
+fn impl_signature(db: &Db, impl_: ImplId) -> Option<String> {
+    let trait_ = impl_.trait_id(db);
+    let receiver = impl_.receiver(db);
+    if !trait_.is_public(db) || !is_public_type(db, receiver) {
+        return None;
+    }
+
+    Some(format!(
+        "impl {} for {}",
+        trait_.name(db),
+        receiver.display(db)
+    ))
+}
+
+fn is_public_type(db: &Db, typ: TypeId) -> bool {
+    match typ.typ(db) {
+        Type::Struct(struct_) => struct_.is_public(db),
+        Type::Enum(enum_) => enum_.is_public(db),
+        Type::Contract(contract) | Type::SelfContract(contract) => contract.is_public(db),
+        _ => true,
+    }
+}
+
 /// Returns graphviz string.
 // TODO: This is temporary function for debugging.
 pub fn dump_mir_single_file(db: &mut Db, path: &str, src: &str) -> Result<String, CompileError> {
@@ -228,10 +600,22 @@ pub fn dump_mir_single_file(db: &mut Db, path: &str, src: &str) -> Result<String
 
 #[cfg(feature = "solc-backend")]
 fn compile_test(db: &mut Db, test: FunctionId, optimize: bool) -> CompiledTest {
-    let yul_test = fe_codegen::yul::isel::lower_test(db, test)
-        .to_string()
-        .replace('"', "\\\"");
-    let bytecode = compile_to_evm("test", &yul_test, optimize, false).bytecode;
+    let _span = tracing::info_span!("compile_test", item = %test.name(db)).entered();
+    let yul_test = tracing::info_span!("codegen").in_scope(|| {
+        fe_codegen::yul::isel::lower_test(db, test)
+            .to_string()
+            .replace('"', "\\\"")
+    });
+    let optimizer_steps = db.codegen_yul_optimizer_steps();
+    let bytecode = compile_to_evm(
+        "test",
+        &yul_test,
+        optimize,
+        optimizer_steps.as_deref(),
+        db.codegen_evm_version(),
+        false,
+    )
+    .bytecode;
     let events = db.codegen_abi_module_events(test.module(db));
     CompiledTest::new(test.name(db), events, bytecode)
 }
@@ -253,24 +637,50 @@ fn compile_module(
     with_runtime_bytecode: bool,
     optimize: bool,
 ) -> Result<CompiledModule, CompileError> {
+    let _span = tracing::info_span!("compile_module", module = %module_id.name(db)).entered();
+    check_target(db, module_id)?;
+    check_user_op_validation(db, module_id)?;
+    check_abi_names(db, module_id)?;
     let mut contracts = IndexMap::default();
 
     for contract in module_id.all_contracts(db.upcast()) {
         let name = &contract.data(db.upcast()).name;
+        let _span = tracing::info_span!("contract", item = %name).entered();
         let abi = db.codegen_abi_contract(contract);
+        let storage_layout = db.codegen_contract_storage_layout(contract);
+        let errors = db.codegen_abi_contract_errors(contract);
+        let selectors = abi.selectors(&errors);
+        let ts_bindings = abi.typescript_bindings(name, &errors);
+        let natspec = db.codegen_abi_contract_natspec(contract);
         let yul_contract = compile_to_yul(db, contract);
 
-        let (bytecode, runtime_bytecode) = if with_bytecode || with_runtime_bytecode {
+        let (bytecode, runtime_bytecode, runtime_asm) = if with_bytecode || with_runtime_bytecode {
             let deployable_name = db.codegen_contract_deployer_symbol_name(contract);
+            let optimizer_steps = db.codegen_yul_optimizer_steps();
             let bytecode = compile_to_evm(
                 deployable_name.as_str(),
                 &yul_contract,
                 optimize,
+                optimizer_steps.as_deref(),
+                db.codegen_evm_version(),
                 with_runtime_bytecode,
             );
-            (bytecode.bytecode, bytecode.runtime_bytecode)
+            if let Some((budget, span)) = contract.code_size_budget(db.upcast()) {
+                check_code_size_budget(name, &bytecode, budget, span)?;
+            }
+            let runtime_asm = if with_runtime_bytecode {
+                let labels = bytecode
+                    .runtime_function_entries
+                    .iter()
+                    .map(|(offset, name)| (*offset, format!("function {name}")))
+                    .collect();
+                fe_yulc::asm::disassemble(&bytecode.runtime_bytecode, &labels)
+            } else {
+                "".to_string()
+            };
+            (bytecode.bytecode, bytecode.runtime_bytecode, runtime_asm)
         } else {
-            ("".to_string(), "".to_string())
+            ("".to_string(), "".to_string(), "".to_string())
         };
 
         contracts.insert(
@@ -278,10 +688,16 @@ fn compile_module(
             // Maybe put the ContractID here so we can trace it back to the source file
             CompiledContract {
                 json_abi: serde_json::to_string_pretty(&abi).unwrap(),
+                json_storage_layout: serde_json::to_string_pretty(&storage_layout).unwrap(),
+                json_selectors: serde_json::to_string_pretty(&selectors).unwrap(),
+                ts_bindings,
+                json_userdoc: serde_json::to_string_pretty(&natspec.userdoc).unwrap(),
+                json_devdoc: serde_json::to_string_pretty(&natspec.devdoc).unwrap(),
                 yul: yul_contract,
                 origin: contract,
                 bytecode,
                 runtime_bytecode,
+                runtime_asm,
             },
         );
     }
@@ -290,6 +706,8 @@ fn compile_module(
         src_ast: format!("{:#?}", module_id.ast(db)),
         lowered_ast: format!("{:#?}", module_id.ast(db)),
         contracts,
+        json_metadata: build_metadata(db, optimize),
+        json_symbol_graph: symbol_graph(db, module_id.ingot(db)),
     })
 }
 
@@ -299,18 +717,33 @@ fn compile_module(
     module_id: ModuleId,
     _with_bytecode: bool,
     _with_runtime_bytecode: bool,
-    _optimize: bool,
+    optimize: bool,
 ) -> Result<CompiledModule, CompileError> {
+    let _span = tracing::info_span!("compile_module", module = %module_id.name(db)).entered();
+    check_target(db, module_id)?;
+    check_user_op_validation(db, module_id)?;
+    check_abi_names(db, module_id)?;
     let mut contracts = IndexMap::default();
     for contract in module_id.all_contracts(db.upcast()) {
         let name = &contract.data(db.upcast()).name;
+        let _span = tracing::info_span!("contract", item = %name).entered();
         let abi = db.codegen_abi_contract(contract);
+        let storage_layout = db.codegen_contract_storage_layout(contract);
+        let errors = db.codegen_abi_contract_errors(contract);
+        let selectors = abi.selectors(&errors);
+        let ts_bindings = abi.typescript_bindings(name, &errors);
+        let natspec = db.codegen_abi_contract_natspec(contract);
         let yul_contract = compile_to_yul(db, contract);
 
         contracts.insert(
             name.to_string(),
             CompiledContract {
                 json_abi: serde_json::to_string_pretty(&abi).unwrap(),
+                json_storage_layout: serde_json::to_string_pretty(&storage_layout).unwrap(),
+                json_selectors: serde_json::to_string_pretty(&selectors).unwrap(),
+                ts_bindings,
+                json_userdoc: serde_json::to_string_pretty(&natspec.userdoc).unwrap(),
+                json_devdoc: serde_json::to_string_pretty(&natspec.devdoc).unwrap(),
                 yul: yul_contract,
                 origin: contract,
             },
@@ -321,22 +754,630 @@ fn compile_module(
         src_ast: format!("{:#?}", module_id.ast(db)),
         lowered_ast: format!("{:#?}", module_id.ast(db)),
         contracts,
+        json_metadata: build_metadata(db, optimize),
+        json_symbol_graph: symbol_graph(db, module_id.ingot(db)),
     })
 }
 
+/// Reports the calls in the contracts of the module that the target can't
+/// execute: calls that lower to instructions that are unavailable or
+/// deprecated on the target EVM version, and calls of precompiles that the
+/// target chain doesn't have. Calls of std functions are reported if the std
+/// function, or a function that it calls, does one of these.
+fn check_target(db: &Db, module_id: ModuleId) -> Result<(), CompileError> {
+    let evm_version = db.codegen_evm_version();
+    let chain = db.codegen_chain_profile();
+    if evm_version.is_none() && chain.is_none() {
+        return Ok(());
+    }
+
+    let mut diags = vec![];
+    let mut checked = IndexSet::new();
+    for contract in module_id.all_contracts(db.upcast()) {
+        let mut functions = contract.init_function(db).into_iter().collect::<Vec<_>>();
+        let graphs = functions
+            .iter()
+            .map(|init| init.dependency_graph(db))
+            .chain([contract.runtime_dependency_graph(db)])
+            .collect::<Vec<_>>();
+        functions.extend(graphs.iter().flat_map(|graph| {
+            graph.nodes().filter_map(|item| match item {
+                Item::Function(function) => Some(function),
+                _ => None,
+            })
+        }));
+
+        for function in functions {
+            if function.module(db).is_in_std(db) || !checked.insert(function) {
+                continue;
+            }
+
+            let body = function.body(db);
+            for (node_id, call) in body.calls.iter() {
+                let (intrinsics, precompiles, callee) = match call_target_features(db, call) {
+                    Some(features) => features,
+                    None => continue,
+                };
+
+                let uses = |feature: String| uses_label(db, callee, &feature);
+                let mut errors = vec![];
+                if let Some(evm_version) = evm_version {
+                    for intrinsic in intrinsics {
+                        let instruction = intrinsic.as_ref().trim_start_matches("__");
+                        if let Some(message) = evm_version.instruction_error(instruction) {
+                            errors
+                                .push((message, uses(format!("the `{instruction}` instruction"))));
+                        }
+                    }
+                }
+                if let Some(evm_version) = evm_version {
+                    for precompile in &precompiles {
+                        match precompile.introduced() {
+                            Some(introduced) if evm_version < introduced => errors.push((
+                                format!(
+                                    "the `{precompile}` precompile is not available on EVM version `{evm_version}`; it was introduced in `{introduced}`"
+                                ),
+                                uses(format!("the `{precompile}` precompile")),
+                            )),
+                            _ => {}
+                        }
+                    }
+                }
+                if let Some(chain) = &chain {
+                    for precompile in precompiles {
+                        if !chain.has_precompile(precompile) {
+                            errors.push((
+                                format!(
+                                    "the `{precompile}` precompile is not available on chain `{}`",
+                                    chain.name
+                                ),
+                                uses(format!("the `{precompile}` precompile")),
+                            ));
+                        }
+                    }
+                }
+
+                for (message, label) in errors {
+                    let mut diag = Diagnostic::error(message);
+                    if let Some(span) = body.spans.get(node_id) {
+                        diag.labels.push(Label::primary(*span, label));
+                    }
+                    diags.push(diag);
+                }
+            }
+        }
+    }
+
+    if diags.is_empty() {
+        Ok(())
+    } else {
+        sort_diagnostics(db, &mut diags);
+        Err(CompileError(diags))
+    }
+}
+
+/// Reports the instructions that ERC-4337 forbids in the validation of user
+/// operations, if they are used by a contract function that takes a
+/// `std::aa::ValidateUserOp`, or by the functions that it calls.
+fn check_user_op_validation(db: &Db, module_id: ModuleId) -> Result<(), CompileError> {
+    let mut diags = vec![];
+    for contract in module_id.all_contracts(db.upcast()) {
+        for validation_fn in contract.all_functions(db).iter() {
+            if !is_user_op_validation_fn(db, *validation_fn) {
+                continue;
+            }
+
+            let graph = validation_fn.dependency_graph(db);
+            let functions = [*validation_fn]
+                .into_iter()
+                .chain(graph.nodes().filter_map(|item| match item {
+                    Item::Function(function) => Some(function),
+                    _ => None,
+                }))
+                .filter(|function| !function.module(db).is_in_std(db))
+                .collect::<IndexSet<_>>();
+
+            for function in functions {
+                let body = function.body(db);
+                for (node_id, call) in body.calls.iter() {
+                    let (intrinsics, _, callee) = match call_target_features(db, call) {
+                        Some(features) => features,
+                        None => continue,
+                    };
+
+                    for instruction in intrinsics
+                        .iter()
+                        .map(|intrinsic| intrinsic.as_ref().trim_start_matches("__"))
+                        .collect::<IndexSet<_>>()
+                    {
+                        if !USER_OP_VALIDATION_BANNED.contains(&instruction) {
+                            continue;
+                        }
+
+                        let mut diag = Diagnostic::error(format!(
+                            "the `{instruction}` instruction is not allowed in user operation validation"
+                        ));
+                        if let Some(span) = body.spans.get(node_id) {
+                            diag.labels.push(Label::primary(
+                                *span,
+                                uses_label(db, callee, &format!("the `{instruction}` instruction")),
+                            ));
+                        }
+                        diag.labels.push(Label::secondary(
+                            validation_fn.name_span(db),
+                            format!("`{}` validates user operations", validation_fn.name(db)),
+                        ));
+                        diag.notes.push(
+                            "ERC-4337 bans instructions whose results may differ between the validation and the execution of an operation".into(),
+                        );
+                        diags.push(diag);
+                    }
+                }
+            }
+        }
+    }
+
+    if diags.is_empty() {
+        Ok(())
+    } else {
+        sort_diagnostics(db, &mut diags);
+        Err(CompileError(diags))
+    }
+}
+
+/// Reports the events, and the errors, that have the same name in the ABI of a
+/// contract in the module, e.g. because the contract emits events of the same
+/// name that are defined in different modules.
+fn check_abi_names(db: &Db, module_id: ModuleId) -> Result<(), CompileError> {
+    let mut diags = vec![];
+    for contract in module_id.all_contracts(db.upcast()) {
+        let kinds = [
+            ("event", db.codegen_contract_event_types(contract)),
+            ("error", db.codegen_contract_error_types(contract)),
+        ];
+        for (kind, types) in kinds {
+            let mut by_name = IndexMap::<SmolStr, Vec<StructId>>::default();
+            for ty in types.iter() {
+                if let Some(struct_) = ty.analyzer_ty(db).and_then(|ty| ty.as_struct(db)) {
+                    by_name
+                        .entry(struct_.abi_name(db))
+                        .or_default()
+                        .push(struct_);
+                }
+            }
+
+            for (name, structs) in by_name {
+                if structs.len() < 2 {
+                    continue;
+                }
+
+                let mut diag = Diagnostic::error(format!(
+                    "contract `{}` has more than one {kind} named `{name}` in its ABI",
+                    contract.name(db)
+                ));
+                for struct_ in structs {
+                    diag.labels.push(Label::primary(
+                        struct_.name_span(db),
+                        format!(
+                            "`{}::{}` is defined here",
+                            struct_.module(db).name(db),
+                            struct_.name(db)
+                        ),
+                    ));
+                }
+                diag.notes.push(format!(
+                    "Hint: give all but one of them another name in the ABI, e.g. `#abi_name(\"Other{name}\")`"
+                ));
+                diags.push(diag);
+            }
+        }
+    }
+
+    if diags.is_empty() {
+        Ok(())
+    } else {
+        sort_diagnostics(db, &mut diags);
+        Err(CompileError(diags))
+    }
+}
+
+/// Returns whether `function` validates ERC-4337 user operations, i.e. takes
+/// a `std::aa::ValidateUserOp`.
+fn is_user_op_validation_fn(db: &Db, function: FunctionId) -> bool {
+    function.signature(db).params.iter().any(|param| {
+        matches!(
+            param.typ.as_ref().map(|typ| typ.typ(db)),
+            Ok(Type::Struct(struct_))
+                if struct_.module(db).is_in_std(db)
+                    && struct_.module(db).name(db) == "aa"
+                    && struct_.name(db) == "ValidateUserOp"
+        )
+    })
+}
+
+/// Returns the intrinsics and precompiles that a call uses, if it calls an
+/// intrinsic or a std function, along with the called std function.
+fn call_target_features(
+    db: &Db,
+    call: &CallType,
+) -> Option<(Vec<Intrinsic>, Vec<Precompile>, Option<FunctionId>)> {
+    match call {
+        CallType::Intrinsic(intrinsic) => Some((vec![*intrinsic], vec![], None)),
+        CallType::Pure(callee)
+        | CallType::AssociatedFunction {
+            function: callee, ..
+        }
+        | CallType::ValueMethod { method: callee, .. }
+            if callee.module(db).is_in_std(db) =>
+        {
+            let (intrinsics, precompiles) = std_target_features(db, *callee);
+            Some((intrinsics, precompiles, Some(*callee)))
+        }
+        _ => None,
+    }
+}
+
+fn uses_label(db: &Db, callee: Option<FunctionId>, feature: &str) -> String {
+    match callee {
+        Some(callee) => format!("`{}` uses {feature}", callee.name(db)),
+        None => format!("uses {feature}"),
+    }
+}
+
+/// Returns the intrinsics and precompiles that are used by a std function and
+/// the functions that it calls.
+fn std_target_features(db: &Db, function: FunctionId) -> (Vec<Intrinsic>, Vec<Precompile>) {
+    let graph = function.dependency_graph(db);
+    let functions = [function]
+        .into_iter()
+        .chain(graph.nodes().filter_map(|item| match item {
+            Item::Function(function) => Some(function),
+            _ => None,
+        }))
+        .collect::<IndexSet<_>>();
+
+    let intrinsics = functions
+        .iter()
+        .flat_map(|function| {
+            function
+                .body(db)
+                .calls
+                .values()
+                .filter_map(|call| match call {
+                    CallType::Intrinsic(intrinsic) => Some(*intrinsic),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let precompiles = functions
+        .iter()
+        .filter(|function| {
+            function.module(db).is_in_std(db) && function.module(db).name(db) == "precompiles"
+        })
+        .filter_map(|function| Precompile::from_name(&function.name(db)))
+        .collect();
+    (intrinsics, precompiles)
+}
+
+/// Returns the compiler settings that affect the emitted bytecode, so that a
+/// build can be reproduced.
+fn build_metadata(db: &Db, optimize: bool) -> String {
+    let metadata = serde_json::json!({
+        "compiler": {
+            "name": "fe",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "settings": {
+            "optimizer": {
+                "enabled": optimize,
+                "yulOptimizerSteps": db.codegen_yul_optimizer_steps().as_deref(),
+            },
+            "calldataValidation": db.codegen_calldata_validation(),
+            "evmVersion": db.codegen_evm_version().map(EvmVersion::as_str),
+            "chain": db.codegen_chain_profile().map(|chain| chain_metadata(&chain)),
+        },
+    });
+    serde_json::to_string_pretty(&metadata).unwrap()
+}
+
+fn chain_metadata(chain: &ChainProfile) -> Value {
+    let precompiles = chain
+        .precompiles
+        .iter()
+        .map(|precompile| precompile.name())
+        .collect::<Vec<_>>();
+    let gas_hints = chain
+        .gas_hints
+        .iter()
+        .map(|(operation, gas)| (operation.to_string(), Value::from(*gas)))
+        .collect::<serde_json::Map<_, _>>();
+    serde_json::json!({
+        "name": chain.name.as_str(),
+        "evmVersion": chain.evm_version.as_str(),
+        "precompiles": precompiles,
+        "gasHints": gas_hints,
+    })
+}
+
+/// Returns a graph of the items of an ingot and the references between them,
+/// for tools that visualize or audit the structure of a project.
+///
+/// Edges are `call`s between functions, `type` uses of structs, enums and
+/// contracts, and the `implements` and `impl_for` relations of impl blocks.
+/// Items of other ingots that are referenced are included as nodes, with a
+/// path that starts with the name of their ingot.
+fn symbol_graph(db: &Db, ingot: IngotId) -> String {
+    let mut graph = SymbolGraph::default();
+    for module in ingot.all_modules(db).iter() {
+        for item in module.all_items(db).iter() {
+            graph.add_item(db, *item);
+        }
+        for impl_ in module.all_impls(db).iter() {
+            graph.add_item(db, Item::Impl(*impl_));
+        }
+    }
+
+    let symbol_id = |item: Item| match item.module(db) {
+        Some(module) if module.ingot(db) != ingot => {
+            format!("{}::{}", module.ingot(db).name(db), item_path(db, item))
+        }
+        _ => item_path(db, item),
+    };
+    let referenced = graph.edges.iter().map(|(_, to, _)| *to);
+    let nodes = graph
+        .items
+        .iter()
+        .copied()
+        .chain(referenced)
+        .collect::<IndexSet<_>>()
+        .into_iter()
+        .map(|item| {
+            let location = item
+                .name_span(db)
+                .and_then(|span| span.location(db.upcast()))
+                .map(|location| location.to_string());
+            serde_json::json!({
+                "id": symbol_id(item),
+                "kind": symbol_kind(item),
+                "location": location,
+            })
+        })
+        .collect::<Vec<_>>();
+    let edges = graph
+        .edges
+        .iter()
+        .map(|(from, to, kind)| {
+            serde_json::json!({
+                "from": symbol_id(*from),
+                "to": symbol_id(*to),
+                "kind": kind,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::to_string_pretty(&serde_json::json!({
+        "ingot": ingot.name(db).as_str(),
+        "nodes": nodes,
+        "edges": edges,
+    }))
+    .unwrap()
+}
+
+#[derive(Default)]
+struct SymbolGraph {
+    items: IndexSet<Item>,
+    edges: IndexSet<(Item, Item, &'static str)>,
+}
+
+impl SymbolGraph {
+    fn add_item(&mut self, db: &Db, item: Item) {
+        if matches!(item, Item::Attribute(_)) || !self.items.insert(item) {
+            return;
+        }
+
+        match item {
+            Item::Function(function) => {
+                let signature = function.signature(db);
+                for typ in signature
+                    .params
+                    .iter()
+                    .filter_map(|param| param.typ.clone().ok())
+                    .chain(signature.return_type.clone())
+                {
+                    self.add_type_use(db, item, typ);
+                }
+
+                for call in function.body(db).calls.values() {
+                    match call {
+                        CallType::Pure(callee)
+                        | CallType::AssociatedFunction {
+                            function: callee, ..
+                        }
+                        | CallType::ValueMethod { method: callee, .. }
+                        | CallType::External {
+                            function: callee, ..
+                        } => {
+                            self.edges.insert((item, Item::Function(*callee), "call"));
+                        }
+                        CallType::TraitValueMethod { trait_id, .. } => {
+                            self.edges.insert((item, Item::Trait(*trait_id), "call"));
+                        }
+                        CallType::TypeConstructor(typ) => self.add_type_use(db, item, *typ),
+                        CallType::EnumConstructor(variant) => {
+                            let enum_ = Item::Type(TypeDef::Enum(variant.parent(db)));
+                            self.edges.insert((item, enum_, "type"));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Item::Type(TypeDef::Struct(struct_)) => {
+                for field in struct_.fields(db).values() {
+                    if let Ok(typ) = field.typ(db) {
+                        self.add_type_use(db, item, typ);
+                    }
+                }
+                self.add_functions(db, &struct_.all_functions(db));
+            }
+            Item::Type(TypeDef::Enum(enum_)) => {
+                for variant in enum_.variants(db).values() {
+                    if let Ok(EnumVariantKind::Tuple(types)) = variant.kind(db) {
+                        for typ in types {
+                            self.add_type_use(db, item, typ);
+                        }
+                    }
+                }
+                self.add_functions(db, &enum_.all_functions(db));
+            }
+            Item::Type(TypeDef::Contract(contract)) => {
+                for field in contract.fields(db).values() {
+                    if let Ok(typ) = field.typ(db) {
+                        self.add_type_use(db, item, typ);
+                    }
+                }
+                self.add_functions(db, &contract.all_functions(db));
+            }
+            Item::Type(TypeDef::Alias(alias)) => {
+                if let Ok(typ) = alias.type_id(db) {
+                    self.add_type_use(db, item, typ);
+                }
+            }
+            Item::Constant(constant) => {
+                if let Ok(typ) = constant.typ(db) {
+                    self.add_type_use(db, item, typ);
+                }
+            }
+            Item::Impl(impl_) => {
+                self.edges
+                    .insert((item, Item::Trait(impl_.trait_id(db)), "implements"));
+                for target in type_items(db, impl_.receiver(db)) {
+                    self.edges.insert((item, target, "impl_for"));
+                }
+                self.add_functions(db, &impl_.all_functions(db));
+            }
+            _ => {}
+        }
+    }
+
+    fn add_functions(&mut self, db: &Db, functions: &[FunctionId]) {
+        for function in functions {
+            self.add_item(db, Item::Function(*function));
+        }
+    }
+
+    fn add_type_use(&mut self, db: &Db, from: Item, typ: TypeId) {
+        for target in type_items(db, typ) {
+            if target != from {
+                self.edges.insert((from, target, "type"));
+            }
+        }
+    }
+}
+
+/// Returns the user defined types that `typ` is made of.
+fn type_items(db: &Db, typ: TypeId) -> Vec<Item> {
+    match typ.typ(db) {
+        Type::Struct(struct_) => vec![Item::Type(TypeDef::Struct(struct_))],
+        Type::Enum(enum_) => vec![Item::Type(TypeDef::Enum(enum_))],
+        Type::Contract(contract) | Type::SelfContract(contract) => {
+            vec![Item::Type(TypeDef::Contract(contract))]
+        }
+        Type::Array(array) => type_items(db, array.inner),
+        Type::Map(map) => [map.key, map.value]
+            .into_iter()
+            .flat_map(|typ| type_items(db, typ))
+            .collect(),
+        Type::Tuple(tuple) => tuple
+            .items
+            .iter()
+            .flat_map(|typ| type_items(db, *typ))
+            .collect(),
+        Type::SPtr(inner) | Type::Mut(inner) => type_items(db, inner),
+        _ => vec![],
+    }
+}
+
+fn symbol_kind(item: Item) -> &'static str {
+    match item {
+        Item::Type(TypeDef::Enum(_)) => "enum",
+        Item::Type(TypeDef::Contract(_)) => "contract",
+        Item::Type(TypeDef::Alias(_)) => "type alias",
+        item => item.item_kind_display_name(),
+    }
+}
+
 fn compile_to_yul(db: &mut Db, contract: ContractId) -> String {
+    let _span = tracing::info_span!("codegen").entered();
     let yul_contract = fe_codegen::yul::isel::lower_contract_deployable(db, contract);
     yul_contract.to_string().replace('"', "\\\"")
 }
 
+/// Returns an error with the size of each function in the runtime bytecode if
+/// the runtime bytecode is larger than `budget` bytes.
+#[cfg(feature = "solc-backend")]
+fn check_code_size_budget(
+    contract_name: &str,
+    bytecode: &fe_yulc::ContractBytecode,
+    budget: usize,
+    span: Span,
+) -> Result<(), CompileError> {
+    let size = bytecode.runtime_bytecode.len() / 2;
+    if size <= budget {
+        return Ok(());
+    }
+
+    // Functions are laid out one after another, so each one extends to the
+    // entry point of the next. Code before the first entry point belongs to
+    // the dispatcher and the functions that were inlined into it.
+    let entries = bytecode.runtime_function_entries.iter().collect::<Vec<_>>();
+    let mut sizes = vec![(
+        "<dispatcher>",
+        entries.first().map_or(size, |(offset, _)| **offset),
+    )];
+    for (idx, (offset, name)) in entries.iter().enumerate() {
+        let end = entries.get(idx + 1).map_or(size, |(next, _)| **next);
+        sizes.push((name.as_str(), end.saturating_sub(**offset)));
+    }
+    sizes.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let breakdown = sizes
+        .iter()
+        .map(|(name, size)| format!("  {size:>6} bytes  {name}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    Err(CompileError(vec![Diagnostic {
+        severity: Severity::Error,
+        message: format!("the runtime bytecode of `{contract_name}` exceeds its code size budget"),
+        labels: vec![Label::primary(
+            span,
+            format!("the budget is {budget} bytes, but the bytecode is {size} bytes"),
+        )],
+        notes: vec![format!(
+            "Size of each function in the runtime bytecode:\n{breakdown}"
+        )],
+        suggestions: vec![],
+    }]))
+}
+
 #[cfg(feature = "solc-backend")]
 fn compile_to_evm(
     name: &str,
     yul_object: &str,
     optimize: bool,
+    optimizer_steps: Option<&str>,
+    evm_version: Option<EvmVersion>,
     verify_runtime_bytecode: bool,
 ) -> fe_yulc::ContractBytecode {
-    match fe_yulc::compile_single_contract(name, yul_object, optimize, verify_runtime_bytecode) {
+    let _span = tracing::info_span!("yulc", optimize, optimizer_steps = ?optimizer_steps).entered();
+    match fe_yulc::compile_single_contract(
+        name,
+        yul_object,
+        optimize,
+        optimizer_steps,
+        evm_version.map(EvmVersion::as_str),
+        verify_runtime_bytecode,
+    ) {
         Ok(bytecode) => bytecode,
 
         Err(error) => {
@@ -0,0 +1,83 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use fe_common::panic::{query_stack, set_ice_callback};
+use walkdir::WalkDir;
+
+/// Writes a reproduction bundle into `dir` if the compiler panics.
+///
+/// The bundle contains the compiler version, the command line, the panic
+/// message and query stack, and a copy of every file or ingot that is passed on
+/// the command line.
+pub fn write_bundle_on_ice(dir: PathBuf) {
+    set_ice_callback(Box::new(move |info| {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        let bundle_dir = dir.join(format!("fe-ice-{timestamp}"));
+
+        match write_bundle(&bundle_dir, &info.to_string()) {
+            Ok(()) => {
+                eprintln!();
+                eprintln!("A reproduction of this crash was saved to:");
+                eprintln!("  {}", bundle_dir.display());
+                eprintln!("Please attach it to the bug report.");
+            }
+            Err(err) => eprintln!("Failed to save the reproduction bundle. Error: {err}"),
+        }
+    }));
+}
+
+fn write_bundle(bundle_dir: &Path, message: &str) -> std::io::Result<()> {
+    let args: Vec<_> = std::env::args().collect();
+
+    let mut report = format!(
+        "fe {}\n\ncommand:\n  {}\n\n{message}\n",
+        env!("CARGO_PKG_VERSION"),
+        args.join(" ")
+    );
+    if let Some(stack) = query_stack() {
+        report.push_str(&format!("\nquery stack:\n{stack}\n"));
+    }
+    fs::create_dir_all(bundle_dir)?;
+    fs::write(bundle_dir.join("report.txt"), report)?;
+
+    // The arguments that are paths to existing files or directories are the
+    // inputs of the command.
+    let inputs_dir = bundle_dir.join("inputs");
+    for arg in args
+        .iter()
+        .skip(1)
+        .map(Path::new)
+        .filter(|arg| arg.exists())
+    {
+        for entry in WalkDir::new(arg).into_iter().filter_map(Result::ok) {
+            let path = entry.path();
+            if !is_input_file(path) {
+                continue;
+            }
+
+            let relative = path.strip_prefix(arg).unwrap_or(path);
+            let arg_name = arg.file_name().map(PathBuf::from).unwrap_or_default();
+            let dest = if relative.as_os_str().is_empty() {
+                inputs_dir.join(arg_name)
+            } else {
+                inputs_dir.join(arg_name).join(relative)
+            };
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(path, dest)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn is_input_file(path: &Path) -> bool {
+    path.is_file()
+        && (path.extension().is_some_and(|ext| ext == "fe")
+            || path.file_name().is_some_and(|name| name == "fe.toml"))
+}
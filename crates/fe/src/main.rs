@@ -1,12 +1,37 @@
+mod ice;
 mod task;
 
+use std::path::PathBuf;
+
 use clap::Parser;
+use fe_common::diagnostics::{set_render_options, ColorMode, ErrorFormat, RenderOptions, Theme};
 use fe_common::panic::install_panic_hook;
 use task::Commands;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
 
 #[derive(Parser)]
 #[clap(author, version, about, long_about = None)]
 struct FelangCli {
+    /// Filter for the log output, e.g. `info` or `fe_driver=debug`. Defaults to
+    /// the value of the `FE_LOG` environment variable.
+    #[clap(long, global = true, takes_value(true))]
+    log: Option<String>,
+    /// Directory to save a reproduction of the inputs to if the compiler
+    /// crashes.
+    #[clap(long, global = true, takes_value(true))]
+    ice_bundle: Option<PathBuf>,
+    /// How to print errors and warnings: `full`, or `short` for one line
+    /// per diagnostic.
+    #[clap(long, global = true, default_value = "full")]
+    error_format: ErrorFormat,
+    /// When to color the output: `auto`, `always` or `never`. `auto` respects
+    /// the `NO_COLOR`, `CLICOLOR` and `CLICOLOR_FORCE` environment variables.
+    #[clap(long, global = true, default_value = "auto")]
+    color: ColorMode,
+    /// The colors of errors and warnings: `default` or `high-contrast`.
+    /// Defaults to the value of the `FE_THEME` environment variable.
+    #[clap(long, global = true, takes_value(true))]
+    theme: Option<Theme>,
     #[clap(subcommand)]
     command: Commands,
 }
@@ -15,6 +40,20 @@ fn main() {
     install_panic_hook();
 
     let cli = FelangCli::parse();
+    init_logging(cli.log.as_deref());
+    set_render_options(RenderOptions {
+        format: cli.error_format,
+        color: cli.color,
+        theme: cli.theme.unwrap_or_else(theme_from_env),
+    });
+    match cli.color {
+        ColorMode::Always => colored::control::set_override(true),
+        ColorMode::Never => colored::control::set_override(false),
+        ColorMode::Auto => {}
+    }
+    if let Some(dir) = cli.ice_bundle {
+        ice::write_bundle_on_ice(dir);
+    }
 
     match cli.command {
         Commands::Build(arg) => {
@@ -23,9 +62,24 @@ fn main() {
         Commands::Check(arg) => {
             task::check(arg);
         }
+        Commands::Fix(arg) => {
+            task::fix(arg);
+        }
         Commands::New(arg) => {
             task::create_new_project(arg);
         }
+        Commands::PublicApi(arg) => {
+            task::public_api(arg);
+        }
+        Commands::SemverCheck(arg) => {
+            task::semver_check(arg);
+        }
+        Commands::Ssr(arg) => {
+            task::ssr(arg);
+        }
+        Commands::StorageDiff(arg) => {
+            task::storage_diff(arg);
+        }
         #[cfg(feature = "solc-backend")]
         Commands::Verify(arg) => {
             task::verify(arg);
@@ -36,3 +90,30 @@ fn main() {
         }
     }
 }
+
+fn theme_from_env() -> Theme {
+    match std::env::var("FE_THEME") {
+        Ok(theme) => theme.parse().unwrap_or_else(|err| {
+            eprintln!("Ignoring `FE_THEME`: {err}");
+            Theme::Default
+        }),
+        Err(_) => Theme::Default,
+    }
+}
+
+fn init_logging(filter: Option<&str>) {
+    let filter = match filter {
+        Some(filter) => EnvFilter::new(filter),
+        None => EnvFilter::try_from_env("FE_LOG").unwrap_or_else(|_| EnvFilter::new("off")),
+    };
+    // The error layer records the active spans regardless of the filter, so
+    // they can be included in internal compiler error reports.
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_filter(filter),
+        )
+        .with(tracing_error::ErrorLayer::default())
+        .init();
+}
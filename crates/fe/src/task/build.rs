@@ -3,21 +3,29 @@ use std::io::{Error, Write};
 use std::path::Path;
 
 use clap::{ArgEnum, Args};
+use fe_common::chain::ChainProfile;
 use fe_common::diagnostics::print_diagnostics;
+use fe_common::evm::EvmVersion;
 use fe_common::files::SourceFileId;
 use fe_common::utils::files::{BuildFiles, ProjectMode};
-use fe_driver::CompiledModule;
+use fe_driver::{CodegenDb, CompiledModule};
 
 const DEFAULT_OUTPUT_DIR_NAME: &str = "output";
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ArgEnum, Debug)]
 enum Emit {
     Abi,
+    Asm,
     Ast,
     LoweredAst,
     Bytecode,
+    Natspec,
     RuntimeBytecode,
+    Selectors,
+    StorageLayout,
+    SymbolGraph,
     Tokens,
+    TsBindings,
     Yul,
 }
 
@@ -41,16 +49,56 @@ pub struct BuildArgs {
     overwrite: bool,
     #[clap(long, takes_value(true))]
     optimize: Option<bool>,
+    /// Skip the calldata size checks in the external function dispatcher.
+    #[clap(long)]
+    no_calldata_validation: bool,
+    /// Step sequence of the Yul optimizer, in the `--yul-optimizations` format
+    /// of solc. The default sequence of solc is used if omitted.
+    #[clap(long, takes_value(true))]
+    yul_optimizer_steps: Option<String>,
+    /// EVM version to compile for, e.g. `shanghai`. Overrides the
+    /// `evm-version` of the manifest. Calls that lower to instructions that
+    /// are unavailable or deprecated on the version are rejected.
+    #[clap(long, takes_value(true))]
+    evm_version: Option<String>,
+    /// Chain to compile for, e.g. `optimism`. Overrides the `chain` of the
+    /// manifest. Its EVM version is used unless one is given explicitly.
+    #[clap(long, takes_value(true))]
+    chain: Option<String>,
+}
+
+fn parse_evm_version(evm_version: Option<&str>) -> Option<EvmVersion> {
+    evm_version.map(|version| match version.parse() {
+        Ok(version) => version,
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1)
+        }
+    })
 }
 
 fn build_single_file(compile_arg: &BuildArgs) -> (String, CompiledModule) {
     let emit = &compile_arg.emit;
     let with_bytecode = emit.contains(&Emit::Bytecode);
-    let with_runtime_bytecode = emit.contains(&Emit::RuntimeBytecode);
+    let with_runtime_bytecode = emit.contains(&Emit::RuntimeBytecode) || emit.contains(&Emit::Asm);
     let input_path = &compile_arg.input_path;
     let optimize = compile_arg.optimize.unwrap_or(true);
 
     let mut db = fe_driver::Db::default();
+    db.set_codegen_calldata_validation(!compile_arg.no_calldata_validation);
+    db.set_codegen_yul_optimizer_steps(compile_arg.yul_optimizer_steps.as_deref().map(Into::into));
+    let chain = compile_arg.chain.as_deref().map(|name| {
+        ChainProfile::builtin(name).unwrap_or_else(|| {
+            let chains = ChainProfile::BUILTIN.join(", ");
+            eprintln!("Unknown chain `{name}`. Known chains: {chains}");
+            std::process::exit(1)
+        })
+    });
+    db.set_codegen_evm_version(
+        parse_evm_version(compile_arg.evm_version.as_deref())
+            .or_else(|| chain.as_ref().map(|chain| chain.evm_version)),
+    );
+    db.set_codegen_chain_profile(chain);
     let content = match std::fs::read_to_string(input_path) {
         Err(err) => {
             eprintln!("Failed to load file: `{input_path}`. Error: {err}");
@@ -80,7 +128,7 @@ fn build_single_file(compile_arg: &BuildArgs) -> (String, CompiledModule) {
 fn build_ingot(compile_arg: &BuildArgs) -> (String, CompiledModule) {
     let emit = &compile_arg.emit;
     let with_bytecode = emit.contains(&Emit::Bytecode);
-    let with_runtime_bytecode = emit.contains(&Emit::RuntimeBytecode);
+    let with_runtime_bytecode = emit.contains(&Emit::RuntimeBytecode) || emit.contains(&Emit::Asm);
     let input_path = &compile_arg.input_path;
     let optimize = compile_arg.optimize.unwrap_or(true);
 
@@ -104,6 +152,21 @@ fn build_ingot(compile_arg: &BuildArgs) -> (String, CompiledModule) {
     }
 
     let mut db = fe_driver::Db::default();
+    db.set_codegen_calldata_validation(!compile_arg.no_calldata_validation);
+    db.set_codegen_yul_optimizer_steps(compile_arg.yul_optimizer_steps.as_deref().map(Into::into));
+    let chain = match build_files.root_project_chain(compile_arg.chain.as_deref()) {
+        Ok(chain) => chain,
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1)
+        }
+    };
+    db.set_codegen_evm_version(
+        parse_evm_version(compile_arg.evm_version.as_deref())
+            .or_else(|| build_files.root_project_evm_version())
+            .or_else(|| chain.as_ref().map(|chain| chain.evm_version)),
+    );
+    db.set_codegen_chain_profile(chain);
     let compiled_module = match fe_driver::compile_ingot(
         &mut db,
         &build_files,
@@ -132,7 +195,7 @@ pub fn build(compile_arg: BuildArgs) {
         return mir_dump(input_path);
     }
 
-    let _with_bytecode = emit.contains(&Emit::Bytecode);
+    let _with_bytecode = emit.contains(&Emit::Bytecode) || emit.contains(&Emit::Asm);
     #[cfg(not(feature = "solc-backend"))]
     if _with_bytecode {
         eprintln!("Warning: bytecode output requires 'solc-backend' feature. Try `cargo build --release --features solc-backend`. Skipping.");
@@ -192,6 +255,22 @@ fn write_compiled_module(
         write_output(&output_dir.join("module.tokens"), &format!("{tokens:#?}"))?;
     }
 
+    if targets.contains(&Emit::SymbolGraph) {
+        write_output(
+            &output_dir.join("symbol_graph.json"),
+            &module.json_symbol_graph,
+        )?;
+    }
+
+    // Record the settings used to produce the bytecode, so that it can be
+    // reproduced.
+    if [Emit::Bytecode, Emit::RuntimeBytecode, Emit::Asm]
+        .iter()
+        .any(|target| targets.contains(target))
+    {
+        write_output(&output_dir.join("metadata.json"), &module.json_metadata)?;
+    }
+
     for (name, contract) in module.contracts.drain(0..) {
         let contract_output_dir = output_dir.join(&name);
         fs::create_dir_all(&contract_output_dir).map_err(ioerr_to_string)?;
@@ -201,6 +280,34 @@ fn write_compiled_module(
             write_output(&contract_output_dir.join(file_name), &contract.json_abi)?;
         }
 
+        if targets.contains(&Emit::Selectors) {
+            let file_name = format!("{}_selectors.json", &name);
+            write_output(
+                &contract_output_dir.join(file_name),
+                &contract.json_selectors,
+            )?;
+        }
+
+        if targets.contains(&Emit::StorageLayout) {
+            let file_name = format!("{}_storage_layout.json", &name);
+            write_output(
+                &contract_output_dir.join(file_name),
+                &contract.json_storage_layout,
+            )?;
+        }
+
+        if targets.contains(&Emit::Natspec) {
+            let file_name = format!("{}_userdoc.json", &name);
+            write_output(&contract_output_dir.join(file_name), &contract.json_userdoc)?;
+            let file_name = format!("{}_devdoc.json", &name);
+            write_output(&contract_output_dir.join(file_name), &contract.json_devdoc)?;
+        }
+
+        if targets.contains(&Emit::TsBindings) {
+            let file_name = format!("{}.ts", &name);
+            write_output(&contract_output_dir.join(file_name), &contract.ts_bindings)?;
+        }
+
         if targets.contains(&Emit::Yul) {
             let file_name = format!("{}_ir.yul", &name);
             write_output(&contract_output_dir.join(file_name), &contract.yul)?;
@@ -219,6 +326,11 @@ fn write_compiled_module(
                 &contract.runtime_bytecode,
             )?;
         }
+        #[cfg(feature = "solc-backend")]
+        if targets.contains(&Emit::Asm) {
+            let file_name = format!("{}.runtime.asm", &name);
+            write_output(&contract_output_dir.join(file_name), &contract.runtime_asm)?;
+        }
     }
 
     Ok(())
@@ -5,12 +5,19 @@ use fe_common::{
     diagnostics::{print_diagnostics, Diagnostic},
     utils::files::BuildFiles,
 };
-use fe_driver::Db;
+use fe_driver::{CompileError, Db};
 
 #[derive(Args)]
 #[clap(about = "Analyze the current project and report errors, but don't build artifacts")]
 pub struct CheckArgs {
     input_path: String,
+    /// Also check that the contracts implement every function, event and
+    /// error of the given JSON ABI, e.g. of an ERC standard
+    #[clap(long, value_name = "ABI")]
+    conforms_to: Option<String>,
+    /// Only check the named contract against the `--conforms-to` ABI
+    #[clap(long)]
+    contract: Option<String>,
 }
 
 fn check_single_file(db: &mut Db, input_path: &str) -> Vec<Diagnostic> {
@@ -41,6 +48,11 @@ pub fn check(args: CheckArgs) {
     let mut db = fe_driver::Db::default();
     let input_path = args.input_path;
 
+    if let Some(abi_path) = &args.conforms_to {
+        check_conformance(&mut db, &input_path, abi_path, args.contract.as_deref());
+        return;
+    }
+
     // check project
     let diags = if Path::new(&input_path).is_file() {
         check_single_file(&mut db, &input_path)
@@ -55,3 +67,72 @@ pub fn check(args: CheckArgs) {
 
     eprintln!("Finished");
 }
+
+fn check_conformance(db: &mut Db, input_path: &str, abi_path: &str, contract: Option<&str>) {
+    let abi = match std::fs::read_to_string(abi_path) {
+        Err(err) => {
+            eprintln!("Failed to load file: `{abi_path}`. Error: {err}");
+            std::process::exit(1)
+        }
+        Ok(content) => content,
+    };
+    let abi = match fe_driver::parse_json_abi(&abi) {
+        Ok(abi) => abi,
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1)
+        }
+    };
+
+    let result = if Path::new(input_path).is_file() {
+        let content = match std::fs::read_to_string(input_path) {
+            Err(err) => {
+                eprintln!("Failed to load file: `{input_path}`. Error: {err}");
+                std::process::exit(1)
+            }
+            Ok(content) => content,
+        };
+        fe_driver::abi_conformance_single_file(db, input_path, &content, &abi)
+    } else {
+        let build_files = match BuildFiles::load_fs(input_path) {
+            Ok(files) => files,
+            Err(err) => {
+                eprintln!("Failed to load project files.\nError: {err}");
+                std::process::exit(1)
+            }
+        };
+        fe_driver::abi_conformance_ingot(db, &build_files, &abi)
+    };
+
+    let mut contracts = match result {
+        Ok(contracts) => contracts,
+        Err(CompileError(diags)) => {
+            print_diagnostics(db, &diags);
+            std::process::exit(1)
+        }
+    };
+    if let Some(name) = contract {
+        contracts.retain(|contract, _| contract == name);
+        if contracts.is_empty() {
+            eprintln!("No contract named `{name}` found");
+            std::process::exit(1)
+        }
+    }
+
+    let mut conforms = true;
+    for (name, mismatches) in contracts {
+        if mismatches.is_empty() {
+            eprintln!("Contract `{name}` conforms to `{abi_path}`");
+        } else {
+            conforms = false;
+            eprintln!("Contract `{name}` doesn't conform to `{abi_path}`:");
+            for mismatch in mismatches {
+                eprintln!("  - {mismatch}");
+            }
+        }
+    }
+
+    if !conforms {
+        std::process::exit(1);
+    }
+}
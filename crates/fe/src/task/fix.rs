@@ -0,0 +1,208 @@
+use std::path::Path;
+
+use clap::Args;
+use fe_common::diagnostics::{
+    apply_suggestions, machine_applicable_suggestions, Applicability, Diagnostic,
+};
+use fe_common::files::SourceFileId;
+use fe_common::utils::files::BuildFiles;
+use fe_driver::Db;
+use fe_parser::ssr::SsrRule;
+
+/// Rewrites of the properties of the global objects of older versions of
+/// Fe, which were replaced by the methods of `Context`.
+///
+/// The rewritten code expects a `ctx: Context` parameter, which the function
+/// may not have, so these rewrites are only reported.
+const CONTEXT_MIGRATIONS: &[&str] = &[
+    "msg.sender ==>> ctx.msg_sender()",
+    "msg.value ==>> ctx.msg_value()",
+    "msg.sig ==>> ctx.msg_sig()",
+    "block.coinbase ==>> ctx.block_coinbase()",
+    "block.difficulty ==>> ctx.prevrandao()",
+    "block.number ==>> ctx.block_number()",
+    "block.timestamp ==>> ctx.block_timestamp()",
+    "chain.id ==>> ctx.chain_id()",
+    "tx.gas_price ==>> ctx.tx_gas_price()",
+    "tx.origin ==>> ctx.tx_origin()",
+];
+
+/// The migration rules, and whether `fe fix --edition` applies them or only
+/// reports them.
+const MIGRATIONS: &[(&[&str], Applicability)] =
+    &[(CONTEXT_MIGRATIONS, Applicability::MaybeIncorrect)];
+
+/// Fixing one problem can reveal another, so the files are checked again
+/// after each round of fixes, up to this many times.
+const MAX_ROUNDS: usize = 8;
+
+#[derive(Args)]
+#[clap(about = "Apply the fixes that the compiler suggests")]
+pub struct FixArgs {
+    /// The ingot or file to fix
+    #[clap(default_value = ".")]
+    input_path: String,
+    /// Also migrate deprecated syntax and renamed `std` items of older
+    /// versions of Fe. Migrations that may need further changes, such as
+    /// adding a `ctx: Context` parameter, are only reported
+    #[clap(long)]
+    edition: bool,
+    /// Print the fixes without rewriting any files
+    #[clap(long)]
+    dry_run: bool,
+}
+
+enum Input {
+    File(String, String),
+    Ingot(BuildFiles),
+}
+
+impl Input {
+    fn load(input_path: &str) -> Self {
+        if Path::new(input_path).is_file() {
+            match std::fs::read_to_string(input_path) {
+                Ok(content) => Input::File(input_path.to_string(), content),
+                Err(err) => {
+                    eprintln!("Failed to load file: `{input_path}`. Error: {err}");
+                    std::process::exit(1)
+                }
+            }
+        } else {
+            match BuildFiles::load_fs(input_path) {
+                Ok(build_files) => Input::Ingot(build_files),
+                Err(err) => {
+                    eprintln!("Failed to load project files.\nError: {err}");
+                    std::process::exit(1)
+                }
+            }
+        }
+    }
+
+    /// The files that are fixed. The files of dependencies are left as is.
+    fn files_mut(&mut self) -> Vec<(&String, &mut String)> {
+        match self {
+            Input::File(path, content) => vec![(&*path, content)],
+            Input::Ingot(build_files) => build_files
+                .project_files
+                .get_mut(&build_files.root_project_path)
+                .unwrap()
+                .src
+                .iter_mut()
+                .map(|(path, content)| (&*path, content))
+                .collect(),
+        }
+    }
+
+    fn check(&self, db: &mut Db) -> Vec<Diagnostic> {
+        match self {
+            Input::File(path, content) => fe_driver::check_single_file(db, path, content),
+            Input::Ingot(build_files) => fe_driver::check_ingot(db, build_files),
+        }
+    }
+}
+
+pub fn fix(args: FixArgs) {
+    let mut input = Input::load(&args.input_path);
+    let original: Vec<_> = input
+        .files_mut()
+        .into_iter()
+        .map(|(_, content)| content.clone())
+        .collect();
+
+    let mut fix_count = 0;
+    if args.edition {
+        fix_count += migrate(&mut input);
+    }
+
+    for _ in 0..MAX_ROUNDS {
+        let mut db = Db::default();
+        let suggestions = machine_applicable_suggestions(&input.check(&mut db));
+        if suggestions.is_empty() {
+            break;
+        }
+
+        let mut applied_count = 0;
+        for (path, content) in input.files_mut() {
+            let suggestions: Vec<_> = suggestions
+                .iter()
+                .filter(|suggestion| suggestion.span.file_id.path(&db).as_str() == path.as_str())
+                .cloned()
+                .collect();
+            let (fixed, applied) = apply_suggestions(content, &suggestions);
+            for suggestion in &applied {
+                let line = content[..suggestion.span.start].matches('\n').count() + 1;
+                println!("{path}:{line}: {}", suggestion.message);
+            }
+            applied_count += applied.len();
+            *content = fixed;
+        }
+        if applied_count == 0 {
+            break;
+        }
+        fix_count += applied_count;
+    }
+
+    if !args.dry_run {
+        for ((path, content), original) in input.files_mut().into_iter().zip(original) {
+            if *content != original {
+                if let Err(err) = std::fs::write(path, content.as_str()) {
+                    eprintln!("Failed to write file: `{path}`. Error: {err}");
+                    std::process::exit(1)
+                }
+            }
+        }
+    }
+
+    if args.dry_run {
+        eprintln!("Found {fix_count} fixes");
+    } else {
+        eprintln!("Applied {fix_count} fixes");
+    }
+}
+
+/// Applies the machine-applicable migration rules to the files, and returns
+/// the number of rewritten expressions. The matches of the other rules are
+/// only reported. Files with syntax errors are skipped.
+fn migrate(input: &mut Input) -> usize {
+    let rules: Vec<_> = MIGRATIONS
+        .iter()
+        .flat_map(|(rules, applicability)| {
+            rules.iter().map(|rule| {
+                let rule = SsrRule::parse(rule).expect("invalid migration rule");
+                (rule, *applicability)
+            })
+        })
+        .collect();
+
+    let mut count = 0;
+    for (path, content) in input.files_mut() {
+        for (rule, applicability) in &rules {
+            let (module, diags) = fe_parser::parse_file(SourceFileId::dummy_file(), content);
+            if !diags.is_empty() {
+                eprintln!("Skipping `{path}`, which has syntax errors");
+                break;
+            }
+
+            let matches = rule.find_matches(&module, content);
+            for match_ in &matches {
+                let line = content[..match_.span.start].matches('\n').count() + 1;
+                let code = &content[match_.span.start..match_.span.end];
+                let action = match applicability {
+                    Applicability::MachineApplicable => "replace",
+                    Applicability::MaybeIncorrect => "consider replacing",
+                };
+                println!(
+                    "{path}:{line}: {action} `{code}` with `{}`",
+                    match_.replacement
+                );
+            }
+            if *applicability == Applicability::MachineApplicable {
+                count += matches.len();
+                if let Some(rewritten) = rule.apply(&module, content) {
+                    *content = rewritten;
+                }
+            }
+        }
+    }
+    count
+}
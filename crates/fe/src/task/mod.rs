@@ -1,6 +1,11 @@
 mod build;
 mod check;
+mod fix;
 mod new;
+mod public_api;
+mod semver_check;
+mod ssr;
+mod storage_diff;
 #[cfg(feature = "solc-backend")]
 mod test;
 mod verify;
@@ -8,7 +13,12 @@ mod verify;
 pub use build::{build, BuildArgs};
 pub use check::{check, CheckArgs};
 use clap::Subcommand;
+pub use fix::{fix, FixArgs};
 pub use new::{create_new_project, NewProjectArgs};
+pub use public_api::{public_api, PublicApiArgs};
+pub use semver_check::{semver_check, SemverCheckArgs};
+pub use ssr::{ssr, SsrArgs};
+pub use storage_diff::{storage_diff, StorageDiffArgs};
 #[cfg(feature = "solc-backend")]
 pub use test::{test, TestArgs};
 #[cfg(feature = "solc-backend")]
@@ -18,7 +28,12 @@ pub use verify::{verify, VerifyArgs};
 pub enum Commands {
     Build(BuildArgs),
     Check(CheckArgs),
+    Fix(FixArgs),
     New(NewProjectArgs),
+    PublicApi(PublicApiArgs),
+    SemverCheck(SemverCheckArgs),
+    Ssr(SsrArgs),
+    StorageDiff(StorageDiffArgs),
     #[cfg(feature = "solc-backend")]
     Verify(VerifyArgs),
     #[cfg(feature = "solc-backend")]
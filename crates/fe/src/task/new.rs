@@ -1,24 +1,42 @@
-use clap::Args;
+use clap::{ArgEnum, Args};
+use fe_common::edition::Edition;
 use include_dir::{include_dir, Dir};
 use std::{fs, path::Path};
 
 const SRC_TEMPLATE_DIR: Dir = include_dir!("$CARGO_MANIFEST_DIR/src/template/src");
+const AA_ACCOUNT_TEMPLATE_DIR: Dir =
+    include_dir!("$CARGO_MANIFEST_DIR/src/aa_account_template/src");
+
+#[derive(Copy, Clone, PartialEq, Eq, ArgEnum, Debug)]
+enum Template {
+    Default,
+    /// An ERC-4337 account contract.
+    AaAccount,
+}
 
 #[derive(Args)]
 #[clap(about = "Create new fe project")]
 pub struct NewProjectArgs {
     name: String,
+    #[clap(arg_enum, long, default_value = "default")]
+    template: Template,
 }
 
-fn create_project(name: &str, path: &Path) {
-    for src_file in SRC_TEMPLATE_DIR.entries() {
+fn create_project(name: &str, path: &Path, template: Template) {
+    let template_dir = match template {
+        Template::Default => &SRC_TEMPLATE_DIR,
+        Template::AaAccount => &AA_ACCOUNT_TEMPLATE_DIR,
+    };
+    for src_file in template_dir.entries() {
         let file = src_file.as_file().unwrap();
         fs::write(path.join("src").join(file.path()), file.contents()).unwrap();
     }
 
+    let edition = Edition::LATEST;
     let manifest_content = format!(
         "name = \"{name}\"
 version = \"1.0\"
+edition = \"{edition}\"
 
 [dependencies]
 # my_lib = \"../my_lib\"
@@ -39,7 +57,7 @@ pub fn create_new_project(args: NewProjectArgs) {
     }
 
     match fs::create_dir_all(project_path.join("src")) {
-        Ok(_) => create_project(&args.name, project_path),
+        Ok(_) => create_project(&args.name, project_path, args.template),
         Err(err) => {
             eprintln!("{err}");
             std::process::exit(1);
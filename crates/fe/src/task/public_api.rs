@@ -0,0 +1,51 @@
+use std::path::Path;
+
+use clap::Args;
+use fe_common::diagnostics::print_diagnostics;
+use fe_common::utils::files::BuildFiles;
+
+#[derive(Args)]
+#[clap(about = "Print a JSON description of the public items of an ingot")]
+pub struct PublicApiArgs {
+    #[clap(default_value = ".")]
+    input_path: String,
+}
+
+/// Analyzes the file or ingot at `input_path` and returns its public API as
+/// JSON. Exits the process if the input can't be loaded or has errors.
+pub fn load_public_api(input_path: &str) -> String {
+    let mut db = fe_driver::Db::default();
+
+    let api = if Path::new(input_path).is_file() {
+        let content = match std::fs::read_to_string(input_path) {
+            Err(err) => {
+                eprintln!("Failed to load file: `{input_path}`. Error: {err}");
+                std::process::exit(1)
+            }
+            Ok(content) => content,
+        };
+        fe_driver::public_api_single_file(&mut db, input_path, &content)
+    } else {
+        let build_files = match BuildFiles::load_fs(input_path) {
+            Ok(files) => files,
+            Err(err) => {
+                eprintln!("Failed to load project files.\nError: {err}");
+                std::process::exit(1)
+            }
+        };
+        fe_driver::public_api_ingot(&mut db, &build_files)
+    };
+
+    match api {
+        Ok(api) => api,
+        Err(error) => {
+            eprintln!("Unable to compile {input_path}.");
+            print_diagnostics(&db, &error.0);
+            std::process::exit(1)
+        }
+    }
+}
+
+pub fn public_api(args: PublicApiArgs) {
+    println!("{}", load_public_api(&args.input_path));
+}
@@ -0,0 +1,42 @@
+use clap::Args;
+
+use super::public_api::load_public_api;
+
+#[derive(Args)]
+#[clap(about = "Check that the public API of an ingot is compatible with a previous version")]
+pub struct SemverCheckArgs {
+    /// Public API of the previous version, as printed by `fe public-api`
+    old_api: String,
+    /// The ingot or file to check
+    #[clap(default_value = ".")]
+    input_path: String,
+}
+
+pub fn semver_check(args: SemverCheckArgs) {
+    let old = match std::fs::read_to_string(&args.old_api) {
+        Err(err) => {
+            eprintln!("Failed to load file: `{}`. Error: {err}", args.old_api);
+            std::process::exit(1)
+        }
+        Ok(content) => content,
+    };
+    let new = load_public_api(&args.input_path);
+
+    let changes = match fe_driver::public_api_breaking_changes(&old, &new) {
+        Ok(changes) => changes,
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1)
+        }
+    };
+
+    if !changes.is_empty() {
+        eprintln!("The public API has breaking changes since the previous version:");
+        for change in changes {
+            eprintln!("  - {change}");
+        }
+        std::process::exit(1);
+    }
+
+    eprintln!("The public API is compatible");
+}
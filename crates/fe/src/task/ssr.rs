@@ -0,0 +1,87 @@
+use std::path::Path;
+
+use clap::Args;
+use fe_common::files::SourceFileId;
+use fe_common::utils::files::BuildFiles;
+use fe_parser::ssr::SsrRule;
+
+#[derive(Args)]
+#[clap(about = "Rewrite the expressions that match a structural pattern")]
+pub struct SsrArgs {
+    /// The rule to apply, e.g. `'$a.checked_add($b) ==>> add($a, $b)'`
+    rule: String,
+    /// The ingot or file to rewrite
+    #[clap(default_value = ".")]
+    input_path: String,
+    /// Print the matches without rewriting any files
+    #[clap(long)]
+    dry_run: bool,
+}
+
+pub fn ssr(args: SsrArgs) {
+    let rule = match SsrRule::parse(&args.rule) {
+        Ok(rule) => rule,
+        Err(err) => {
+            eprintln!("Invalid rule: {err}");
+            std::process::exit(1)
+        }
+    };
+
+    let files = if Path::new(&args.input_path).is_file() {
+        match std::fs::read_to_string(&args.input_path) {
+            Ok(content) => vec![(args.input_path.clone(), content)],
+            Err(err) => {
+                eprintln!("Failed to load file: `{}`. Error: {err}", args.input_path);
+                std::process::exit(1)
+            }
+        }
+    } else {
+        // Only the files of the ingot itself are rewritten, not those of its
+        // dependencies.
+        match BuildFiles::load_fs(&args.input_path) {
+            Ok(mut build_files) => {
+                build_files
+                    .project_files
+                    .remove(&build_files.root_project_path)
+                    .unwrap()
+                    .src
+            }
+            Err(err) => {
+                eprintln!("Failed to load project files.\nError: {err}");
+                std::process::exit(1)
+            }
+        }
+    };
+
+    let mut match_count = 0;
+    for (path, src) in files {
+        let (module, diags) = fe_parser::parse_file(SourceFileId::dummy_file(), &src);
+        if !diags.is_empty() {
+            eprintln!("Skipping `{path}`, which has syntax errors");
+            continue;
+        }
+
+        let matches = rule.find_matches(&module, &src);
+        for match_ in &matches {
+            let line = src[..match_.span.start].matches('\n').count() + 1;
+            let code = &src[match_.span.start..match_.span.end];
+            println!("{path}:{line}: `{code}` => `{}`", match_.replacement);
+        }
+        match_count += matches.len();
+
+        if !args.dry_run {
+            if let Some(rewritten) = rule.apply(&module, &src) {
+                if let Err(err) = std::fs::write(&path, rewritten) {
+                    eprintln!("Failed to write file: `{path}`. Error: {err}");
+                    std::process::exit(1)
+                }
+            }
+        }
+    }
+
+    if args.dry_run {
+        eprintln!("Found {match_count} matches");
+    } else {
+        eprintln!("Replaced {match_count} matches");
+    }
+}
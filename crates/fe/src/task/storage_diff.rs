@@ -0,0 +1,43 @@
+use clap::Args;
+
+#[derive(Args)]
+#[clap(about = "Check that a contract's storage layout is compatible with a previous version")]
+pub struct StorageDiffArgs {
+    /// Storage layout of the deployed contract version
+    old_layout: String,
+    /// Storage layout of the new contract version
+    new_layout: String,
+}
+
+fn read_layout(path: &str) -> String {
+    match std::fs::read_to_string(path) {
+        Err(err) => {
+            eprintln!("Failed to load file: `{path}`. Error: {err}");
+            std::process::exit(1)
+        }
+        Ok(content) => content,
+    }
+}
+
+pub fn storage_diff(args: StorageDiffArgs) {
+    let old = read_layout(&args.old_layout);
+    let new = read_layout(&args.new_layout);
+
+    let changes = match fe_driver::storage_layout_breaking_changes(&old, &new) {
+        Ok(changes) => changes,
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1)
+        }
+    };
+
+    if !changes.is_empty() {
+        eprintln!("Storage layout is not compatible with the previous version:");
+        for change in changes {
+            eprintln!("  - {change}");
+        }
+        std::process::exit(1);
+    }
+
+    eprintln!("Storage layout is compatible");
+}
@@ -6,7 +6,7 @@ use colored::Colorize;
 use fe_common::diagnostics::print_diagnostics;
 use fe_common::utils::files::{get_project_root, BuildFiles};
 use fe_driver::CompiledTest;
-use fe_test_runner::TestSink;
+use fe_test_runner::{TestEnv, TestSink};
 
 #[derive(Args)]
 #[clap(about = "Execute tests in the current project")]
@@ -19,6 +19,43 @@ pub struct TestArgs {
     optimize: Option<bool>,
     #[clap(long)]
     logs: bool,
+    /// Versioned hashes of the blobs of the test transactions, as hex.
+    #[clap(long, use_value_delimiter = true, takes_value(true))]
+    blob_hashes: Vec<String>,
+    /// Excess blob gas of the test block, which determines the blob base fee.
+    #[clap(long, default_value = "0")]
+    excess_blob_gas: u64,
+}
+
+fn test_env(args: &TestArgs) -> TestEnv {
+    let blob_hashes = args
+        .blob_hashes
+        .iter()
+        .map(|hash| {
+            parse_blob_hash(hash).unwrap_or_else(|| {
+                eprintln!("Invalid blob hash `{hash}`. Expected 32 bytes of hex.");
+                std::process::exit(1)
+            })
+        })
+        .collect();
+
+    TestEnv {
+        blob_hashes,
+        excess_blob_gas: args.excess_blob_gas,
+    }
+}
+
+fn parse_blob_hash(hash: &str) -> Option<[u8; 32]> {
+    let hash = hash.strip_prefix("0x").unwrap_or(hash);
+    if hash.len() != 64 || !hash.is_ascii() {
+        return None;
+    }
+
+    let mut bytes = [0; 32];
+    for (idx, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hash[2 * idx..2 * idx + 2], 16).ok()?;
+    }
+    Some(bytes)
 }
 
 pub fn test(args: TestArgs) {
@@ -37,7 +74,12 @@ pub fn test(args: TestArgs) {
     }
 }
 
-pub fn execute_tests(module_name: &str, tests: &[CompiledTest], sink: &mut TestSink) {
+pub fn execute_tests(
+    module_name: &str,
+    tests: &[CompiledTest],
+    env: &TestEnv,
+    sink: &mut TestSink,
+) {
     if tests.len() == 1 {
         println!("executing 1 test in {module_name}:");
     } else {
@@ -46,7 +88,7 @@ pub fn execute_tests(module_name: &str, tests: &[CompiledTest], sink: &mut TestS
 
     for test in tests {
         print!("  {} ...", test.name);
-        let test_passed = test.execute(sink);
+        let test_passed = test.execute_in(env, sink);
 
         if test_passed {
             println!(" {}", "passed".green())
@@ -74,7 +116,7 @@ fn test_single_file(args: &TestArgs) -> TestSink {
     match fe_driver::compile_single_file_tests(&mut db, input_path, &content, optimize) {
         Ok((name, tests)) => {
             let mut sink = TestSink::new(logs);
-            execute_tests(&name, &tests, &mut sink);
+            execute_tests(&name, &tests, &test_env(args), &mut sink);
             sink
         }
         Err(error) => {
@@ -108,9 +150,10 @@ fn test_ingot(args: &TestArgs) -> TestSink {
     match fe_driver::compile_ingot_tests(&mut db, &build_files, optimize) {
         Ok(test_batches) => {
             let mut sink = TestSink::new(logs);
+            let env = test_env(args);
             for (module_name, tests) in test_batches {
                 let tests = filter_tests(&tests, &args.filter);
-                execute_tests(&module_name, &tests, &mut sink);
+                execute_tests(&module_name, &tests, &env, &mut sink);
             }
             sink
         }
@@ -44,6 +44,8 @@ pub fn mir_lowered_pseudo_monomorphized_func_signature(
 }
 
 pub fn mir_lowered_func_body(db: &dyn MirDb, func: ir::FunctionId) -> Rc<ir::FunctionBody> {
+    let _span =
+        tracing::debug_span!("mir_lowered_func_body", item = %func.debug_name(db)).entered();
     lower_func_body(db, func)
 }
 
@@ -335,6 +335,15 @@ impl TypeId {
         matches!(&self.data(db).as_ref().kind, TypeKind::Struct(_))
     }
 
+    /// Returns `true` if the type is `std::buf::CalldataBytes`, which is
+    /// decoded as a reference to `bytes` in calldata.
+    pub fn is_calldata_bytes(self, db: &dyn MirDb) -> bool {
+        self.is_struct(db)
+            && self
+                .analyzer_ty(db)
+                .is_some_and(|ty| ty.is_calldata_bytes(db.upcast()))
+    }
+
     pub fn is_array(self, db: &dyn MirDb) -> bool {
         matches!(&self.data(db).kind, TypeKind::Array(_))
     }
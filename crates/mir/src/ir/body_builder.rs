@@ -121,6 +121,8 @@ impl BodyBuilder {
     impl_unary_inst!(not, UnOp::Not);
     impl_unary_inst!(neg, UnOp::Neg);
     impl_unary_inst!(inv, UnOp::Inv);
+    impl_unary_inst!(leading_zeros, UnOp::LeadingZeros);
+    impl_unary_inst!(count_ones, UnOp::CountOnes);
 
     impl_binary_inst!(add, BinOp::Add);
     impl_binary_inst!(sub, BinOp::Sub);
@@ -317,6 +317,10 @@ pub enum UnOp {
     Neg,
     /// `~` operator for bitwise inversion.
     Inv,
+    /// `leading_zeros` method of integers.
+    LeadingZeros,
+    /// `count_ones` method of integers.
+    CountOnes,
 }
 
 impl fmt::Display for UnOp {
@@ -325,6 +329,8 @@ impl fmt::Display for UnOp {
             Self::Not => write!(w, "not"),
             Self::Neg => write!(w, "-"),
             Self::Inv => write!(w, "~"),
+            Self::LeadingZeros => write!(w, "leading_zeros "),
+            Self::CountOnes => write!(w, "count_ones "),
         }
     }
 }
@@ -439,6 +445,12 @@ pub enum YulIntrinsicOp {
     Mstore8,
     Sload,
     Sstore,
+    /// Transient storage (EIP-1153), which the code generator replaces with
+    /// `sload` on EVM versions before Cancun.
+    Tload,
+    /// Transient storage (EIP-1153), which the code generator replaces with
+    /// `sstore` on EVM versions before Cancun.
+    Tstore,
     Msize,
     Gas,
     Address,
@@ -481,6 +493,8 @@ pub enum YulIntrinsicOp {
     Number,
     Prevrandao,
     Gaslimit,
+    Blobhash,
+    Blobbasefee,
 }
 impl YulIntrinsicOp {
     pub fn is_terminator(self) -> bool {
@@ -489,6 +503,19 @@ impl YulIntrinsicOp {
             Self::Return | Self::Revert | Self::Selfdestruct | Self::Invalid
         )
     }
+
+    /// Returns the opcode of an instruction that the bundled solc doesn't
+    /// support as a Yul builtin, so that it has to be emitted with `verbatim`,
+    /// and the number of values that the instruction returns.
+    pub fn verbatim_opcode(self) -> Option<(u8, usize)> {
+        match self {
+            Self::Blobhash => Some((0x49, 1)),
+            Self::Blobbasefee => Some((0x4a, 1)),
+            Self::Tload => Some((0x5c, 1)),
+            Self::Tstore => Some((0x5d, 0)),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for YulIntrinsicOp {
@@ -528,6 +555,8 @@ impl fmt::Display for YulIntrinsicOp {
             Self::Mstore8 => "__mstore8",
             Self::Sload => "__sload",
             Self::Sstore => "__sstore",
+            Self::Tload => "__tload",
+            Self::Tstore => "__tstore",
             Self::Msize => "__msize",
             Self::Gas => "__gas",
             Self::Address => "__address",
@@ -570,6 +599,8 @@ impl fmt::Display for YulIntrinsicOp {
             Self::Number => "__number",
             Self::Prevrandao => "__prevrandao",
             Self::Gaslimit => "__gaslimit",
+            Self::Blobhash => "__blobhash",
+            Self::Blobbasefee => "__blobbasefee",
         };
 
         write!(w, "{op}")
@@ -656,6 +687,8 @@ impl From<fe_analyzer::builtins::Intrinsic> for YulIntrinsicOp {
             Intrinsic::__number => Self::Number,
             Intrinsic::__prevrandao => Self::Prevrandao,
             Intrinsic::__gaslimit => Self::Gaslimit,
+            Intrinsic::__blobhash => Self::Blobhash,
+            Intrinsic::__blobbasefee => Self::Blobbasefee,
         }
     }
 }
@@ -2,18 +2,18 @@ use std::{collections::BTreeMap, rc::Rc, vec};
 
 use fe_analyzer::{
     builtins::{ContractTypeMethod, GlobalFunction, ValueMethod},
-    constants::{EMITTABLE_TRAIT_NAME, EMIT_FN_NAME},
+    constants::{EMITTABLE_TRAIT_NAME, EMIT_FN_NAME, PAUSED},
     context::{Adjustment, AdjustmentKind, CallType as AnalyzerCallType, NamedThing},
     namespace::{
         items as analyzer_items,
         types::{self as analyzer_types, Type},
     },
 };
-use fe_common::numeric::Literal;
+use fe_common::{numeric::Literal, utils::keccak};
 use fe_parser::{ast, node::Node};
 use fxhash::FxHashMap;
 use id_arena::{Arena, Id};
-use num_bigint::BigInt;
+use num_bigint::{BigInt, Sign};
 use smol_str::SmolStr;
 
 use crate::{
@@ -23,7 +23,7 @@ use crate::{
         body_builder::BodyBuilder,
         constant::ConstantValue,
         function::Linkage,
-        inst::{CallType, InstKind},
+        inst::{CallType, InstKind, YulIntrinsicOp},
         value::{AssignableValue, Local},
         BasicBlockId, Constant, FunctionBody, FunctionId, FunctionParam, FunctionSignature, InstId,
         SourceInfo, TypeId, Value, ValueId,
@@ -97,6 +97,13 @@ pub fn lower_func_body(db: &dyn MirDb, func: FunctionId) -> Rc<FunctionBody> {
         .into()
 }
 
+/// The storage slot of the lock of `#nonreentrant` functions. It's derived
+/// from a hash, like the slots of map entries, so it doesn't overlap with the
+/// fields of the contract.
+fn reentrancy_lock_slot() -> BigInt {
+    BigInt::from_bytes_be(Sign::Plus, &keccak::full_as_bytes(b"fe.reentrancy_lock"))
+}
+
 pub(super) struct BodyLowerHelper<'db, 'a> {
     pub(super) db: &'db dyn MirDb,
     pub(super) builder: BodyBuilder,
@@ -105,6 +112,8 @@ pub(super) struct BodyLowerHelper<'db, 'a> {
     analyzer_body: &'a fe_analyzer::context::FunctionBody,
     scopes: Arena<Scope>,
     current_scope: ScopeId,
+    /// Whether the function locks the contract with `#nonreentrant`.
+    is_nonreentrant: bool,
 }
 
 impl<'db, 'a> BodyLowerHelper<'db, 'a> {
@@ -116,6 +125,7 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
                 } else {
                     self.make_unit()
                 };
+                self.release_reentrancy_lock();
                 self.builder.ret(value, stmt.into());
                 let next_block = self.builder.make_block();
                 self.builder.move_to_block(next_block);
@@ -375,9 +385,23 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
             }
 
             ast::Expr::BinOperation { left, op, right } => {
-                let lhs = self.lower_expr_to_value(left);
-                let rhs = self.lower_expr_to_value(right);
-                self.lower_binop(op.kind, lhs, rhs, expr.into())
+                // String concatenation is folded during analysis, so the
+                // result is emitted as a static constant.
+                if let Some(value) = self.analyzer_body.expressions[&expr.id].const_value.clone() {
+                    let const_value = self.make_local_constant(
+                        "str_in_func".into(),
+                        ty,
+                        value.into(),
+                        expr.into(),
+                    );
+                    self.builder.bind(const_value, expr.into())
+                } else if let Some(call_type) = self.analyzer_body.calls.get(&expr.id).cloned() {
+                    self.lower_overloaded_binop(&call_type, left, right, expr.into())
+                } else {
+                    let lhs = self.lower_expr_to_value(left);
+                    let rhs = self.lower_expr_to_value(right);
+                    self.lower_binop(op.kind, lhs, rhs, expr.into())
+                }
             }
 
             ast::Expr::UnaryOperation { op, operand } => {
@@ -422,7 +446,17 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
                 args,
             } => {
                 let ty = self.expr_ty(expr);
-                self.lower_call(func, generic_args, &args.kind, ty, expr.into())
+                // Calls with compile-time known results, e.g. `keccak256` of a byte
+                // array literal, are replaced with their value.
+                if let Some(value) = self.analyzer_body.expressions[&expr.id].const_value.clone() {
+                    let imm = match value.into() {
+                        ConstantValue::Immediate(imm) => self.builder.make_imm(imm, ty),
+                        _ => unreachable!("only calls returning integers are folded"),
+                    };
+                    self.builder.bind(imm, expr.into())
+                } else {
+                    self.lower_call(func, generic_args, &args.kind, ty, expr.into())
+                }
             }
 
             ast::Expr::List { elts } | ast::Expr::Tuple { elts } => {
@@ -582,6 +616,7 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
             analyzer_body,
             scopes,
             current_scope,
+            is_nonreentrant: false,
         }
     }
 
@@ -604,6 +639,17 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
     }
 
     fn lower(mut self) -> FunctionBody {
+        let guards = self.func.analyzer_func(self.db).guards(self.db.upcast());
+        if let Some(access_control) = &guards.access_control {
+            self.lower_access_control(access_control);
+        }
+        if let Some(error) = guards.when_not_paused {
+            self.lower_pause_check(error);
+        }
+        if let Some(error) = guards.nonreentrant {
+            self.lower_reentrancy_lock(error);
+        }
+
         for stmt in &self.ast.kind.body {
             self.lower_stmt(stmt)
         }
@@ -611,12 +657,130 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
         let last_block = self.builder.current_block();
         if !self.builder.is_block_terminated(last_block) {
             let unit = self.make_unit();
+            self.release_reentrancy_lock();
             self.builder.ret(unit, SourceInfo::dummy());
         }
 
         self.builder.build()
     }
 
+    /// Reverts with `std::error::Unauthorized` unless the caller is the
+    /// address stored in the field of the `#only(field)` attribute, or is
+    /// mapped to `true` by it.
+    fn lower_access_control(&mut self, access_control: &analyzer_items::AccessControl) {
+        let field = self.contract_field(&access_control.field);
+        let address_ty = self
+            .db
+            .mir_lowered_type(analyzer_types::TypeId::address(self.db.upcast()));
+        let caller =
+            self.builder
+                .yul_intrinsic(YulIntrinsicOp::Caller, vec![], SourceInfo::dummy());
+        let caller = self.map_to_tmp(caller, address_ty);
+
+        let ok_bb = self.builder.make_block();
+        let err_bb = self.builder.make_block();
+        if access_control.is_role {
+            let bool_ty = self.bool_ty();
+            let role = self.builder.map_access(field, caller, SourceInfo::dummy());
+            let role = self.map_to_tmp(role, bool_ty.make_sptr(self.db));
+            let is_allowed = self.builder.load(role, SourceInfo::dummy());
+            let is_allowed = self.map_to_tmp(is_allowed, bool_ty);
+            self.builder
+                .branch(is_allowed, ok_bb, err_bb, SourceInfo::dummy());
+        } else {
+            let owner = self.builder.load(field, SourceInfo::dummy());
+            let owner = self.map_to_tmp(owner, address_ty);
+            self.branch_eq(owner, caller, ok_bb, err_bb, SourceInfo::dummy());
+        }
+
+        self.builder.move_to_block(err_bb);
+        self.revert_with(access_control.error, vec![caller]);
+        self.builder.move_to_block(ok_bb);
+    }
+
+    /// Reverts with `std::error::Paused` while the `paused` field of the
+    /// contract is `true`.
+    fn lower_pause_check(&mut self, error: analyzer_items::StructId) {
+        let field = self.contract_field(PAUSED);
+        let bool_ty = self.bool_ty();
+        let paused = self.builder.load(field, SourceInfo::dummy());
+        let paused = self.map_to_tmp(paused, bool_ty);
+
+        let ok_bb = self.builder.make_block();
+        let err_bb = self.builder.make_block();
+        self.builder
+            .branch(paused, err_bb, ok_bb, SourceInfo::dummy());
+
+        self.builder.move_to_block(err_bb);
+        self.revert_with(error, vec![]);
+        self.builder.move_to_block(ok_bb);
+    }
+
+    /// Reverts with `std::error::ReentrantCall` if the contract is locked,
+    /// and locks it otherwise. The lock is released before every return. It's
+    /// kept in transient storage, which falls back to storage on EVM versions
+    /// before Cancun.
+    fn lower_reentrancy_lock(&mut self, error: analyzer_items::StructId) {
+        let slot = self.make_u256_imm(reentrancy_lock_slot());
+        let lock =
+            self.builder
+                .yul_intrinsic(YulIntrinsicOp::Tload, vec![slot], SourceInfo::dummy());
+        let u256_ty = self.u256_ty();
+        let lock = self.map_to_tmp(lock, u256_ty);
+
+        let ok_bb = self.builder.make_block();
+        let err_bb = self.builder.make_block();
+        let unlocked = self.make_u256_imm(0);
+        self.branch_eq(lock, unlocked, ok_bb, err_bb, SourceInfo::dummy());
+
+        self.builder.move_to_block(err_bb);
+        self.revert_with(error, vec![]);
+
+        self.builder.move_to_block(ok_bb);
+        let locked = self.make_u256_imm(1);
+        self.builder.yul_intrinsic(
+            YulIntrinsicOp::Tstore,
+            vec![slot, locked],
+            SourceInfo::dummy(),
+        );
+        self.is_nonreentrant = true;
+    }
+
+    fn release_reentrancy_lock(&mut self) {
+        if self.is_nonreentrant {
+            let slot = self.make_u256_imm(reentrancy_lock_slot());
+            let unlocked = self.make_u256_imm(0);
+            self.builder.yul_intrinsic(
+                YulIntrinsicOp::Tstore,
+                vec![slot, unlocked],
+                SourceInfo::dummy(),
+            );
+        }
+    }
+
+    /// Returns a storage pointer to the field of the contract of `self`.
+    fn contract_field(&mut self, name: &str) -> ValueId {
+        let self_value = self.resolve_name("self");
+        let self_ty = self.builder.value_ty(self_value);
+        let index = self_ty.index_from_fname(self.db, name);
+        let index = self.make_u256_imm(index);
+        let field_ty = self_ty.projection_ty(self.db, self.builder.value_data(index));
+        let field = self
+            .builder
+            .aggregate_access(self_value, vec![index], SourceInfo::dummy());
+        self.map_to_tmp(field, field_ty)
+    }
+
+    /// Reverts with the error struct constructed from `args`.
+    fn revert_with(&mut self, error: analyzer_items::StructId, args: Vec<ValueId>) {
+        let error_ty = self.db.mir_lowered_type(error.as_type(self.db.upcast()));
+        let error = self
+            .builder
+            .aggregate_construct(error_ty, args, SourceInfo::dummy());
+        let error = self.map_to_tmp(error, error_ty);
+        self.builder.revert(Some(error), SourceInfo::dummy());
+    }
+
     fn branch_eq(
         &mut self,
         v1: ValueId,
@@ -980,6 +1144,8 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
                 match method {
                     ValueMethod::ToMem => self.builder.mem_copy(arg, source),
                     ValueMethod::AbiEncode => self.builder.abi_encode(arg, source),
+                    ValueMethod::LeadingZeros => self.builder.leading_zeros(arg, source),
+                    ValueMethod::CountOnes => self.builder.count_ones(arg, source),
                 }
             }
 
@@ -1023,22 +1189,7 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
                 let mut method_args = vec![self.lower_method_receiver(func)];
                 method_args.append(&mut args);
 
-                let concrete_type = self
-                    .func
-                    .signature(self.db)
-                    .resolved_generics
-                    .get(&generic_type.name)
-                    .cloned()
-                    .expect("unresolved generic type");
-
-                let impl_ = concrete_type
-                    .get_impl_for(self.db.upcast(), *trait_id)
-                    .expect("missing impl");
-
-                let function = impl_
-                    .function(self.db.upcast(), &method.name(self.db.upcast()))
-                    .expect("missing function");
-
+                let function = self.resolve_trait_method(*trait_id, *method, generic_type);
                 let func_id = self.db.mir_lowered_func_signature(function);
                 self.builder
                     .call(func_id, method_args, CallType::Internal, source)
@@ -1089,6 +1240,59 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
         }
     }
 
+    /// Returns the function of the `impl` of the trait for the type that the
+    /// generic parameter is resolved to in the current function.
+    fn resolve_trait_method(
+        &self,
+        trait_id: analyzer_items::TraitId,
+        method: analyzer_items::FunctionSigId,
+        generic_type: &analyzer_types::Generic,
+    ) -> analyzer_items::FunctionId {
+        let concrete_type = self
+            .func
+            .signature(self.db)
+            .resolved_generics
+            .get(&generic_type.name)
+            .cloned()
+            .expect("unresolved generic type");
+
+        let impl_ = concrete_type
+            .get_impl_for(self.db.upcast(), trait_id)
+            .expect("missing impl");
+
+        impl_
+            .function(self.db.upcast(), &method.name(self.db.upcast()))
+            .expect("missing function")
+    }
+
+    /// Lowers an arithmetic operation that a `std::ops` trait overloads to a
+    /// call of the trait method on the left operand.
+    fn lower_overloaded_binop(
+        &mut self,
+        call_type: &AnalyzerCallType,
+        left: &Node<ast::Expr>,
+        right: &Node<ast::Expr>,
+        source: SourceInfo,
+    ) -> InstId {
+        let lhs = self.lower_expr_to_value(left);
+        let rhs = self.lower_expr_to_value(right);
+
+        let func_id = match call_type {
+            AnalyzerCallType::ValueMethod { method, .. } => self.lower_function_id(method, &[rhs]),
+            AnalyzerCallType::TraitValueMethod {
+                trait_id,
+                method,
+                generic_type,
+            } => {
+                let function = self.resolve_trait_method(*trait_id, *method, generic_type);
+                self.db.mir_lowered_func_signature(function)
+            }
+            _ => unreachable!("operators are only overloaded by trait methods"),
+        };
+        self.builder
+            .call(func_id, vec![lhs, rhs], CallType::Internal, source)
+    }
+
     // FIXME: This is ugly hack to properly analyze method call. Remove this when  https://github.com/ethereum/fe/issues/670 is resolved.
     fn lower_method_receiver(&mut self, receiver: &Node<ast::Expr>) -> ValueId {
         match &receiver.kind {
@@ -40,6 +40,7 @@ pub fn lower_type(db: &dyn MirDb, analyzer_ty: analyzer_types::TypeId) -> TypeId
         analyzer_types::Type::Generic(_) => {
             panic!("should be lowered in `lower_analyzer_type`")
         }
+        analyzer_types::Type::Error => panic!("modules with errors aren't lowered"),
     };
 
     intern_type(db, ty_kind, Some(analyzer_ty.deref(db.upcast())))
@@ -101,15 +102,23 @@ fn lower_contract(db: &dyn MirDb, contract: analyzer_items::ContractId) -> TypeK
     let name = contract.name(db.upcast());
 
     // Note: contract field types are wrapped in SPtr in TypeId::projection_ty
-    let fields = contract
-        .fields(db.upcast())
-        .iter()
-        .map(|(fname, fid)| {
-            let analyzer_type = fid.typ(db.upcast()).unwrap();
-            let ty = db.mir_lowered_type(analyzer_type);
-            (fname.clone(), ty)
-        })
-        .collect();
+    let mut fields = vec![];
+    for (fname, fid) in contract.fields(db.upcast()).iter() {
+        // A storage gap is lowered to an unnamed array of slots right before
+        // the field.
+        if let Some(slots) = fid.storage_gap(db.upcast()) {
+            let gap = analyzer_types::Type::Array(analyzer_types::Array {
+                inner: analyzer_types::TypeId::int(db.upcast(), analyzer_types::Integer::U256),
+                size: slots,
+            });
+            let ty = db.mir_lowered_type(gap.id(db.upcast()));
+            fields.push((format!("$gap_{fname}").into(), ty));
+        }
+
+        let analyzer_type = fid.typ(db.upcast()).unwrap();
+        let ty = db.mir_lowered_type(analyzer_type);
+        fields.push((fname.clone(), ty));
+    }
 
     // Obtain span.
     let span = contract.span(db.upcast());
@@ -25,10 +25,18 @@ pub enum ModuleStmt {
     Trait(Node<Trait>),
     Impl(Node<Impl>),
     Function(Node<Function>),
-    Attribute(Node<SmolStr>),
+    Attribute(Node<Attribute>),
     ParseError(Span),
 }
 
+/// An attribute of the item that follows it, e.g. `#test` or
+/// `#storage_gap(48)`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Attribute {
+    pub name: Node<SmolStr>,
+    pub args: Option<Node<Vec<Node<Expr>>>>,
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
 pub struct Pragma {
     pub version_requirement: Node<SmolStr>,
@@ -110,6 +118,8 @@ pub struct Enum {
 pub struct Trait {
     pub name: Node<SmolStr>,
     pub functions: Vec<Node<FunctionSignature>>,
+    /// The functions of `functions` that have a default body.
+    pub default_fns: Vec<Node<Function>>,
     pub pub_qual: Option<Span>,
 }
 
@@ -138,6 +148,10 @@ pub enum TypeDesc {
         args: Node<Vec<GenericArg>>,
     },
     SelfType,
+    /// A reference to a value in storage, e.g. `&storage MyStruct`.
+    StorageRef {
+        inner: Box<Node<TypeDesc>>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
@@ -162,7 +176,7 @@ pub enum GenericParameter {
     Unbounded(Node<SmolStr>),
     Bounded {
         name: Node<SmolStr>,
-        bound: Node<TypeDesc>,
+        bounds: Vec<Node<TypeDesc>>,
     },
 }
 
@@ -183,7 +197,7 @@ impl Spanned for GenericParameter {
     fn span(&self) -> Span {
         match self {
             GenericParameter::Unbounded(node) => node.span,
-            GenericParameter::Bounded { name, bound } => name.span + bound.span,
+            GenericParameter::Bounded { name, bounds } => name.span + bounds.last(),
         }
     }
 }
@@ -193,7 +207,7 @@ impl Spanned for GenericParameter {
 pub struct Field {
     pub is_pub: bool,
     pub is_const: bool,
-    pub attributes: Vec<Node<SmolStr>>,
+    pub attributes: Vec<Node<Attribute>>,
     pub name: Node<SmolStr>,
     pub typ: Node<TypeDesc>,
     pub value: Option<Node<Expr>>,
@@ -236,6 +250,11 @@ pub enum VariantKind {
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
 pub enum ContractStmt {
     Function(Node<Function>),
+    /// `impl SomeTrait for Self { .. }` inside of a contract body. The functions
+    /// of the block become public functions of the contract.
+    Impl(Node<Impl>),
+    /// An attribute of the function that follows it, e.g. `#only(owner)`.
+    Attribute(Node<Attribute>),
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
@@ -585,6 +604,8 @@ impl Spanned for ContractStmt {
     fn span(&self) -> Span {
         match self {
             ContractStmt::Function(inner) => inner.span,
+            ContractStmt::Impl(inner) => inner.span,
+            ContractStmt::Attribute(inner) => inner.span,
         }
     }
 }
@@ -604,7 +625,12 @@ impl fmt::Display for Module {
         let mut delim = "";
         for stmt in rest {
             writeln!(f, "{delim}{stmt}")?;
-            delim = "\n";
+            // Attributes are kept right above the item that they belong to.
+            delim = if matches!(stmt, ModuleStmt::Attribute(_)) {
+                ""
+            } else {
+                "\n"
+            };
         }
         Ok(())
     }
@@ -623,7 +649,7 @@ impl fmt::Display for ModuleStmt {
             ModuleStmt::Struct(node) => write!(f, "{}", node.kind),
             ModuleStmt::Enum(node) => write!(f, "{}", node.kind),
             ModuleStmt::Function(node) => write!(f, "{}", node.kind),
-            ModuleStmt::Attribute(node) => writeln!(f, "#{}", node.kind),
+            ModuleStmt::Attribute(node) => write!(f, "{}", node.kind),
             ModuleStmt::ParseError(span) => {
                 write!(f, "# PARSE ERROR: {}..{}", span.start, span.end)
             }
@@ -631,6 +657,16 @@ impl fmt::Display for ModuleStmt {
     }
 }
 
+impl fmt::Display for Attribute {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.name.kind)?;
+        if let Some(args) = &self.args {
+            write!(f, "({})", node_comma_joined(&args.kind))?;
+        }
+        Ok(())
+    }
+}
+
 impl fmt::Display for Pragma {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "pragma {}", self.version_requirement.kind)
@@ -693,21 +729,55 @@ impl fmt::Display for ConstantDecl {
 
 impl fmt::Display for Trait {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        writeln!(f, "trait {}:", self.name.kind)?;
+        let Trait {
+            name,
+            functions,
+            default_fns,
+            pub_qual,
+        } = self;
 
-        Ok(())
+        if pub_qual.is_some() {
+            write!(f, "pub ")?;
+        }
+        write!(f, "trait {} {{", name.kind)?;
+
+        if !functions.is_empty() {
+            let functions: Vec<_> = functions
+                .iter()
+                .map(|sig| {
+                    match default_fns
+                        .iter()
+                        .find(|func| func.kind.sig.kind.name.kind == sig.kind.name.kind)
+                    {
+                        Some(func) => func.kind.to_string(),
+                        None => format!("{};", sig.kind),
+                    }
+                })
+                .collect();
+            writeln!(f)?;
+            write!(indented(f), "{}", double_line_joined(&functions))?;
+            writeln!(f)?;
+        }
+        write!(f, "}}")
     }
 }
 
 impl fmt::Display for Impl {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        writeln!(
-            f,
-            "impl {} for {}",
-            self.impl_trait.kind, self.receiver.kind
-        )?;
+        let Impl {
+            impl_trait,
+            receiver,
+            functions,
+        } = self;
 
-        Ok(())
+        write!(f, "impl {} for {} {{", impl_trait.kind, receiver.kind)?;
+
+        if !functions.is_empty() {
+            writeln!(f)?;
+            write!(indented(f), "{}", double_line_joined(functions))?;
+            writeln!(f)?;
+        }
+        write!(f, "}}")
     }
 }
 
@@ -771,10 +841,8 @@ impl fmt::Display for Struct {
         write!(f, "{{")?;
         write_nodes_line_wrapped(&mut indented(f), fields)?;
 
-        if !self.fields.is_empty() && !functions.is_empty() {
-            writeln!(f)?;
-        }
         if !functions.is_empty() {
+            writeln!(f)?;
             writeln!(indented(f), "{}", double_line_joined(functions))?;
         }
         write!(f, "}}")
@@ -799,6 +867,7 @@ impl fmt::Display for Enum {
         write_nodes_line_wrapped(&mut indented(f), variants)?;
 
         if !functions.is_empty() {
+            writeln!(f)?;
             writeln!(indented(f), "{}", double_line_joined(functions))?;
         }
         write!(f, "}}")
@@ -822,6 +891,7 @@ impl fmt::Display for TypeDesc {
                 write!(f, "{}<{}>", base.kind, comma_joined(args.kind.iter()))
             }
             TypeDesc::SelfType => write!(f, "Self"),
+            TypeDesc::StorageRef { inner } => write!(f, "&storage {}", inner.kind),
         }
     }
 }
@@ -830,7 +900,14 @@ impl fmt::Display for GenericParameter {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             GenericParameter::Unbounded(name) => write!(f, "{}", name.kind),
-            GenericParameter::Bounded { name, bound } => write!(f, "{}: {}", name.kind, bound.kind),
+            GenericParameter::Bounded { name, bounds } => {
+                let bounds = bounds
+                    .iter()
+                    .map(|bound| bound.kind.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" + ");
+                write!(f, "{}: {}", name.kind, bounds)
+            }
         }
     }
 }
@@ -847,13 +924,20 @@ impl fmt::Display for GenericArg {
 
 impl fmt::Display for Field {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for attr in &self.attributes {
+            writeln!(f, "{}", attr.kind)?;
+        }
         if self.is_pub {
             write!(f, "pub ")?;
         }
         if self.is_const {
             write!(f, "const ")?;
         }
-        write!(f, "{}: {}", self.name.kind, self.typ.kind)
+        write!(f, "{}: {}", self.name.kind, self.typ.kind)?;
+        if let Some(value) = &self.value {
+            write!(f, " = {}", value.kind)?;
+        }
+        Ok(())
     }
 }
 
@@ -873,6 +957,8 @@ impl fmt::Display for ContractStmt {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             ContractStmt::Function(node) => write!(f, "{}", node.kind),
+            ContractStmt::Impl(node) => write!(f, "{}", node.kind),
+            ContractStmt::Attribute(node) => write!(f, "{}", node.kind),
         }
     }
 }
@@ -884,6 +970,14 @@ impl fmt::Display for Node<Function> {
 }
 
 impl fmt::Display for Function {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {{", self.sig.kind)?;
+        write_nodes_line_wrapped(&mut indented(f), &self.body)?;
+        write!(f, "}}")
+    }
+}
+
+impl fmt::Display for FunctionSignature {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let FunctionSignature {
             pub_,
@@ -892,7 +986,7 @@ impl fmt::Display for Function {
             generic_params,
             args,
             return_type,
-        } = &self.sig.kind;
+        } = self;
 
         if pub_.is_some() {
             write!(f, "pub ")?;
@@ -909,9 +1003,7 @@ impl fmt::Display for Function {
         if let Some(return_type) = return_type.as_ref() {
             write!(f, " -> {}", return_type.kind)?;
         }
-        write!(f, " {{")?;
-        write_nodes_line_wrapped(&mut indented(f), &self.body)?;
-        write!(f, "}}")
+        Ok(())
     }
 }
 
@@ -1125,7 +1217,7 @@ impl fmt::Display for Expr {
             Expr::Name(name) => write!(f, "{name}"),
             Expr::Path(path) => write!(f, "{path}"),
             Expr::Num(num) => write!(f, "{num}"),
-            Expr::Str(str) => write!(f, "\"{str}\""),
+            Expr::Str(str) => write!(f, "\"{}\"", escape_string(str)),
             Expr::Unit => write!(f, "()"),
         }
     }
@@ -1253,6 +1345,22 @@ impl fmt::Display for LiteralPattern {
     }
 }
 
+/// Reverses the unescaping of string literals by the parser.
+fn escape_string(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len());
+    for c in string.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 fn node_comma_joined(nodes: &[Node<impl fmt::Display>]) -> String {
     node_delim_joined(nodes, ", ")
 }
@@ -1,9 +1,11 @@
 use super::functions::parse_fn_def;
-use super::types::{parse_field, parse_opt_qualifier};
+use super::module::parse_attribute;
+use super::types::{parse_field, parse_impl_def, parse_opt_qualifier};
 
-use crate::ast::{Contract, ContractStmt};
+use crate::ast::{Contract, ContractStmt, TypeDesc};
 use crate::node::{Node, Span};
-use crate::{ParseFailed, ParseResult, Parser, TokenKind};
+use crate::{Label, ParseFailed, ParseResult, Parser, TokenKind};
+use fe_common::edition::Feature;
 
 // Rule: all "statement" level parse functions consume their trailing
 // newline(s), either directly or via a function they call.
@@ -30,6 +32,8 @@ pub fn parse_contract_def(
 
     let mut fields = vec![];
     let mut defs = vec![];
+    // Attributes apply to the field or function that follows them.
+    let mut attributes = vec![];
 
     loop {
         par.eat_newlines();
@@ -43,9 +47,15 @@ pub fn parse_contract_def(
             );
         }
 
-        match par.peek_or_err()? {
+        let next = par.peek_or_err()?;
+        if !matches!(next, TokenKind::Name | TokenKind::Hash) {
+            defs.extend(attributes.drain(..).map(ContractStmt::Attribute));
+        }
+
+        match next {
             TokenKind::Name => {
-                let field = parse_field(par, vec![], pub_qual, const_qual)?;
+                let field =
+                    parse_field(par, std::mem::take(&mut attributes), pub_qual, const_qual)?;
                 if !defs.is_empty() {
                     par.error(
                         field.span,
@@ -63,6 +73,36 @@ pub fn parse_contract_def(
                 }
                 defs.push(ContractStmt::Function(parse_fn_def(par, pub_qual)?));
             }
+            TokenKind::Impl => {
+                if let Some(span) = pub_qual.or(const_qual) {
+                    par.error(span, "`impl` blocks can't have qualifiers");
+                }
+                let impl_def = parse_impl_def(par)?;
+                par.feature_gate(
+                    Feature::ContractTraitImpls,
+                    impl_def.kind.impl_trait.span + impl_def.kind.receiver.span,
+                );
+                if !matches!(impl_def.kind.receiver.kind, TypeDesc::SelfType) {
+                    par.fancy_error(
+                        "`impl` blocks inside of a contract must be for `Self`",
+                        vec![Label::primary(
+                            impl_def.kind.receiver.span,
+                            "replace this with `Self`",
+                        )],
+                        vec![format!(
+                            "Example: `impl {} for Self {{ ... }}`",
+                            impl_def.kind.impl_trait.kind
+                        )],
+                    );
+                }
+                defs.push(ContractStmt::Impl(impl_def));
+            }
+            TokenKind::Hash => {
+                if let Some(span) = pub_qual.or(const_qual) {
+                    par.error(span, "attributes can't have qualifiers");
+                }
+                attributes.push(parse_attribute(par)?);
+            }
             TokenKind::BraceClose => {
                 span += par.next()?.span;
                 break;
@@ -389,7 +389,7 @@ fn parse_group_or_tuple(par: &mut Parser) -> ParseResult<Node<Expr>> {
 
 /// Parse some number of comma-separated expressions, until `end_marker` is
 /// `peek()`ed.
-fn parse_expr_list(
+pub fn parse_expr_list(
     par: &mut Parser,
     end_markers: &[TokenKind],
     head: Option<Node<Expr>>,
@@ -7,6 +7,7 @@ use crate::ast::{
 };
 use crate::node::{Node, Span};
 use crate::{Label, ParseFailed, ParseResult, Parser, TokenKind};
+use fe_common::edition::Feature;
 
 /// Parse a function definition without a body. The optional `pub` qualifier
 /// must be parsed by the caller, and passed in. Next token must be `unsafe` or
@@ -105,7 +106,16 @@ pub fn parse_fn_sig(
 /// the caller, and passed in. Next token must be `unsafe` or `fn`.
 pub fn parse_fn_def(par: &mut Parser, pub_qual: Option<Span>) -> ParseResult<Node<Function>> {
     let sig = parse_fn_sig(par, pub_qual)?;
+    parse_fn_body(par, sig)
+}
 
+/// Parse the body of a function with the given signature.
+/// # Panics
+/// Panics if the next token isn't `{`.
+pub fn parse_fn_body(
+    par: &mut Parser,
+    sig: Node<FunctionSignature>,
+) -> ParseResult<Node<Function>> {
     // TODO: allow multi-line return type? `fn f()\n ->\n u8`
     par.enter_block(sig.span, "function definition")?;
     let body = parse_block_stmts(par)?;
@@ -121,7 +131,8 @@ pub fn parse_fn_def(par: &mut Parser, pub_qual: Option<Span>) -> ParseResult<Nod
 }
 
 /// Parse a single generic function parameter (eg. `T:SomeTrait` in `fn foo<T:
-/// SomeTrait>(some_arg: u256) -> bool`). # Panics
+/// SomeTrait>(some_arg: u256) -> bool`). Multiple bounds may be joined with `+`
+/// (eg. `T: SomeTrait + OtherTrait`). # Panics
 /// Panics if the first token isn't `Name`.
 pub fn parse_generic_param(par: &mut Parser) -> ParseResult<GenericParameter> {
     use TokenKind::*;
@@ -129,15 +140,25 @@ pub fn parse_generic_param(par: &mut Parser) -> ParseResult<GenericParameter> {
     let name = par.assert(Name);
     match par.optional(Colon) {
         Some(_) => {
-            let bound = par.expect(TokenKind::Name, "failed to parse generic bound")?;
-            Ok(GenericParameter::Bounded {
-                name: Node::new(name.text.into(), name.span),
-                bound: Node::new(
+            let mut bounds = vec![];
+            loop {
+                let bound = par.expect(TokenKind::Name, "failed to parse generic bound")?;
+                bounds.push(Node::new(
                     TypeDesc::Base {
                         base: bound.text.into(),
                     },
                     bound.span,
-                ),
+                ));
+                if par.optional(Plus).is_none() {
+                    break;
+                }
+            }
+            if let [first, .., last] = bounds.as_slice() {
+                par.feature_gate(Feature::MultipleBounds, first.span + last.span);
+            }
+            Ok(GenericParameter::Bounded {
+                name: Node::new(name.text.into(), name.span),
+                bounds,
             })
         }
         None => Ok(GenericParameter::Unbounded(Node::new(
@@ -1,11 +1,11 @@
-use super::expressions::parse_expr;
+use super::expressions::{parse_expr, parse_expr_list};
 use super::functions::parse_fn_def;
 use super::types::{
     parse_impl_def, parse_path_tail, parse_struct_def, parse_trait_def, parse_type_alias,
     parse_type_desc,
 };
 use super::{contracts::parse_contract_def, types::parse_enum_def};
-use crate::ast::{ConstantDecl, Module, ModuleStmt, Pragma, Use, UseTree};
+use crate::ast::{Attribute, ConstantDecl, Module, ModuleStmt, Pragma, Use, UseTree};
 use crate::node::{Node, Span};
 use crate::{Label, ParseFailed, ParseResult, Parser, TokenKind};
 
@@ -74,12 +74,7 @@ pub fn parse_module_stmt(par: &mut Parser) -> ParseResult<ModuleStmt> {
             }
         }
         TokenKind::Fn | TokenKind::Unsafe => ModuleStmt::Function(parse_fn_def(par, None)?),
-        TokenKind::Hash => {
-            let attr = par.expect(TokenKind::Hash, "expected `#`")?;
-            let attr_name = par.expect_with_notes(TokenKind::Name, "failed to parse attribute definition", |_|
-                vec!["Note: an attribute name must start with a letter or underscore, and contain letters, numbers, or underscores".into()])?;
-            ModuleStmt::Attribute(Node::new(attr_name.text.into(), attr.span + attr_name.span))
-        }
+        TokenKind::Hash => ModuleStmt::Attribute(parse_attribute(par)?),
         _ => {
             let tok = par.next()?;
             par.unexpected_token_error(
@@ -93,6 +88,37 @@ pub fn parse_module_stmt(par: &mut Parser) -> ParseResult<ModuleStmt> {
     Ok(stmt)
 }
 
+/// Parse an attribute, e.g. `#test` or `#storage_gap(48)`.
+/// # Panics
+/// Panics if the next token isn't `#`.
+pub fn parse_attribute(par: &mut Parser) -> ParseResult<Node<Attribute>> {
+    let hash = par.assert(TokenKind::Hash);
+    let name = par.expect_with_notes(TokenKind::Name, "failed to parse attribute definition", |_|
+        vec!["Note: an attribute name must start with a letter or underscore, and contain letters, numbers, or underscores".into()])?;
+    let mut span = hash.span + name.span;
+
+    let args = if par.peek() == Some(TokenKind::ParenOpen) {
+        let lparen = par.next()?;
+        let args = parse_expr_list(par, &[TokenKind::ParenClose], None)?;
+        let rparen = par.expect(
+            TokenKind::ParenClose,
+            "failed to parse attribute argument list",
+        )?;
+        span += rparen.span;
+        Some(Node::new(args, lparen.span + rparen.span))
+    } else {
+        None
+    };
+
+    Ok(Node::new(
+        Attribute {
+            name: Node::new(name.text.into(), name.span),
+            args,
+        },
+        span,
+    ))
+}
+
 /// Parse a constant, e.g. `const MAGIC_NUMBER: u256 = 4711`.
 /// # Panics
 /// Panics if the next token isn't `const`.
@@ -1,12 +1,15 @@
 use crate::ast::{
-    self, Enum, Field, GenericArg, Impl, Path, Trait, TypeAlias, TypeDesc, Variant, VariantKind,
+    self, Attribute, Enum, Field, GenericArg, Impl, Path, Trait, TypeAlias, TypeDesc, Variant,
+    VariantKind,
 };
 use crate::grammar::expressions::parse_expr;
-use crate::grammar::functions::{parse_fn_def, parse_fn_sig};
+use crate::grammar::functions::{parse_fn_body, parse_fn_def, parse_fn_sig};
+use crate::grammar::module::parse_attribute;
 use crate::node::{Node, Span};
 use crate::Token;
 use crate::{ParseFailed, ParseResult, Parser, TokenKind};
 use fe_common::diagnostics::Label;
+use fe_common::edition::Feature;
 use if_chain::if_chain;
 use smol_str::SmolStr;
 use vec1::Vec1;
@@ -31,11 +34,9 @@ pub fn parse_struct_def(
     loop {
         par.eat_newlines();
 
-        let attributes = if let Some(attr) = par.optional(TokenKind::Hash) {
-            let attr_name = par.expect_with_notes(TokenKind::Name, "failed to parse attribute definition", |_|
-                vec!["Note: an attribute name must start with a letter or underscore, and contain letters, numbers, or underscores".into()])?;
+        let attributes = if par.peek() == Some(TokenKind::Hash) {
             // This hints to a future where we would support multiple attributes per field. For now we don't need it.
-            vec![Node::new(attr_name.text.into(), attr.span + attr_name.span)]
+            vec![parse_attribute(par)?]
         } else {
             vec![]
         };
@@ -169,18 +170,23 @@ pub fn parse_trait_def(par: &mut Parser, pub_qual: Option<Span>) -> ParseResult<
 
     let header_span = trait_tok.span + trait_name.span;
     let mut functions = vec![];
+    let mut default_fns = vec![];
     par.enter_block(header_span, "trait definition")?;
 
     loop {
         match par.peek_or_err()? {
             TokenKind::Fn => {
-                // TODO: Traits should also be allowed to have functions that do contain a body.
-                functions.push(parse_fn_sig(par, None)?);
-                par.expect_with_notes(
-                    TokenKind::Semi,
-                    "failed to parse trait definition",
-                    |_| vec!["Note: trait functions must appear without body and followed by a semicolon.".into()],
-                )?;
+                let sig = parse_fn_sig(par, None)?;
+                functions.push(sig.clone());
+                if par.peek() == Some(TokenKind::BraceOpen) {
+                    default_fns.push(parse_fn_body(par, sig)?);
+                } else {
+                    par.expect_with_notes(
+                        TokenKind::Semi,
+                        "failed to parse trait definition",
+                        |_| vec!["Note: trait functions must be followed by a semicolon, or by a default body.".into()],
+                    )?;
+                }
                 par.eat_newlines();
             }
             TokenKind::BraceClose => {
@@ -200,6 +206,7 @@ pub fn parse_trait_def(par: &mut Parser, pub_qual: Option<Span>) -> ParseResult<
         Trait {
             name: Node::new(trait_name.text.into(), trait_name.span),
             functions,
+            default_fns,
             pub_qual,
         },
         span,
@@ -290,7 +297,7 @@ pub fn parse_type_alias(par: &mut Parser, pub_qual: Option<Span>) -> ParseResult
 /// `const` qualifiers must be parsed by the caller, and passed in.
 pub fn parse_field(
     par: &mut Parser,
-    attributes: Vec<Node<SmolStr>>,
+    attributes: Vec<Node<Attribute>>,
     pub_qual: Option<Span>,
     const_qual: Option<Span>,
 ) -> ParseResult<Node<Field>> {
@@ -529,6 +536,30 @@ pub fn parse_type_desc(par: &mut Parser) -> ParseResult<Node<TypeDesc>> {
             let _self = par.next()?;
             Node::new(TypeDesc::SelfType, _self.span)
         }
+        Amper => {
+            let amper = par.next()?;
+            let location = par.expect(Name, "failed to parse storage reference type")?;
+            if location.text != "storage" {
+                par.fancy_error(
+                    "failed to parse storage reference type",
+                    vec![Label::primary(location.span, "expected `storage`")],
+                    vec![
+                        "Note: only references to storage are supported, e.g. `&storage MyStruct`"
+                            .into(),
+                    ],
+                );
+                return Err(ParseFailed);
+            }
+            par.feature_gate(Feature::StorageReferences, amper.span + location.span);
+            let inner = parse_type_desc(par)?;
+            let span = amper.span + inner.span;
+            Node::new(
+                TypeDesc::StorageRef {
+                    inner: Box::new(inner),
+                },
+                span,
+            )
+        }
         Name => {
             let name = par.next()?;
             match par.peek() {
@@ -570,6 +601,7 @@ pub fn parse_type_desc(par: &mut Parser) -> ParseResult<Node<TypeDesc>> {
         ParenOpen => {
             let mut span = par.next()?.span;
             let mut items = vec![];
+            let mut trailing_comma = false;
             loop {
                 match par.peek_or_err()? {
                     ParenClose => {
@@ -577,11 +609,12 @@ pub fn parse_type_desc(par: &mut Parser) -> ParseResult<Node<TypeDesc>> {
                         break;
                     }
 
-                    Name | ParenOpen => {
+                    Name | ParenOpen | SelfType => {
                         let item = parse_type_desc(par)?;
                         span += item.span;
                         items.push(item);
-                        if par.peek_or_err()? == Comma {
+                        trailing_comma = par.peek_or_err()? == Comma;
+                        if trailing_comma {
                             par.next()?;
                         } else {
                             span += par
@@ -607,6 +640,9 @@ pub fn parse_type_desc(par: &mut Parser) -> ParseResult<Node<TypeDesc>> {
             }
             if items.is_empty() {
                 Node::new(TypeDesc::Unit, span)
+            } else if items.len() == 1 && !trailing_comma {
+                // `(T)` is a parenthesized `T`; a single element tuple is written `(T,)`.
+                Node::new(items.remove(0).kind, span)
             } else {
                 Node::new(
                     TypeDesc::Tuple {
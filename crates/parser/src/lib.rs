@@ -5,9 +5,11 @@ pub use lexer::{Token, TokenKind};
 mod parser;
 pub use parser::{Label, ParseFailed, ParseResult, Parser};
 pub mod node;
+pub mod ssr;
 
 use ast::Module;
 use fe_common::diagnostics::Diagnostic;
+use fe_common::edition::Edition;
 use fe_common::files::SourceFileId;
 
 /// Parse a [`Module`] from the file content string.
@@ -24,7 +26,18 @@ use fe_common::files::SourceFileId;
 /// A [`SourceFileId`] is required to associate any diagnostics with the
 /// underlying file.
 pub fn parse_file(file_id: SourceFileId, src: &str) -> (Module, Vec<Diagnostic>) {
+    parse_file_with_edition(file_id, src, Edition::LATEST)
+}
+
+/// Parse a [`Module`] from the file content string, rejecting the syntax that
+/// `edition` doesn't support. See [`parse_file`].
+pub fn parse_file_with_edition(
+    file_id: SourceFileId,
+    src: &str,
+    edition: Edition,
+) -> (Module, Vec<Diagnostic>) {
     let mut parser = Parser::new(file_id, src);
+    parser.edition = edition;
     let node = crate::grammar::module::parse_module(&mut parser);
     (node.kind, parser.diagnostics)
 }
@@ -1,5 +1,6 @@
 pub use fe_common::diagnostics::Label;
 use fe_common::diagnostics::{Diagnostic, Severity};
+use fe_common::edition::{Edition, Feature};
 use fe_common::files::SourceFileId;
 
 use crate::lexer::{Lexer, Token, TokenKind};
@@ -24,6 +25,8 @@ pub type ParseResult<T> = Result<T, ParseFailed>;
 /// See [`BTParser`] if you need backtrackable parser.
 pub struct Parser<'a> {
     pub file_id: SourceFileId,
+    /// The edition of the code, which determines the syntax that is accepted.
+    pub edition: Edition,
     lexer: Lexer<'a>,
 
     /// Tokens that have been "peeked", or split from a larger token.
@@ -42,6 +45,7 @@ impl<'a> Parser<'a> {
     pub fn new(file_id: SourceFileId, content: &'a str) -> Self {
         Parser {
             file_id,
+            edition: Edition::LATEST,
             lexer: Lexer::new(file_id, content),
             buffered: vec![],
             enclosure_stack: vec![],
@@ -313,6 +317,7 @@ impl<'a> Parser<'a> {
             message: message.into(),
             labels: vec![Label::primary(span, "")],
             notes: vec![],
+            suggestions: vec![],
         })
     }
 
@@ -329,8 +334,17 @@ impl<'a> Parser<'a> {
             message: message.into(),
             labels,
             notes,
+            suggestions: vec![],
         })
     }
+
+    /// Emit an error if the edition of the code doesn't support `feature`,
+    /// which is used at `span`, but don't stop parsing.
+    pub fn feature_gate(&mut self, feature: Feature, span: Span) {
+        if let Some(diag) = feature.gate(self.edition, span) {
+            self.diagnostics.push(diag);
+        }
+    }
 }
 
 /// A thin wrapper that makes [`Parser`] backtrackable.
@@ -343,6 +357,7 @@ impl<'a, 'b> BTParser<'a, 'b> {
     pub fn new(snapshot: &'b mut Parser<'a>) -> Self {
         let parser = Parser {
             file_id: snapshot.file_id,
+            edition: snapshot.edition,
             lexer: snapshot.lexer.clone(),
             buffered: snapshot.buffered.clone(),
             enclosure_stack: snapshot.enclosure_stack.clone(),
@@ -0,0 +1,565 @@
+//! Structural search and replace of expressions, for mechanical migrations
+//! like rewriting every `$a.checked_add($b)` to `add($a, $b)`.
+//!
+//! A rule has the form `pattern ==>> template`. The pattern is an expression
+//! in which `$name` is a placeholder that matches any expression. A
+//! placeholder that appears more than once must match the same code each
+//! time. The template is an expression in which each placeholder is replaced
+//! with the code it matched.
+use crate::ast::{self, Expr, FuncStmt, Module, ModuleStmt};
+use crate::grammar::expressions::parse_expr;
+use crate::node::{Node, Span, Spanned};
+use crate::Parser;
+use fe_common::files::SourceFileId;
+use smol_str::SmolStr;
+
+/// Placeholders are parsed as names with this prefix, since `$` isn't a
+/// token of the language.
+const PLACEHOLDER_PREFIX: &str = "__ssr_";
+
+const RULE_SEPARATOR: &str = "==>>";
+
+#[derive(Debug, Clone)]
+pub struct SsrRule {
+    pattern: Node<Expr>,
+    /// The source of the pattern, with placeholders renamed.
+    pattern_src: String,
+    template: String,
+    /// Whether the template needs parentheses when it's an operand.
+    template_is_operation: bool,
+}
+
+/// An expression that matches a rule, and the code to replace it with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SsrMatch {
+    pub span: Span,
+    pub replacement: String,
+}
+
+impl SsrRule {
+    /// Parses a rule of the form `pattern ==>> template`.
+    pub fn parse(rule: &str) -> Result<Self, String> {
+        let (pattern, template) = rule.split_once(RULE_SEPARATOR).ok_or_else(|| {
+            format!("a rule must have the form `pattern {RULE_SEPARATOR} template`")
+        })?;
+        let (pattern, template) = (pattern.trim(), template.trim());
+
+        let pattern_src = substitute(pattern, |name| Some(format!("{PLACEHOLDER_PREFIX}{name}")));
+        let pattern_expr = parse_standalone_expr(&pattern_src)
+            .map_err(|err| format!("invalid pattern `{pattern}`: {err}"))?;
+        if placeholder_name(&pattern_expr).is_some() {
+            return Err("the pattern can't be a lone placeholder".into());
+        }
+
+        let mut placeholders = vec![];
+        substitute(pattern, |name| {
+            placeholders.push(name.to_string());
+            None
+        });
+        let mut unknown = None;
+        let template_src = substitute(template, |name| {
+            if !placeholders.iter().any(|placeholder| placeholder == name) {
+                unknown.get_or_insert_with(|| name.to_string());
+            }
+            Some(format!("{PLACEHOLDER_PREFIX}{name}"))
+        });
+        if let Some(name) = unknown {
+            return Err(format!(
+                "the template uses `${name}`, which isn't in the pattern"
+            ));
+        }
+        let template_expr = parse_standalone_expr(&template_src)
+            .map_err(|err| format!("invalid template `{template}`: {err}"))?;
+
+        Ok(Self {
+            pattern: pattern_expr,
+            pattern_src,
+            template: template.to_string(),
+            template_is_operation: is_operation(&template_expr.kind),
+        })
+    }
+
+    /// Returns the expressions of the module that match the rule, in source
+    /// order. Matches don't overlap: the expressions inside of a match
+    /// aren't searched.
+    pub fn find_matches(&self, module: &Module, src: &str) -> Vec<SsrMatch> {
+        let mut finder = Finder {
+            rule: self,
+            src,
+            matches: vec![],
+        };
+        for stmt in &module.body {
+            finder.module_stmt(stmt);
+        }
+        finder.matches.sort_by_key(|match_| match_.span.start);
+        finder.matches
+    }
+
+    /// Returns `src` with every match of the rule replaced, or `None` if
+    /// nothing matches.
+    pub fn apply(&self, module: &Module, src: &str) -> Option<String> {
+        let matches = self.find_matches(module, src);
+        if matches.is_empty() {
+            return None;
+        }
+
+        let mut result = src.to_string();
+        for match_ in matches.iter().rev() {
+            result.replace_range(match_.span.start..match_.span.end, &match_.replacement);
+        }
+        Some(result)
+    }
+}
+
+fn parse_standalone_expr(src: &str) -> Result<Node<Expr>, String> {
+    let mut par = Parser::new(SourceFileId::dummy_file(), src);
+    let expr = parse_expr(&mut par);
+    par.eat_newlines();
+    let expr = match (expr, par.diagnostics.first()) {
+        (Ok(expr), None) => expr,
+        (_, Some(diag)) => return Err(diag.message.clone()),
+        (Err(_), None) => return Err("expected an expression".into()),
+    };
+    if !par.done() {
+        return Err("expected a single expression".into());
+    }
+    Ok(expr)
+}
+
+/// Replaces each `$name` in `src` with `replace(name)`, or leaves it as is if
+/// that returns `None`.
+fn substitute(src: &str, mut replace: impl FnMut(&str) -> Option<String>) -> String {
+    let mut result = String::new();
+    let mut rest = src;
+    while let Some(idx) = rest.find('$') {
+        result.push_str(&rest[..idx]);
+        let after = &rest[idx + 1..];
+        let len = after
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(after.len());
+        let name = &after[..len];
+        match replace(name).filter(|_| !name.is_empty()) {
+            Some(replacement) => result.push_str(&replacement),
+            None => {
+                result.push('$');
+                result.push_str(name);
+            }
+        }
+        rest = &after[len..];
+    }
+    result.push_str(rest);
+    result
+}
+
+fn placeholder_name(expr: &Node<Expr>) -> Option<&str> {
+    match &expr.kind {
+        Expr::Name(name) => name.strip_prefix(PLACEHOLDER_PREFIX),
+        _ => None,
+    }
+}
+
+/// Returns `true` if the expression must be parenthesized to be used as an
+/// operand.
+fn is_operation(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Ternary { .. }
+            | Expr::BoolOperation { .. }
+            | Expr::BinOperation { .. }
+            | Expr::UnaryOperation { .. }
+            | Expr::CompOperation { .. }
+    )
+}
+
+/// Compares code ignoring whitespace.
+fn same_code(a: &str, b: &str) -> bool {
+    a.split_whitespace().eq(b.split_whitespace())
+}
+
+struct Finder<'a> {
+    rule: &'a SsrRule,
+    src: &'a str,
+    matches: Vec<SsrMatch>,
+}
+
+impl<'a> Finder<'a> {
+    fn module_stmt(&mut self, stmt: &ModuleStmt) {
+        match stmt {
+            ModuleStmt::Constant(constant) => self.expr(&constant.kind.value, false),
+            ModuleStmt::Contract(contract) => {
+                self.fields(&contract.kind.fields);
+                for stmt in &contract.kind.body {
+                    match stmt {
+                        ast::ContractStmt::Function(function) => self.function(function),
+                        ast::ContractStmt::Impl(impl_) => self.functions(&impl_.kind.functions),
+                        ast::ContractStmt::Attribute(_) => {}
+                    }
+                }
+            }
+            ModuleStmt::Struct(struct_) => {
+                self.fields(&struct_.kind.fields);
+                self.functions(&struct_.kind.functions);
+            }
+            ModuleStmt::Enum(enum_) => self.functions(&enum_.kind.functions),
+            ModuleStmt::Impl(impl_) => self.functions(&impl_.kind.functions),
+            ModuleStmt::Function(function) => self.function(function),
+            ModuleStmt::Pragma(_)
+            | ModuleStmt::Use(_)
+            | ModuleStmt::TypeAlias(_)
+            | ModuleStmt::Trait(_)
+            | ModuleStmt::Attribute(_)
+            | ModuleStmt::ParseError(_) => {}
+        }
+    }
+
+    fn fields(&mut self, fields: &[Node<ast::Field>]) {
+        for field in fields {
+            if let Some(value) = &field.kind.value {
+                self.expr(value, false);
+            }
+        }
+    }
+
+    fn functions(&mut self, functions: &[Node<ast::Function>]) {
+        functions
+            .iter()
+            .for_each(|function| self.function(function))
+    }
+
+    fn function(&mut self, function: &Node<ast::Function>) {
+        self.stmts(&function.kind.body)
+    }
+
+    fn stmts(&mut self, body: &[Node<FuncStmt>]) {
+        for stmt in body {
+            match &stmt.kind {
+                FuncStmt::Return { value: Some(expr) }
+                | FuncStmt::Revert { error: Some(expr) }
+                | FuncStmt::Expr { value: expr }
+                | FuncStmt::VarDecl {
+                    value: Some(expr), ..
+                }
+                | FuncStmt::ConstantDecl { value: expr, .. } => self.expr(expr, false),
+                FuncStmt::Assign { target, value } | FuncStmt::AugAssign { target, value, .. } => {
+                    self.expr(target, false);
+                    self.expr(value, false);
+                }
+                FuncStmt::For { iter, body, .. } => {
+                    self.expr(iter, false);
+                    self.stmts(body);
+                }
+                FuncStmt::While { test, body } => {
+                    self.expr(test, false);
+                    self.stmts(body);
+                }
+                FuncStmt::If {
+                    test,
+                    body,
+                    or_else,
+                } => {
+                    self.expr(test, false);
+                    self.stmts(body);
+                    self.stmts(or_else);
+                }
+                FuncStmt::Match { expr, arms } => {
+                    self.expr(expr, false);
+                    arms.iter().for_each(|arm| self.stmts(&arm.kind.body));
+                }
+                FuncStmt::Assert { test, msg } => {
+                    self.expr(test, false);
+                    if let Some(msg) = msg {
+                        self.expr(msg, false);
+                    }
+                }
+                FuncStmt::Unsafe(body) => self.stmts(body),
+                FuncStmt::Return { value: None }
+                | FuncStmt::Revert { error: None }
+                | FuncStmt::VarDecl { value: None, .. }
+                | FuncStmt::Break
+                | FuncStmt::Continue => {}
+            }
+        }
+    }
+
+    /// Searches `expr` for matches. `is_operand` is `true` if `expr` is an
+    /// operand of an operator or the receiver of an attribute, index or call.
+    fn expr(&mut self, expr: &Node<Expr>, is_operand: bool) {
+        let mut matcher = Matcher {
+            rule: self.rule,
+            src: self.src,
+            placeholders: vec![],
+        };
+        if matcher.expr(&self.rule.pattern, expr) {
+            let replacement = substitute(&self.rule.template, |name| {
+                let (_, span, is_operation) = matcher
+                    .placeholders
+                    .iter()
+                    .find(|(placeholder, ..)| placeholder == name)?;
+                let code = &self.src[span.start..span.end];
+                // A placeholder that is the whole template keeps its context.
+                if *is_operation && self.rule.template.trim() != format!("${name}") {
+                    Some(format!("({code})"))
+                } else {
+                    Some(code.to_string())
+                }
+            });
+            let replacement = if is_operand && self.rule.template_is_operation {
+                format!("({replacement})")
+            } else {
+                replacement
+            };
+            self.matches.push(SsrMatch {
+                span: expr.span,
+                replacement,
+            });
+            return;
+        }
+
+        match &expr.kind {
+            Expr::Ternary {
+                if_expr,
+                test,
+                else_expr,
+            } => {
+                self.expr(if_expr, true);
+                self.expr(test, true);
+                self.expr(else_expr, true);
+            }
+            Expr::BoolOperation { left, right, .. }
+            | Expr::BinOperation { left, right, .. }
+            | Expr::CompOperation { left, right, .. } => {
+                self.expr(left, true);
+                self.expr(right, true);
+            }
+            Expr::UnaryOperation { operand, .. } => self.expr(operand, true),
+            Expr::Attribute { value, .. } => self.expr(value, true),
+            Expr::Subscript { value, index } => {
+                self.expr(value, true);
+                self.expr(index, false);
+            }
+            Expr::Call { func, args, .. } => {
+                self.expr(func, true);
+                args.kind
+                    .iter()
+                    .for_each(|arg| self.expr(&arg.kind.value, false));
+            }
+            Expr::List { elts } | Expr::Tuple { elts } => {
+                elts.iter().for_each(|elt| self.expr(elt, false))
+            }
+            Expr::Repeat { value, .. } => self.expr(value, false),
+            Expr::Bool(_)
+            | Expr::Name(_)
+            | Expr::Path(_)
+            | Expr::Num(_)
+            | Expr::Str(_)
+            | Expr::Unit => {}
+        }
+    }
+}
+
+struct Matcher<'a> {
+    rule: &'a SsrRule,
+    src: &'a str,
+    /// The name, span and whether it's an operation of the expression each
+    /// placeholder matched.
+    placeholders: Vec<(SmolStr, Span, bool)>,
+}
+
+impl<'a> Matcher<'a> {
+    fn expr(&mut self, pat: &Node<Expr>, expr: &Node<Expr>) -> bool {
+        if let Some(name) = placeholder_name(pat) {
+            let code = &self.src[expr.span.start..expr.span.end];
+            if let Some((_, span, _)) = self.placeholders.iter().find(|(n, ..)| n == name) {
+                return same_code(&self.src[span.start..span.end], code);
+            }
+            self.placeholders
+                .push((name.into(), expr.span, is_operation(&expr.kind)));
+            return true;
+        }
+
+        match (&pat.kind, &expr.kind) {
+            (
+                Expr::Ternary {
+                    if_expr: pat_if,
+                    test: pat_test,
+                    else_expr: pat_else,
+                },
+                Expr::Ternary {
+                    if_expr,
+                    test,
+                    else_expr,
+                },
+            ) => {
+                self.expr(pat_if, if_expr)
+                    && self.expr(pat_test, test)
+                    && self.expr(pat_else, else_expr)
+            }
+            (
+                Expr::BoolOperation {
+                    left: pat_left,
+                    op: pat_op,
+                    right: pat_right,
+                },
+                Expr::BoolOperation { left, op, right },
+            ) => pat_op.kind == op.kind && self.expr(pat_left, left) && self.expr(pat_right, right),
+            (
+                Expr::BinOperation {
+                    left: pat_left,
+                    op: pat_op,
+                    right: pat_right,
+                },
+                Expr::BinOperation { left, op, right },
+            ) => pat_op.kind == op.kind && self.expr(pat_left, left) && self.expr(pat_right, right),
+            (
+                Expr::CompOperation {
+                    left: pat_left,
+                    op: pat_op,
+                    right: pat_right,
+                },
+                Expr::CompOperation { left, op, right },
+            ) => pat_op.kind == op.kind && self.expr(pat_left, left) && self.expr(pat_right, right),
+            (
+                Expr::UnaryOperation {
+                    op: pat_op,
+                    operand: pat_operand,
+                },
+                Expr::UnaryOperation { op, operand },
+            ) => pat_op.kind == op.kind && self.expr(pat_operand, operand),
+            (
+                Expr::Attribute {
+                    value: pat_value,
+                    attr: pat_attr,
+                },
+                Expr::Attribute { value, attr },
+            ) => pat_attr.kind == attr.kind && self.expr(pat_value, value),
+            (
+                Expr::Subscript {
+                    value: pat_value,
+                    index: pat_index,
+                },
+                Expr::Subscript { value, index },
+            ) => self.expr(pat_value, value) && self.expr(pat_index, index),
+            (
+                Expr::Call {
+                    func: pat_func,
+                    generic_args: pat_generic_args,
+                    args: pat_args,
+                },
+                Expr::Call {
+                    func,
+                    generic_args,
+                    args,
+                },
+            ) => {
+                let same_generic_args = match (pat_generic_args, generic_args) {
+                    (Some(pat_args), Some(args)) => self.same_code(pat_args.span, args.span),
+                    (None, None) => true,
+                    _ => false,
+                };
+                same_generic_args
+                    && pat_args.kind.len() == args.kind.len()
+                    && self.expr(pat_func, func)
+                    && pat_args
+                        .kind
+                        .iter()
+                        .zip(args.kind.iter())
+                        .all(|(pat_arg, arg)| {
+                            pat_arg.kind.label.as_ref().map(|label| &label.kind)
+                                == arg.kind.label.as_ref().map(|label| &label.kind)
+                                && self.expr(&pat_arg.kind.value, &arg.kind.value)
+                        })
+            }
+            (Expr::List { elts: pat_elts }, Expr::List { elts })
+            | (Expr::Tuple { elts: pat_elts }, Expr::Tuple { elts }) => {
+                pat_elts.len() == elts.len()
+                    && pat_elts
+                        .iter()
+                        .zip(elts)
+                        .all(|(pat_elt, elt)| self.expr(pat_elt, elt))
+            }
+            (
+                Expr::Repeat {
+                    value: pat_value,
+                    len: pat_len,
+                },
+                Expr::Repeat { value, len },
+            ) => self.same_code(pat_len.span(), len.span()) && self.expr(pat_value, value),
+            (Expr::Path(pat_path), Expr::Path(path)) => pat_path
+                .segments
+                .iter()
+                .map(|segment| &segment.kind)
+                .eq(path.segments.iter().map(|segment| &segment.kind)),
+            (Expr::Bool(pat), Expr::Bool(value)) => pat == value,
+            (Expr::Name(pat), Expr::Name(value))
+            | (Expr::Num(pat), Expr::Num(value))
+            | (Expr::Str(pat), Expr::Str(value)) => pat == value,
+            (Expr::Unit, Expr::Unit) => true,
+            _ => false,
+        }
+    }
+
+    /// Compares code of the pattern with code of the source.
+    fn same_code(&self, pat_span: Span, span: Span) -> bool {
+        same_code(
+            &self.rule.pattern_src[pat_span.start..pat_span.end],
+            &self.src[span.start..span.end],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_file;
+
+    fn apply(rule: &str, src: &str) -> Option<String> {
+        let (module, diags) = parse_file(SourceFileId::dummy_file(), src);
+        assert!(diags.is_empty());
+        SsrRule::parse(rule).unwrap().apply(&module, src)
+    }
+
+    #[test]
+    fn replace_method_calls() {
+        let src = "fn f(x: u256, y: u256) -> u256 {\n    \
+                   return x.checked_add(y).checked_add(x * 2)\n}\n";
+        assert_eq!(
+            apply("$a.checked_add($b) ==>> add($a, $b)", src).unwrap(),
+            "fn f(x: u256, y: u256) -> u256 {\n    \
+             return add(x.checked_add(y), (x * 2))\n}\n"
+        );
+    }
+
+    #[test]
+    fn parenthesize_operations() {
+        let src = "fn f(x: u256, y: u256) -> u256 {\n    return x.add(y) * 2\n}\n";
+        assert_eq!(
+            apply("$a.add($b) ==>> $a + $b", src).unwrap(),
+            "fn f(x: u256, y: u256) -> u256 {\n    return (x + y) * 2\n}\n"
+        );
+    }
+
+    #[test]
+    fn repeated_placeholders() {
+        let src = "fn f(x: u256, y: u256) -> u256 {\n    \
+                   let a: u256 = x - x\n    \
+                   return x - y\n}\n";
+        assert_eq!(
+            apply("$a - $a ==>> 0", src).unwrap(),
+            "fn f(x: u256, y: u256) -> u256 {\n    \
+             let a: u256 = 0\n    \
+             return x - y\n}\n"
+        );
+        assert_eq!(apply("$a.foo() ==>> $a", src), None);
+    }
+
+    #[test]
+    fn invalid_rules() {
+        assert!(SsrRule::parse("$a.foo()").is_err());
+        assert!(SsrRule::parse("$a ==>> $a").is_err());
+        assert!(SsrRule::parse("$a.foo( ==>> $a").is_err());
+        assert_eq!(
+            SsrRule::parse("$a.foo() ==>> $b").unwrap_err(),
+            "the template uses `$b`, which isn't in the pattern"
+        );
+    }
+}
@@ -180,6 +180,9 @@ test_parse! { type_map3, types::parse_type_desc, "Map<address, Map<u8, Map<u8, u
 test_parse! { type_map4, types::parse_type_desc, "map < address , map < u8, u256 > >" }
 test_parse! { type_tuple, types::parse_type_desc, "(u8, u16, address, Map<u8, u8>)" }
 test_parse! { type_unit, types::parse_type_desc, "()" }
+test_parse! { type_tuple1, types::parse_type_desc, "(u8,)" }
+test_parse! { type_parens, types::parse_type_desc, "(u8)" }
+test_parse! { type_storage_ref, types::parse_type_desc, "&storage Map<address, u256>" }
 
 test_parse! { fn_def, try_parse_module, "fn transfer(from sender: address, to recip: address, _ val: u64) -> bool {\n false \n}"}
 
@@ -237,6 +240,16 @@ test_parse! { contract_def, try_parse_module, r#"contract Foo {
 }
 "# }
 
+test_parse! { contract_field_attributes, try_parse_module, r#"contract Foo {
+  x: address
+  #storage_gap(48)
+  y: u256
+
+  #only(x)
+  pub fn foo(self) {}
+}
+"# }
+
 test_parse! { empty_contract_def, try_parse_module, "contract Foo {}" }
 
 test_parse! { pub_contract_def, try_parse_module, r#"
@@ -34,6 +34,10 @@ macro_rules! test_print {
                 assert_snapshot!(parse_and_print($path, src))
             }
 
+            // The printed code must parse, and parse back into the same code.
+            let printed = parse_and_print($path, src);
+            assert_eq!(parse_and_print($path, &printed), printed);
+
             // These tests used to assert that the source text and the formatted
             // text are equal, which is probably more likely to catch errors,
             // but the diffs from `pretty_assertions` made it hard to debug.
@@ -46,3 +50,4 @@ test_print! { erc20, "demos/erc20_token.fe" }
 test_print! { guest_book, "demos/guest_book.fe" }
 test_print! { expr_parens, "printing/expr_parens.fe" }
 test_print! { defs, "printing/defs.fe" }
+test_print! { traits, "printing/traits.fe" }
@@ -1,8 +1,9 @@
-use bytes::Bytes;
 use colored::Colorize;
 use ethabi::{Event, Hash, RawLog};
 use indexmap::IndexMap;
-use revm::primitives::{AccountInfo, Bytecode, Env, ExecutionResult, TransactTo, B160, U256};
+use revm::primitives::{
+    AccountInfo, Address, Bytecode, Bytes, Env, ExecutionResult, SpecId, TransactTo, B256, U256,
+};
 use std::fmt::Display;
 
 pub use ethabi;
@@ -107,24 +108,66 @@ impl Display for TestSink {
     }
 }
 
+/// The parts of the environment of a test transaction that can be configured.
+/// Tests are executed with the rules of the Cancun hard fork.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TestEnv {
+    /// The versioned hashes of the blobs of the transaction (EIP-4844). Each
+    /// hash must start with the version byte `0x01`.
+    pub blob_hashes: Vec<[u8; 32]>,
+    /// The excess blob gas of the block, which determines the blob base fee.
+    pub excess_blob_gas: u64,
+}
+
 pub fn execute(name: &str, events: &[Event], bytecode: &str, sink: &mut TestSink) -> bool {
+    execute_in(name, events, bytecode, &TestEnv::default(), sink)
+}
+
+pub fn execute_in(
+    name: &str,
+    events: &[Event],
+    bytecode: &str,
+    test_env: &TestEnv,
+    sink: &mut TestSink,
+) -> bool {
     let events: IndexMap<_, _> = events
         .iter()
         .map(|event| (event.signature(), event))
         .collect();
-    let bytecode = Bytecode::new_raw(Bytes::copy_from_slice(&hex::decode(bytecode).unwrap()));
+    let bytecode = Bytecode::new_raw(Bytes::from(hex::decode(bytecode).unwrap()));
 
     let mut database = revm::InMemoryDB::default();
-    let test_address = B160::from(42);
-    let test_info = AccountInfo::new(U256::ZERO, 0, bytecode);
+    let test_address = Address::with_last_byte(42);
+    let test_info = AccountInfo {
+        code: Some(bytecode),
+        ..Default::default()
+    };
     database.insert_account_info(test_address, test_info);
 
     let mut env = Env::default();
     env.tx.transact_to = TransactTo::Call(test_address);
+    env.block
+        .set_blob_excess_gas_and_price(test_env.excess_blob_gas);
+    if !test_env.blob_hashes.is_empty() {
+        env.tx.blob_hashes = test_env
+            .blob_hashes
+            .iter()
+            .map(|hash| B256::from(*hash))
+            .collect();
+        env.tx.max_fee_per_blob_gas = env.block.get_blob_gasprice().map(U256::from);
+        // the caller pays for the blob gas
+        let caller_info = AccountInfo {
+            balance: U256::MAX,
+            ..Default::default()
+        };
+        database.insert_account_info(env.tx.caller, caller_info);
+    }
 
-    let mut evm = revm::new();
-    evm.env = env;
-    evm.database(&mut database);
+    let mut evm = revm::Evm::builder()
+        .with_db(&mut database)
+        .with_env(Box::new(env))
+        .with_spec_id(SpecId::CANCUN)
+        .build();
     let result = evm.transact_commit().expect("evm failure");
 
     if let ExecutionResult::Success { logs, .. } = result {
@@ -132,16 +175,16 @@ pub fn execute(name: &str, events: &[Event], bytecode: &str, sink: &mut TestSink
             .iter()
             .map(|log| {
                 if let Some(Some(event)) = log
-                    .topics
-                    .get(0)
-                    .map(|sig| events.get(&Hash::from_slice(sig.as_bytes())))
+                    .topics()
+                    .first()
+                    .map(|sig| events.get(&Hash::from_slice(sig.as_slice())))
                 {
                     let topics = log
-                        .topics
+                        .topics()
                         .iter()
-                        .map(|topic| Hash::from_slice(topic.as_bytes()))
+                        .map(|topic| Hash::from_slice(topic.as_slice()))
                         .collect();
-                    let data = log.data.clone().to_vec();
+                    let data = log.data.data.to_vec();
                     let raw_log = RawLog { topics, data };
                     if let Ok(parsed_event) = event.parse_log(raw_log) {
                         format!(
@@ -187,3 +230,33 @@ pub fn execute(name: &str, events: &[Event], bytecode: &str, sink: &mut TestSink
         panic!("test halted")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reverts unless the transaction has a blob at index 0.
+    const REQUIRE_BLOB: &str = "60004915600857005b600080fd";
+
+    #[test]
+    fn blob_hashes() {
+        let mut sink = TestSink::new(false);
+        let mut blob_hash = [0; 32];
+        blob_hash[0] = 1;
+        let test_env = TestEnv {
+            blob_hashes: vec![blob_hash],
+            excess_blob_gas: 0,
+        };
+
+        assert!(execute_in(
+            "with_blob",
+            &[],
+            REQUIRE_BLOB,
+            &test_env,
+            &mut sink
+        ));
+        assert!(!execute("without_blob", &[], REQUIRE_BLOB, &mut sink));
+        assert_eq!(sink.success_count(), 1);
+        assert_eq!(sink.failure_count(), 1);
+    }
+}
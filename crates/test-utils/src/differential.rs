@@ -0,0 +1,236 @@
+//! Differential testing against Solidity.
+//!
+//! Paired Fe and Solidity contracts implementing the same interface live in
+//! `fixtures/differential`. Both are deployed to the same executor, called with
+//! the same inputs and their results are compared.
+use crate::{ContractHarness, Executor};
+
+/// A pair of contracts, one compiled from Fe and one from Solidity, that
+/// implement the same interface.
+pub struct DualHarness {
+    fe_harness: ContractHarness,
+    solidity_harness: ContractHarness,
+}
+
+/// The results of calling the same function on both contracts of a
+/// [`DualHarness`].
+pub struct CaptureResult<'a> {
+    function: ethabi::Function,
+    fe_capture: evm::Capture<(evm::ExitReason, Vec<u8>), std::convert::Infallible>,
+    fe_used_gas: u64,
+    solidity_capture: evm::Capture<(evm::ExitReason, Vec<u8>), std::convert::Infallible>,
+    solidity_used_gas: u64,
+    name: &'a str,
+    input: &'a [ethabi::Token],
+}
+
+impl<'a> CaptureResult<'a> {
+    pub fn assert_fe_max_percentage_more_gas(&self, max_percentage: i64) -> &Self {
+        let fe_percentage: i64 = (self.fe_used_gas as i64 - self.solidity_used_gas as i64) * 100
+            / self.solidity_used_gas as i64;
+
+        assert!(fe_percentage <= max_percentage, "Fe used gas: {}, Solidity used gas: {}, Fe used {}% more gas. Called {} with input: {:?}", self.fe_used_gas, self.solidity_used_gas, fe_percentage, self.name, self.input);
+        self
+    }
+
+    pub fn assert_perfomed_equal(&self) -> &Self {
+        assert_eq!(
+            self.fe_capture, self.solidity_capture,
+            "Called {} with input: {:?}",
+            self.name, self.input
+        );
+        self
+    }
+
+    pub fn assert_return_data_equal(&self) -> &Self {
+        if let (evm::Capture::Exit((_, fe_data)), evm::Capture::Exit((_, sol_data))) =
+            (&self.fe_capture, &self.solidity_capture)
+        {
+            assert_eq!(
+                fe_data, sol_data,
+                "Called {} with input: {:?}",
+                self.name, self.input
+            )
+        }
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn assert_reverted(&self) -> &Self {
+        if !matches!(
+            (self.fe_capture.clone(), self.solidity_capture.clone()),
+            (
+                evm::Capture::Exit((evm::ExitReason::Revert(_), _)),
+                evm::Capture::Exit((evm::ExitReason::Revert(_), _))
+            )
+        ) {
+            panic!(
+                "Asserted both revert but was: Fe: {:?} Solidity: {:?}",
+                self.fe_capture, self.solidity_capture
+            )
+        }
+        self
+    }
+
+    pub fn assert_any_success_with_equal_return_data(&self) -> &Self {
+        self.assert_any_success().assert_return_data_equal();
+        self
+    }
+
+    pub fn assert_any_success_or_revert_with_equal_return_data(&self) -> &Self {
+        if !(self.both_succeeded() || self.both_reverted()) {
+            panic!(
+                "Asserted both succeeded or reverted but was: Fe: {:?} Solidity: {:?}",
+                self.fe_capture, self.solidity_capture
+            )
+        } else {
+            self.assert_return_data_equal()
+        }
+    }
+
+    pub fn assert_any_success(&self) -> &Self {
+        if !matches!(
+            (self.fe_capture.clone(), self.solidity_capture.clone()),
+            (
+                evm::Capture::Exit((evm::ExitReason::Succeed(_), _)),
+                evm::Capture::Exit((evm::ExitReason::Succeed(_), _))
+            )
+        ) {
+            panic!(
+                "Asserted both succeeded but was: Fe: {:?} Solidity: {:?}",
+                self.fe_capture, self.solidity_capture
+            )
+        }
+        self
+    }
+
+    pub fn both_succeeded(&self) -> bool {
+        matches!(
+            (self.fe_capture.clone(), self.solidity_capture.clone()),
+            (
+                evm::Capture::Exit((evm::ExitReason::Succeed(_), _)),
+                evm::Capture::Exit((evm::ExitReason::Succeed(_), _))
+            )
+        )
+    }
+
+    pub fn both_reverted(&self) -> bool {
+        matches!(
+            (self.fe_capture.clone(), self.solidity_capture.clone()),
+            (
+                evm::Capture::Exit((evm::ExitReason::Revert(_), _)),
+                evm::Capture::Exit((evm::ExitReason::Revert(_), _))
+            )
+        )
+    }
+
+    #[allow(dead_code)]
+    pub fn performed_equal(&self) -> bool {
+        self.fe_capture == self.solidity_capture
+    }
+
+    /// Asserts that both calls either succeeded and returned the same values,
+    /// or reverted with the same revert data.
+    ///
+    /// Unlike [`Self::assert_perfomed_equal`], return data is compared after
+    /// decoding it with the ABI of the called function, so the contracts may
+    /// differ in how they encode it (e.g. dirty padding is not a difference).
+    pub fn assert_abi_equal(&self) -> &Self {
+        match (&self.fe_capture, &self.solidity_capture) {
+            (
+                evm::Capture::Exit((evm::ExitReason::Succeed(_), fe_data)),
+                evm::Capture::Exit((evm::ExitReason::Succeed(_), sol_data)),
+            ) => {
+                let decode = |data: &[u8]| {
+                    self.function.decode_output(data).unwrap_or_else(|err| {
+                        panic!("Unable to decode output of {}: {err:?}", self.name)
+                    })
+                };
+                assert_eq!(
+                    decode(fe_data),
+                    decode(sol_data),
+                    "Called {} with input: {:?}",
+                    self.name,
+                    self.input
+                )
+            }
+            (
+                evm::Capture::Exit((evm::ExitReason::Revert(_), fe_data)),
+                evm::Capture::Exit((evm::ExitReason::Revert(_), sol_data)),
+            ) => assert_eq!(
+                fe_data, sol_data,
+                "Revert data differs. Called {} with input: {:?}",
+                self.name, self.input
+            ),
+            _ => panic!(
+                "Fe: {:?} Solidity: {:?}. Called {} with input: {:?}",
+                self.fe_capture, self.solidity_capture, self.name, self.input
+            ),
+        }
+        self
+    }
+}
+
+impl<'a> DualHarness {
+    /// Deploys the contract `contract_name` from both
+    /// `differential/{fixture}.fe` and `differential/{fixture}.sol`.
+    pub fn from_fixture(
+        executor: &mut Executor,
+        fixture: &str,
+        contract_name: &str,
+        init_params: &[ethabi::Token],
+    ) -> DualHarness {
+        let fe_harness = crate::deploy_contract(
+            executor,
+            &format!("differential/{fixture}.fe"),
+            contract_name,
+            init_params,
+        );
+        let solidity_harness = crate::deploy_solidity_contract(
+            executor,
+            &format!("differential/{fixture}.sol"),
+            contract_name,
+            init_params,
+            true,
+        );
+        DualHarness {
+            fe_harness,
+            solidity_harness,
+        }
+    }
+
+    pub fn capture_call(
+        &self,
+        executor: &mut Executor,
+        name: &'a str,
+        input: &'a [ethabi::Token],
+    ) -> CaptureResult<'a> {
+        let initially_used = executor.used_gas();
+        let fe_capture = self.fe_harness.capture_call(executor, name, input);
+        let fe_used_gas = executor.used_gas() - initially_used;
+        let solidity_capture = self.solidity_harness.capture_call(executor, name, input);
+        let solidity_used_gas = executor.used_gas() - fe_used_gas - initially_used;
+
+        CaptureResult {
+            function: self.fe_harness.abi.functions[name][0].clone(),
+            fe_capture,
+            fe_used_gas,
+            solidity_capture,
+            solidity_used_gas,
+            name,
+            input,
+        }
+    }
+
+    /// Calls each function with the given input on both contracts and asserts
+    /// that the results are equal at the ABI level.
+    pub fn assert_calls_abi_equal(
+        &self,
+        executor: &mut Executor,
+        calls: &[(&str, Vec<ethabi::Token>)],
+    ) {
+        for (name, input) in calls {
+            self.capture_call(executor, name, input).assert_abi_equal();
+        }
+    }
+}
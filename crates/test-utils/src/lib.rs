@@ -9,6 +9,9 @@ use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 use yultsur::*;
 
+#[cfg(feature = "solc-backend")]
+pub mod differential;
+
 #[macro_export]
 macro_rules! assert_harness_gas_report {
     ($harness: expr) => {
@@ -719,8 +722,9 @@ impl ExecutionOutput {
 #[cfg(feature = "solc-backend")]
 fn execute_runtime_functions(executor: &mut Executor, runtime: &Runtime) -> (ExitReason, Vec<u8>) {
     let yul_code = runtime.to_yul().to_string().replace('"', "\\\"");
-    let contract_bytecode = fe_yulc::compile_single_contract("Contract", &yul_code, false, false)
-        .expect("failed to compile Yul");
+    let contract_bytecode =
+        fe_yulc::compile_single_contract("Contract", &yul_code, false, None, None, false)
+            .expect("failed to compile Yul");
     let bytecode = hex::decode(contract_bytecode.bytecode).expect("failed to decode bytecode");
 
     if let evm::Capture::Exit((reason, _, output)) = executor.create(
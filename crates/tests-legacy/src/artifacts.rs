@@ -0,0 +1,156 @@
+//! Snapshots of the ABI and storage layout of every fixture contract, and
+//! the NatSpec documentation of a documented one.
+//!
+//! A change to these snapshots means that contracts compiled by the new
+//! compiler can't be called with the old ABI, or can't be used to upgrade a
+//! contract that was deployed with the old compiler. Review such changes
+//! carefully before accepting them with `cargo insta review`.
+use dir_test::{dir_test, Fixture};
+use fe_common::diagnostics::print_diagnostics;
+use insta::assert_snapshot;
+use std::path::Path;
+
+#[dir_test(dir: "$CARGO_MANIFEST_DIR/../test-files/fixtures/features", glob: "*.fe")]
+fn feature_artifacts(fixture: Fixture<&str>) {
+    assert_artifacts_snapshot(fixture.path(), fixture.content())
+}
+
+#[dir_test(dir: "$CARGO_MANIFEST_DIR/../test-files/fixtures/demos", glob: "*.fe")]
+fn demo_artifacts(fixture: Fixture<&str>) {
+    assert_artifacts_snapshot(fixture.path(), fixture.content())
+}
+
+fn assert_artifacts_snapshot(path: &str, src: &str) {
+    let mut db = fe_driver::Db::default();
+    let module = match fe_driver::compile_single_file(&mut db, path, src, false, false, false) {
+        Ok(module) => module,
+        Err(error) => {
+            print_diagnostics(&db, &error.0);
+            panic!("failed to compile {path}")
+        }
+    };
+
+    let snapshot = module
+        .contracts
+        .iter()
+        .map(|(name, contract)| {
+            format!(
+                "// {name} ABI\n{}\n\n// {name} storage layout\n{}\n",
+                contract.json_abi, contract.json_storage_layout
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let name = Path::new(path).file_stem().unwrap().to_str().unwrap();
+    assert_snapshot!(name, snapshot);
+}
+
+#[test]
+fn natspec_documentation() {
+    let path = "features/natspec.fe";
+    let mut db = fe_driver::Db::default();
+    let module = match fe_driver::compile_single_file(
+        &mut db,
+        path,
+        test_files::fixture(path),
+        false,
+        false,
+        false,
+    ) {
+        Ok(module) => module,
+        Err(error) => {
+            print_diagnostics(&db, &error.0);
+            panic!("failed to compile {path}")
+        }
+    };
+    let token = &module.contracts["Token"];
+
+    assert_eq!(
+        token.json_userdoc,
+        r#"{
+  "kind": "user",
+  "version": 1,
+  "notice": "A token that only its owner can mint.",
+  "methods": {
+    "balance_of(address)": {
+      "notice": "Returns the balance of an account."
+    },
+    "mint(address,uint256)": {
+      "notice": "Mints tokens to an account."
+    },
+    "transfer(address,uint256)": {
+      "notice": "Transfers tokens to another account."
+    }
+  },
+  "events": {
+    "InsufficientBalance(uint256)": {
+      "notice": "The sender doesn't have enough tokens."
+    },
+    "Minted(address,uint256)": {
+      "notice": "Emitted when tokens are minted."
+    }
+  },
+  "errors": {
+    "InsufficientBalance(uint256)": [
+      {
+        "notice": "The sender doesn't have enough tokens."
+      }
+    ],
+    "Unauthorized(address)": [
+      {
+        "notice": "The error of calling a contract function with an `#only(field)` attribute from an address that the field doesn't allow."
+      }
+    ]
+  }
+}"#
+    );
+    assert_eq!(
+        token.json_devdoc,
+        r#"{
+  "kind": "dev",
+  "version": 1,
+  "title": "Token",
+  "author": "The Fe Developers",
+  "methods": {
+    "balance_of(address)": {
+      "params": {
+        "account": "The account to query"
+      },
+      "returns": {
+        "_0": "The balance, in the smallest unit"
+      }
+    },
+    "constructor": {
+      "params": {
+        "owner": "The account that can mint tokens"
+      }
+    },
+    "mint(address,uint256)": {
+      "details": "Only the owner can mint."
+    }
+  },
+  "events": {
+    "InsufficientBalance(uint256)": {
+      "params": {
+        "available": "The balance of the sender"
+      }
+    },
+    "Minted(address,uint256)": {
+      "params": {
+        "to": "The account the tokens were minted to"
+      }
+    }
+  },
+  "errors": {
+    "InsufficientBalance(uint256)": [
+      {
+        "params": {
+          "available": "The balance of the sender"
+        }
+      }
+    ]
+  }
+}"#
+    );
+}
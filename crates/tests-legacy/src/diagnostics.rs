@@ -0,0 +1,319 @@
+use fe_common::chain::ChainProfile;
+use fe_common::diagnostics::{
+    apply_suggestions, diagnostics_string, machine_applicable_suggestions, LabelStyle,
+};
+use fe_common::evm::{EvmVersion, Precompile};
+use fe_driver::CodegenDb;
+
+fn check(path: &str) -> (String, Vec<usize>) {
+    let mut db = fe_driver::Db::default();
+    let diags = fe_driver::check_single_file(&mut db, path, test_files::fixture(path));
+    let starts = diags
+        .iter()
+        .filter_map(|diag| {
+            diag.labels
+                .iter()
+                .find(|label| label.style == LabelStyle::Primary)
+                .map(|label| label.span.start)
+        })
+        .collect();
+    (diagnostics_string(&db, &diags), starts)
+}
+
+#[test]
+fn diagnostics_are_sorted_by_location() {
+    let (_, starts) = check("compile_errors/bad_enums.fe");
+    assert!(starts.len() > 1);
+    assert!(starts.windows(2).all(|pair| pair[0] <= pair[1]));
+}
+
+#[test]
+fn diagnostics_order_is_independent_of_threads() {
+    let path = "compile_errors/bad_enums.fe";
+    let (expected, _) = check(path);
+
+    for threads in [1, 2, 4, 8] {
+        let outputs: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads)
+                .map(|_| scope.spawn(|| check(path).0))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        });
+        for output in outputs {
+            assert_eq!(
+                output, expected,
+                "diagnostics differ with {threads} threads"
+            );
+        }
+    }
+}
+
+#[test]
+fn suggestions_fix_legacy_static_calls() {
+    let path = "compile_errors/call_static_function_without_double_colon.fe";
+    let src = test_files::fixture(path);
+    let mut db = fe_driver::Db::default();
+    let diags = fe_driver::check_single_file(&mut db, path, src);
+    let (fixed, applied) = apply_suggestions(src, &machine_applicable_suggestions(&diags));
+    assert_eq!(applied.len(), 1);
+    assert!(fixed.contains("Bar::call_me()"), "{fixed}");
+
+    let mut db = fe_driver::Db::default();
+    let diags = fe_driver::check_single_file(&mut db, path, &fixed);
+    assert!(diags.is_empty(), "{}", diagnostics_string(&db, &diags));
+}
+
+#[test]
+fn poisoned_variables_are_not_lowered() {
+    // `x` and `y` have the type `Type::Error`, which MIR lowering panics on.
+    let src = "contract Foo {
+    pub fn bar() -> u256 {
+        let x: MysteryType = 1
+        let y: u256 = x + 1
+        return y
+    }
+}
+";
+    let mut db = fe_driver::Db::default();
+    let output =
+        match fe_driver::compile_single_file(&mut db, "poisoned.fe", src, false, false, false) {
+            Ok(_) => panic!("a module with a poisoned variable was compiled"),
+            Err(error) => diagnostics_string(&db, &error.0),
+        };
+    assert!(
+        output.contains("`MysteryType` has not been defined"),
+        "{output}"
+    );
+}
+
+// The size of a contract is only known once solc has compiled it.
+#[test]
+#[cfg(feature = "solc-backend")]
+fn code_size_budget_is_enforced() {
+    let src = "#code_size_budget(10)
+contract Foo {
+    pub fn bar() -> u256 {
+        return 1
+    }
+}
+";
+    let mut db = fe_driver::Db::default();
+    let output = match fe_driver::compile_single_file(&mut db, "budget.fe", src, true, false, false)
+    {
+        Ok(_) => panic!("the code size budget was not enforced"),
+        Err(error) => diagnostics_string(&db, &error.0),
+    };
+    assert!(output.contains("exceeds its code size budget"), "{output}");
+    assert!(output.contains("<dispatcher>"), "{output}");
+
+    let src = src.replace("(10)", "(24576)");
+    let mut db = fe_driver::Db::default();
+    assert!(fe_driver::compile_single_file(&mut db, "budget.fe", &src, true, false, false).is_ok());
+}
+
+#[test]
+fn deprecated_instructions_are_rejected_on_evm_version() {
+    let src = "use std::evm
+
+contract Foo {
+    pub fn destroy(ctx: Context) {
+        unsafe {
+            evm::selfdestruct(ctx.msg_sender())
+        }
+    }
+}
+";
+    let mut db = fe_driver::Db::default();
+    db.set_codegen_evm_version(Some(EvmVersion::Cancun));
+    let output =
+        match fe_driver::compile_single_file(&mut db, "destroy.fe", src, true, false, false) {
+            Ok(_) => panic!("selfdestruct was allowed on cancun"),
+            Err(error) => diagnostics_string(&db, &error.0),
+        };
+    assert!(output.contains("`selfdestruct` is deprecated"), "{output}");
+
+    let mut db = fe_driver::Db::default();
+    db.set_codegen_evm_version(Some(EvmVersion::Paris));
+    assert!(fe_driver::compile_single_file(&mut db, "destroy.fe", src, true, false, false).is_ok());
+}
+
+#[test]
+fn reentrancy_lock_uses_transient_storage_on_cancun() {
+    // keccak256("fe.reentrancy_lock")
+    let lock_slot = "56810363807661762276261130198264923450519026495096126048108590451807611049424";
+    let path = "features/function_guards.fe";
+    let vault_yul = |evm_version| {
+        let mut db = fe_driver::Db::default();
+        db.set_codegen_evm_version(evm_version);
+        let module = fe_driver::compile_single_file(
+            &mut db,
+            path,
+            test_files::fixture(path),
+            false,
+            false,
+            false,
+        )
+        .unwrap_or_else(|error| panic!("{}", diagnostics_string(&db, &error.0)));
+        module.contracts["Vault"].yul.clone()
+    };
+
+    // TLOAD and TSTORE aren't builtins of the bundled solc.
+    let yul = vault_yul(Some(EvmVersion::Cancun));
+    assert!(
+        yul.contains(&format!(r#"verbatim_2i_0o(hex\"5d\", {lock_slot}, 1)"#)),
+        "{yul}"
+    );
+    assert!(!yul.contains(&format!("sstore({lock_slot}")), "{yul}");
+
+    for evm_version in [None, Some(EvmVersion::Shanghai)] {
+        let yul = vault_yul(evm_version);
+        assert!(yul.contains(&format!("sstore({lock_slot}, 1)")), "{yul}");
+        assert!(!yul.contains("verbatim"), "{yul}");
+    }
+}
+
+#[test]
+fn precompiles_are_checked_against_chain_profile() {
+    let src = "use std::precompiles
+
+contract Foo {
+    pub fn recover(hash: u256, v: u256, r: u256, s: u256) -> address {
+        return precompiles::ec_recover(hash, v, r, s)
+    }
+}
+";
+    let mut chain = ChainProfile::builtin("optimism").unwrap();
+    chain
+        .precompiles
+        .retain(|precompile| *precompile != Precompile::EcRecover);
+    let mut db = fe_driver::Db::default();
+    db.set_codegen_chain_profile(Some(chain));
+    let output =
+        match fe_driver::compile_single_file(&mut db, "recover.fe", src, true, false, false) {
+            Ok(_) => panic!("a missing precompile was allowed"),
+            Err(error) => diagnostics_string(&db, &error.0),
+        };
+    assert!(
+        output.contains("the `ec_recover` precompile is not available on chain `optimism`"),
+        "{output}"
+    );
+
+    let mut db = fe_driver::Db::default();
+    db.set_codegen_chain_profile(ChainProfile::builtin("optimism"));
+    let module = fe_driver::compile_single_file(&mut db, "recover.fe", src, true, false, false)
+        .unwrap_or_else(|error| panic!("{}", diagnostics_string(&db, &error.0)));
+    assert!(module.json_metadata.contains("\"optimism\""));
+}
+
+#[test]
+fn precompiles_are_checked_against_evm_version() {
+    let src = "use std::buf::MemoryBuffer
+use std::precompiles
+
+contract Foo {
+    pub fn evaluate(versioned_hash: u256, z: u256, y: u256) -> u256 {
+        let result: (u256, u256) = precompiles::point_evaluation(
+            versioned_hash,
+            z,
+            y,
+            commitment: MemoryBuffer::new(len: 48),
+            proof: MemoryBuffer::new(len: 48)
+        )
+        return result.item0
+    }
+}
+";
+    let mut db = fe_driver::Db::default();
+    db.set_codegen_evm_version(Some(EvmVersion::Shanghai));
+    let output = match fe_driver::compile_single_file(&mut db, "blob.fe", src, true, false, false) {
+        Ok(_) => panic!("point_evaluation was allowed on shanghai"),
+        Err(error) => diagnostics_string(&db, &error.0),
+    };
+    assert!(
+        output.contains(
+            "the `point_evaluation` precompile is not available on EVM version `shanghai`"
+        ),
+        "{output}"
+    );
+
+    let mut db = fe_driver::Db::default();
+    db.set_codegen_evm_version(Some(EvmVersion::Cancun));
+    assert!(fe_driver::compile_single_file(&mut db, "blob.fe", src, true, false, false).is_ok());
+}
+
+#[test]
+fn user_op_validation_rejects_banned_instructions() {
+    let src = "use std::aa::ValidateUserOp
+
+contract Account {
+    pub fn __call__(self, ctx: Context) {
+        let deadline: u256 = self.validate_user_op(ctx, request: ValidateUserOp::decode(ctx))
+    }
+
+    fn validate_user_op(self, ctx: Context, request: ValidateUserOp) -> u256 {
+        return ctx.block_timestamp()
+    }
+}
+";
+    let mut db = fe_driver::Db::default();
+    let output =
+        match fe_driver::compile_single_file(&mut db, "account.fe", src, true, false, false) {
+            Ok(_) => panic!("a banned instruction was allowed in validation"),
+            Err(error) => diagnostics_string(&db, &error.0),
+        };
+    assert!(
+        output.contains("the `timestamp` instruction is not allowed in user operation validation"),
+        "{output}"
+    );
+
+    let template = include_str!("../../fe/src/aa_account_template/src/main.fe");
+    let mut db = fe_driver::Db::default();
+    if let Err(error) =
+        fe_driver::compile_single_file(&mut db, "account.fe", template, true, false, false)
+    {
+        panic!("{}", diagnostics_string(&db, &error.0));
+    }
+}
+
+#[test]
+fn abi_names_of_events_are_unique() {
+    let src = r#"struct Transfer {
+    #indexed
+    pub from: address
+    pub value: u256
+}
+
+#abi_name("Transfer")
+struct Sent {
+    pub to: address
+}
+
+contract Token {
+    pub fn send(mut ctx: Context) {
+        ctx.emit(Transfer(from: ctx.msg_sender(), value: 1))
+        ctx.emit(Sent(to: ctx.msg_sender()))
+    }
+}
+"#;
+    let mut db = fe_driver::Db::default();
+    let output = match fe_driver::compile_single_file(&mut db, "token.fe", src, false, false, false)
+    {
+        Ok(_) => panic!("the conflicting events were not rejected"),
+        Err(error) => diagnostics_string(&db, &error.0),
+    };
+    assert!(
+        output.contains("contract `Token` has more than one event named `Transfer` in its ABI"),
+        "{output}"
+    );
+    assert!(output.contains("`token::Sent` is defined here"), "{output}");
+
+    let src = src.replace(r#"#abi_name("Transfer")"#, r#"#abi_name("SentTo")"#);
+    let mut db = fe_driver::Db::default();
+    let module =
+        fe_driver::compile_single_file(&mut db, "token.fe", &src, false, false, false).unwrap();
+    let abi = &module.contracts["Token"].json_abi;
+    assert!(abi.contains(r#""name": "SentTo""#), "{abi}");
+}
@@ -2,177 +2,8 @@
 #![cfg(all(feature = "solc-backend", not(target_arch = "wasm32")))]
 use proptest::prelude::*;
 
+use fe_compiler_test_utils::differential::DualHarness;
 use fe_compiler_test_utils::*;
-use fe_compiler_test_utils::{self as test_utils};
-
-struct DualHarness {
-    fe_harness: ContractHarness,
-    solidity_harness: ContractHarness,
-}
-
-struct CaptureResult<'a> {
-    fe_capture: evm::Capture<(evm::ExitReason, Vec<u8>), std::convert::Infallible>,
-    fe_used_gas: u64,
-    solidity_capture: evm::Capture<(evm::ExitReason, Vec<u8>), std::convert::Infallible>,
-    solidity_used_gas: u64,
-    name: &'a str,
-    input: &'a [ethabi::Token],
-}
-
-impl<'a> CaptureResult<'a> {
-    pub fn assert_fe_max_percentage_more_gas(&self, max_percentage: i64) -> &Self {
-        let fe_percentage: i64 = (self.fe_used_gas as i64 - self.solidity_used_gas as i64) * 100
-            / self.solidity_used_gas as i64;
-
-        assert!(fe_percentage <= max_percentage, "Fe used gas: {}, Solidity used gas: {}, Fe used {}% more gas. Called {} with input: {:?}", self.fe_used_gas, self.solidity_used_gas, fe_percentage, self.name, self.input);
-        self
-    }
-
-    pub fn assert_perfomed_equal(&self) -> &Self {
-        assert_eq!(
-            self.fe_capture, self.solidity_capture,
-            "Called {} with input: {:?}",
-            self.name, self.input
-        );
-        self
-    }
-
-    pub fn assert_return_data_equal(&self) -> &Self {
-        if let (evm::Capture::Exit((_, fe_data)), evm::Capture::Exit((_, sol_data))) =
-            (&self.fe_capture, &self.solidity_capture)
-        {
-            assert_eq!(
-                fe_data, sol_data,
-                "Called {} with input: {:?}",
-                self.name, self.input
-            )
-        }
-        self
-    }
-
-    #[allow(dead_code)]
-    pub fn assert_reverted(&self) -> &Self {
-        if !matches!(
-            (self.fe_capture.clone(), self.solidity_capture.clone()),
-            (
-                evm::Capture::Exit((evm::ExitReason::Revert(_), _)),
-                evm::Capture::Exit((evm::ExitReason::Revert(_), _))
-            )
-        ) {
-            panic!(
-                "Asserted both revert but was: Fe: {:?} Solidity: {:?}",
-                self.fe_capture, self.solidity_capture
-            )
-        }
-        self
-    }
-
-    pub fn assert_any_success_with_equal_return_data(&self) -> &Self {
-        self.assert_any_success().assert_return_data_equal();
-        self
-    }
-
-    pub fn assert_any_success_or_revert_with_equal_return_data(&self) -> &Self {
-        if !(self.both_succeeded() || self.both_reverted()) {
-            panic!(
-                "Asserted both succeeded or reverted but was: Fe: {:?} Solidity: {:?}",
-                self.fe_capture, self.solidity_capture
-            )
-        } else {
-            self.assert_return_data_equal()
-        }
-    }
-
-    pub fn assert_any_success(&self) -> &Self {
-        if !matches!(
-            (self.fe_capture.clone(), self.solidity_capture.clone()),
-            (
-                evm::Capture::Exit((evm::ExitReason::Succeed(_), _)),
-                evm::Capture::Exit((evm::ExitReason::Succeed(_), _))
-            )
-        ) {
-            panic!(
-                "Asserted both succeeded but was: Fe: {:?} Solidity: {:?}",
-                self.fe_capture, self.solidity_capture
-            )
-        }
-        self
-    }
-
-    pub fn both_succeeded(&self) -> bool {
-        matches!(
-            (self.fe_capture.clone(), self.solidity_capture.clone()),
-            (
-                evm::Capture::Exit((evm::ExitReason::Succeed(_), _)),
-                evm::Capture::Exit((evm::ExitReason::Succeed(_), _))
-            )
-        )
-    }
-
-    pub fn both_reverted(&self) -> bool {
-        matches!(
-            (self.fe_capture.clone(), self.solidity_capture.clone()),
-            (
-                evm::Capture::Exit((evm::ExitReason::Revert(_), _)),
-                evm::Capture::Exit((evm::ExitReason::Revert(_), _))
-            )
-        )
-    }
-
-    #[allow(dead_code)]
-    pub fn performed_equal(&self) -> bool {
-        self.fe_capture == self.solidity_capture
-    }
-}
-
-impl<'a> DualHarness {
-    pub fn from_fixture(
-        executor: &mut Executor,
-        fixture: &str,
-        contract_name: &str,
-        init_params: &[ethabi::Token],
-    ) -> DualHarness {
-        let fe_harness = test_utils::deploy_contract(
-            executor,
-            &format!("differential/{fixture}.fe"),
-            contract_name,
-            init_params,
-        );
-        let solidity_harness = test_utils::deploy_solidity_contract(
-            executor,
-            &format!("differential/{fixture}.sol"),
-            contract_name,
-            init_params,
-            true,
-        );
-        DualHarness {
-            fe_harness,
-            solidity_harness,
-        }
-    }
-
-    pub fn capture_call(
-        &self,
-        executor: &mut Executor,
-        name: &'a str,
-        input: &'a [ethabi::Token],
-    ) -> CaptureResult<'a> {
-        let initially_used = executor.used_gas();
-        let fe_capture = self.fe_harness.capture_call(executor, name, input);
-        let fe_used_gas = executor.used_gas() - initially_used;
-        let solidity_capture = self.solidity_harness.capture_call(executor, name, input);
-        let solidity_used_gas = executor.used_gas() - fe_used_gas - initially_used;
-
-        CaptureResult {
-            fe_capture,
-            fe_used_gas,
-            solidity_capture,
-            solidity_used_gas,
-            name,
-            input,
-        }
-    }
-}
 
 proptest! {
 
@@ -260,3 +91,27 @@ proptest! {
         });
     }
 }
+
+#[test]
+fn math_u8_boundaries() {
+    with_executor(&|mut executor| {
+        let harness = DualHarness::from_fixture(&mut executor, "math_u8", "Foo", &[]);
+
+        let mut calls = vec![];
+        for (val, val2) in [
+            (0, 0),
+            (1, 0),
+            (0, 1),
+            (255, 1),
+            (255, 255),
+            (16, 2),
+            (2, 8),
+        ] {
+            let input = vec![uint_token(val), uint_token(val2)];
+            for name in ["add", "subtract", "multiply", "divide", "pow", "modulo"] {
+                calls.push((name, input.clone()));
+            }
+        }
+        harness.assert_calls_abi_equal(&mut executor, &calls);
+    });
+}
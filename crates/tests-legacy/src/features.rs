@@ -1507,6 +1507,157 @@ fn can_deploy_fixture(fixture_file: &str, contract_name: &str) {
     })
 }
 
+#[test]
+fn storage_refs() {
+    with_executor(&|mut executor| {
+        let harness = deploy_contract(&mut executor, "storage_refs.fe", "Foo", &[]);
+        harness.test_function(
+            &mut executor,
+            "bar",
+            &[uint_token(10), uint_token(20)],
+            Some(&uint_token(50)),
+        );
+    });
+}
+
+#[test]
+fn calldata_bytes() {
+    with_executor(&|mut executor| {
+        let harness = deploy_contract(&mut executor, "calldata_bytes.fe", "Foo", &[]);
+        let mut data = vec![0; 31];
+        data.push(7);
+        data.extend([9; 8]);
+        let bytes = ethabi::Token::Bytes(data);
+
+        harness.test_function(
+            &mut executor,
+            "length",
+            &[bytes.clone()],
+            Some(&uint_token(40)),
+        );
+        harness.test_function(
+            &mut executor,
+            "sum_between",
+            &[uint_token(1), bytes.clone(), uint_token(2)],
+            Some(&uint_token(82)),
+        );
+        harness.test_function(
+            &mut executor,
+            "first_word",
+            &[bytes.clone()],
+            Some(&uint_token(7)),
+        );
+        harness.test_function(&mut executor, "copy_len", &[bytes], Some(&uint_token(40)));
+    });
+}
+
+#[test]
+fn memory_region() {
+    with_executor(&|mut executor| {
+        let harness = deploy_contract(&mut executor, "memory_region.fe", "Foo", &[]);
+        harness.test_function(
+            &mut executor,
+            "bar",
+            &[uint_token(100)],
+            Some(&bool_token(true)),
+        );
+    });
+}
+
+#[test]
+fn storage_return() {
+    with_executor(&|mut executor| {
+        let harness = deploy_contract(&mut executor, "storage_return.fe", "Foo", &[]);
+        let name = string_token("a name that is long enough to take up two words");
+        let data = ethabi::Token::Bytes((1..=40).collect());
+        harness.test_function(&mut executor, "set_name", &[name.clone()], None);
+        harness.test_function(&mut executor, "set_data", &[data.clone()], None);
+
+        let mut gas_used = |function: &str, expected: &ethabi::Token| {
+            let start_gas = executor.used_gas();
+            harness.test_function(&mut executor, function, &[], Some(expected));
+            executor.used_gas() - start_gas
+        };
+
+        // Returning storage strings and bytes encodes them straight from
+        // storage, instead of copying them to memory first.
+        let name_gas = gas_used("name", &name);
+        let name_copy_gas = gas_used("name_copy", &name);
+        assert!(name_gas < name_copy_gas);
+        let data_gas = gas_used("data", &data);
+        let data_copy_gas = gas_used("data_copy", &data);
+        assert!(data_gas < data_copy_gas);
+
+        harness.test_function(&mut executor, "greeting", &[], Some(&name));
+    });
+}
+
+#[test]
+fn only_attribute() {
+    with_executor(&|mut executor| {
+        let mut harness = deploy_contract(&mut executor, "only_attribute.fe", "Vault", &[]);
+        let alice = "2000000000000000000000000000000000000002";
+        let bob = "3000000000000000000000000000000000000003";
+
+        // The deployer is the owner, but not an admin.
+        harness.test_function(&mut executor, "add_admin", &[address_token(bob)], None);
+        validate_revert(
+            harness.capture_call(&mut executor, "increment", &[]),
+            &encode_revert("Unauthorized(address)", &[address_token(DEFAULT_CALLER)]),
+        );
+
+        harness.set_caller(address(alice));
+        validate_revert(
+            harness.capture_call(&mut executor, "add_admin", &[address_token(alice)]),
+            &encode_revert("Unauthorized(address)", &[address_token(alice)]),
+        );
+
+        harness.set_caller(address(bob));
+        harness.test_function(&mut executor, "increment", &[], Some(&uint_token(1)));
+    });
+}
+
+#[test]
+fn function_guards() {
+    with_executor(&|mut executor| {
+        let vault = deploy_contract(&mut executor, "function_guards.fe", "Vault", &[]);
+        let receiver = deploy_contract(&mut executor, "function_guards.fe", "Receiver", &[]);
+        let receiver_address = ethabi::Token::Address(receiver.address);
+
+        vault.test_function(&mut executor, "increment", &[], Some(&uint_token(1)));
+        vault.test_function(&mut executor, "set_paused", &[bool_token(true)], None);
+        validate_revert(
+            vault.capture_call(&mut executor, "increment", &[]),
+            &encode_revert("Paused()", &[]),
+        );
+        vault.test_function(&mut executor, "set_paused", &[bool_token(false)], None);
+        vault.test_function(&mut executor, "increment", &[], Some(&uint_token(2)));
+
+        // The lock is released when `notify` returns, so it can be called again.
+        let args = [receiver_address];
+        vault.test_function(&mut executor, "notify", &args, Some(&uint_token(3)));
+        vault.test_function(&mut executor, "notify", &args, Some(&uint_token(4)));
+
+        receiver.test_function(&mut executor, "set_reenter", &[bool_token(true)], None);
+        validate_revert(
+            vault.capture_call(&mut executor, "notify", &args),
+            &encode_revert("ReentrantCall()", &[]),
+        );
+    });
+}
+
+#[test]
+fn storage_gap() {
+    with_executor(&|mut executor| {
+        let harness = deploy_contract(&mut executor, "storage_gap.fe", "Vault", &[]);
+
+        harness.test_function(&mut executor, "deposit", &[uint_token(5)], None);
+        harness.test_function(&mut executor, "deposit", &[uint_token(7)], None);
+        harness.test_function(&mut executor, "total", &[], Some(&uint_token(12)));
+        harness.test_function(&mut executor, "total_slot", &[], Some(&uint_token(12)));
+    });
+}
+
 #[test]
 fn self_address() {
     with_executor(&|mut executor| {
@@ -1782,6 +1933,16 @@ fn abi_decode_checks() {
             ];
             harness.test_function_reverts(&mut executor, func_name, &invalid_input, &revert_data);
         }
+
+        // decode_no_args
+        {
+            let data = harness.build_calldata("decode_no_args", &[]);
+
+            // add a byte
+            let mut tampered_data = data;
+            tampered_data.push(42);
+            harness.test_call_reverts(&mut executor, tampered_data, &revert_data);
+        }
     });
 }
 
@@ -1,4 +1,6 @@
 #[cfg(test)]
+mod artifacts;
+#[cfg(test)]
 mod crashes;
 #[cfg(test)]
 mod demo_erc20;
@@ -9,6 +11,8 @@ mod demo_simple_open_auction;
 #[cfg(test)]
 mod demo_uniswap;
 #[cfg(test)]
+mod diagnostics;
+#[cfg(test)]
 mod differential;
 #[cfg(test)]
 mod features;
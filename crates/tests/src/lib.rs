@@ -5,7 +5,7 @@ use std::path::Path;
 use dir_test::{dir_test, Fixture};
 use fe_common::diagnostics::print_diagnostics;
 use fe_common::utils::files::BuildFiles;
-use fe_test_runner::TestSink;
+use fe_test_runner::{TestEnv, TestSink};
 
 #[dir_test(dir: "$CARGO_MANIFEST_DIR/fixtures/files", glob: "*.fe")]
 fn single_file_test_run(fixture: Fixture<&str>) {
@@ -66,3 +66,37 @@ fn ingot_test_run(fixture: Fixture<&str>) {
         }
     }
 }
+
+#[test]
+fn blob_context_test_run() {
+    let src = "#test
+fn test_blob_hash(ctx: Context) {
+    assert ctx.blob_hash(0) == 0x01000000000000000000000000000000000000000000000000000000000000ff
+    assert ctx.blob_hash(1) == 0
+}
+";
+    let mut db = fe_driver::Db::default();
+    let (_, tests) = match fe_driver::compile_single_file_tests(&mut db, "blob.fe", src, true) {
+        Ok(tests) => tests,
+        Err(error) => {
+            print_diagnostics(&db, &error.0);
+            panic!("failed to compile tests")
+        }
+    };
+
+    let mut blob_hash = [0; 32];
+    blob_hash[0] = 0x01;
+    blob_hash[31] = 0xff;
+    let env = TestEnv {
+        blob_hashes: vec![blob_hash],
+        excess_blob_gas: 0,
+    };
+    let mut test_sink = TestSink::new(true);
+    for test in tests {
+        test.execute_in(&env, &mut test_sink);
+    }
+
+    if test_sink.failure_count() != 0 {
+        panic!("{}", test_sink)
+    }
+}
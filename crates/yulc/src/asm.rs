@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Disassembles hex encoded EVM bytecode into one instruction per line.
+///
+/// `labels` maps code offsets to names (e.g. the Yul functions that start at
+/// the offset), which are printed as comments above the instruction at that
+/// offset.
+pub fn disassemble(bytecode: &str, labels: &BTreeMap<usize, String>) -> String {
+    let code = decode_hex(bytecode);
+    let mut asm = String::new();
+
+    let mut pc = 0;
+    while pc < code.len() {
+        if let Some(label) = labels.get(&pc) {
+            writeln!(asm, "\n; {label}").unwrap();
+        }
+
+        let opcode = code[pc];
+        write!(asm, "{pc:04x}: {}", mnemonic(opcode)).unwrap();
+        pc += 1;
+
+        let push_size = push_size(opcode);
+        if push_size > 0 {
+            let end = (pc + push_size).min(code.len());
+            let imm: String = code[pc..end].iter().map(|b| format!("{b:02x}")).collect();
+            write!(asm, " 0x{imm}").unwrap();
+            pc = end;
+        }
+        asm.push('\n');
+    }
+
+    asm
+}
+
+fn decode_hex(bytecode: &str) -> Vec<u8> {
+    let bytecode = bytecode.trim_start_matches("0x");
+    (0..bytecode.len() / 2)
+        .map(|i| u8::from_str_radix(&bytecode[2 * i..2 * i + 2], 16).unwrap_or(0xfe))
+        .collect()
+}
+
+fn push_size(opcode: u8) -> usize {
+    if (0x60..=0x7f).contains(&opcode) {
+        (opcode - 0x5f) as usize
+    } else {
+        0
+    }
+}
+
+fn mnemonic(opcode: u8) -> String {
+    let name = match opcode {
+        0x00 => "STOP",
+        0x01 => "ADD",
+        0x02 => "MUL",
+        0x03 => "SUB",
+        0x04 => "DIV",
+        0x05 => "SDIV",
+        0x06 => "MOD",
+        0x07 => "SMOD",
+        0x08 => "ADDMOD",
+        0x09 => "MULMOD",
+        0x0a => "EXP",
+        0x0b => "SIGNEXTEND",
+        0x10 => "LT",
+        0x11 => "GT",
+        0x12 => "SLT",
+        0x13 => "SGT",
+        0x14 => "EQ",
+        0x15 => "ISZERO",
+        0x16 => "AND",
+        0x17 => "OR",
+        0x18 => "XOR",
+        0x19 => "NOT",
+        0x1a => "BYTE",
+        0x1b => "SHL",
+        0x1c => "SHR",
+        0x1d => "SAR",
+        0x20 => "KECCAK256",
+        0x30 => "ADDRESS",
+        0x31 => "BALANCE",
+        0x32 => "ORIGIN",
+        0x33 => "CALLER",
+        0x34 => "CALLVALUE",
+        0x35 => "CALLDATALOAD",
+        0x36 => "CALLDATASIZE",
+        0x37 => "CALLDATACOPY",
+        0x38 => "CODESIZE",
+        0x39 => "CODECOPY",
+        0x3a => "GASPRICE",
+        0x3b => "EXTCODESIZE",
+        0x3c => "EXTCODECOPY",
+        0x3d => "RETURNDATASIZE",
+        0x3e => "RETURNDATACOPY",
+        0x3f => "EXTCODEHASH",
+        0x40 => "BLOCKHASH",
+        0x41 => "COINBASE",
+        0x42 => "TIMESTAMP",
+        0x43 => "NUMBER",
+        0x44 => "PREVRANDAO",
+        0x45 => "GASLIMIT",
+        0x46 => "CHAINID",
+        0x47 => "SELFBALANCE",
+        0x48 => "BASEFEE",
+        0x50 => "POP",
+        0x51 => "MLOAD",
+        0x52 => "MSTORE",
+        0x53 => "MSTORE8",
+        0x54 => "SLOAD",
+        0x55 => "SSTORE",
+        0x56 => "JUMP",
+        0x57 => "JUMPI",
+        0x58 => "PC",
+        0x59 => "MSIZE",
+        0x5a => "GAS",
+        0x5b => "JUMPDEST",
+        0x5f => "PUSH0",
+        0x60..=0x7f => return format!("PUSH{}", opcode - 0x5f),
+        0x80..=0x8f => return format!("DUP{}", opcode - 0x7f),
+        0x90..=0x9f => return format!("SWAP{}", opcode - 0x8f),
+        0xa0..=0xa4 => return format!("LOG{}", opcode - 0xa0),
+        0xf0 => "CREATE",
+        0xf1 => "CALL",
+        0xf2 => "CALLCODE",
+        0xf3 => "RETURN",
+        0xf4 => "DELEGATECALL",
+        0xf5 => "CREATE2",
+        0xfa => "STATICCALL",
+        0xfd => "REVERT",
+        0xfe => "INVALID",
+        0xff => "SELFDESTRUCT",
+        _ => return format!("UNKNOWN(0x{opcode:02x})"),
+    };
+    name.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disassemble_with_labels() {
+        let labels = BTreeMap::from([(5, "function foo".to_string())]);
+        let asm = disassemble("6080604052005b61ffff56", &labels);
+        assert_eq!(
+            asm,
+            "0000: PUSH1 0x80\n\
+             0002: PUSH1 0x40\n\
+             0004: MSTORE\n\
+             \n; function foo\n\
+             0005: STOP\n\
+             0006: JUMPDEST\n\
+             0007: PUSH2 0xffff\n\
+             000a: JUMP\n"
+        );
+    }
+
+    #[test]
+    fn truncated_push() {
+        assert_eq!(disassemble("61ff", &BTreeMap::new()), "0000: PUSH2 0xff\n");
+    }
+}
@@ -1,4 +1,7 @@
 use indexmap::map::IndexMap;
+use std::collections::BTreeMap;
+
+pub mod asm;
 
 #[derive(Debug)]
 pub struct YulcError(pub String);
@@ -6,6 +9,9 @@ pub struct YulcError(pub String);
 pub struct ContractBytecode {
     pub bytecode: String,
     pub runtime_bytecode: String,
+    /// Maps the offsets of the Yul functions in the runtime bytecode to their
+    /// names. Functions that were inlined by the optimizer are omitted.
+    pub runtime_function_entries: BTreeMap<usize, String>,
 }
 
 /// Compile a map of Yul contracts to a map of bytecode contracts.
@@ -14,26 +20,43 @@ pub struct ContractBytecode {
 pub fn compile(
     contracts: impl Iterator<Item = (impl AsRef<str>, impl AsRef<str>)>,
     optimize: bool,
+    optimizer_steps: Option<&str>,
+    evm_version: Option<&str>,
 ) -> Result<IndexMap<String, ContractBytecode>, YulcError> {
     contracts
         .map(|(name, yul_src)| {
-            compile_single_contract(name.as_ref(), yul_src.as_ref(), optimize, true)
-                .map(|bytecode| (name.as_ref().to_string(), bytecode))
+            compile_single_contract(
+                name.as_ref(),
+                yul_src.as_ref(),
+                optimize,
+                optimizer_steps,
+                evm_version,
+                true,
+            )
+            .map(|bytecode| (name.as_ref().to_string(), bytecode))
         })
         .collect()
 }
 
 #[cfg(feature = "solc-backend")]
 /// Compiles a single Yul contract to bytecode.
+///
+/// If `optimizer_steps` is given, the Yul optimizer runs that step sequence
+/// instead of the default one of solc. If `evm_version` is given, the bytecode
+/// targets that EVM version instead of the default one of solc.
 pub fn compile_single_contract(
     name: &str,
     yul_src: &str,
     optimize: bool,
+    optimizer_steps: Option<&str>,
+    evm_version: Option<&str>,
     verify_runtime_bytecode: bool,
 ) -> Result<ContractBytecode, YulcError> {
     let solc_temp = include_str!("solc_temp.json");
     let input = solc_temp
         .replace("{optimizer_enabled}", &optimize.to_string())
+        .replace("{optimizer_details}", &optimizer_details(optimizer_steps))
+        .replace("{evm_version}", &evm_version_setting(evm_version))
         .replace("{src}", yul_src);
     let raw_output = solc::compile(&input);
     let output: serde_json::Value = serde_json::from_str(&raw_output)
@@ -55,9 +78,22 @@ pub fn compile_single_contract(
         return Err(YulcError(output.to_string()));
     }
 
+    let mut runtime_function_entries = BTreeMap::new();
+    if let Some(debug_data) = output["contracts"]["input.yul"][name]["evm"]["deployedBytecode"]
+        ["functionDebugData"]
+        .as_object()
+    {
+        for (func_name, data) in debug_data {
+            if let Some(entry) = data["entryPoint"].as_u64() {
+                runtime_function_entries.insert(entry as usize, func_name.clone());
+            }
+        }
+    }
+
     Ok(ContractBytecode {
         bytecode,
         runtime_bytecode,
+        runtime_function_entries,
     })
 }
 
@@ -67,6 +103,8 @@ pub fn compile_single_contract(
     _name: &str,
     _yul_src: &str,
     _optimize: bool,
+    _optimizer_steps: Option<&str>,
+    _evm_version: Option<&str>,
     _verify_runtime_bytecode: bool,
 ) -> Result<ContractBytecode, YulcError> {
     // This is ugly, but required (as far as I can tell) to make
@@ -74,6 +112,29 @@ pub fn compile_single_contract(
     panic!("fe-yulc requires 'solc-backend' feature")
 }
 
+/// Returns the `details` entry of the optimizer settings in the solc
+/// standard JSON input.
+#[cfg(feature = "solc-backend")]
+fn optimizer_details(optimizer_steps: Option<&str>) -> String {
+    match optimizer_steps {
+        Some(steps) => format!(
+            r#", "details": {{ "yul": true, "yulDetails": {{ "optimizerSteps": {} }} }}"#,
+            serde_json::Value::from(steps)
+        ),
+        None => String::new(),
+    }
+}
+
+/// Returns the `evmVersion` entry of the settings in the solc standard JSON
+/// input.
+#[cfg(feature = "solc-backend")]
+fn evm_version_setting(evm_version: Option<&str>) -> String {
+    match evm_version {
+        Some(version) => format!(r#" "evmVersion": {},"#, serde_json::Value::from(version)),
+        None => String::new(),
+    }
+}
+
 #[cfg(feature = "solc-backend")]
 #[test]
 fn test_solc_sanity() {
@@ -81,6 +142,8 @@ fn test_solc_sanity() {
     let solc_temp = include_str!("solc_temp.json");
     let input = solc_temp
         .replace("{optimizer_enabled}", "false")
+        .replace("{optimizer_details}", "")
+        .replace("{evm_version}", "")
         .replace("{src}", yul_src);
 
     let raw_output = solc::compile(&input);
@@ -94,3 +157,18 @@ fn test_solc_sanity() {
     // solc 0.8.7: push1 0; dup1;    sstore  "60008055"
     assert_eq!(bytecode, "60008055", "incorrect bytecode",);
 }
+
+#[cfg(feature = "solc-backend")]
+#[test]
+fn test_solc_optimizer_steps() {
+    let yul_src = "{ sstore(0, add(1, 2)) }";
+    let steps = Some("dhfoDgvulfnTUtnIf");
+    let bytecode = compile_single_contract("object", yul_src, true, steps, None, false)
+        .unwrap()
+        .bytecode;
+    assert!(!bytecode.is_empty(), "missing bytecode");
+
+    let invalid_steps = Some("not a step sequence");
+    let result = compile_single_contract("object", yul_src, true, invalid_steps, None, false);
+    assert!(result.is_err(), "invalid step sequence was accepted");
+}